@@ -0,0 +1,145 @@
+//! Benchmarks `augment_batch` of every augmenter on datasets of different numbers and lengths of
+//! series, sequentially so that regressions of single augmenters aren't hidden by rayon.
+//!
+//! Run with `cargo bench --bench augmenters`, or e.g. `cargo bench --bench augmenters -- crop` for
+//! a single augmenter.
+
+mod common;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rats_rs::augmenters::*;
+use rats_rs::transforms::filter::FilterDesign;
+use rats_rs::transforms::stft::{StftParams, WindowFunction};
+use rats_rs::transforms::wavelet::Wavelet;
+
+/// Benchmarks the augmenter `make(len)` creates for series of length `len` over all shapes
+fn bench_augmenter<A, F>(c: &mut Criterion, name: &str, make: F)
+where
+    A: Augmenter + Sync,
+    F: Fn(usize) -> A,
+{
+    let mut group = c.benchmark_group(name);
+    for (n, len) in common::shapes() {
+        let dataset = common::make_dataset(n, len);
+        let augmenter = make(len);
+        group.throughput(Throughput::Elements((n * len) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(common::shape_id(n, len)),
+            &dataset,
+            |b, dataset| {
+                b.iter_batched_ref(
+                    || dataset.clone(),
+                    |set| augmenter.augment_batch(set, false, false),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn noise(c: &mut Criterion) {
+    bench_augmenter(c, "add_noise/uniform", |_| {
+        AddNoise::new(NoiseType::Uniform, Some((-0.1, 0.1)), None, None)
+    });
+    bench_augmenter(c, "add_noise/gaussian", |_| {
+        AddNoise::new(NoiseType::Gaussian, None, Some(0.0), Some(0.1))
+    });
+    bench_augmenter(c, "add_noise/spike", |_| {
+        AddNoise::new(NoiseType::Spike, Some((-2.0, 2.0)), None, None)
+    });
+    bench_augmenter(c, "add_noise/slope", |_| {
+        AddNoise::new(NoiseType::Slope, Some((-0.01, 0.01)), None, None)
+    });
+    bench_augmenter(c, "jittering", |_| Jittering::new(0.1));
+    bench_augmenter(c, "drift", |_| Drift::new(0.5, 5));
+    bench_augmenter(c, "drop", |_| Drop::new(0.1, None));
+}
+
+fn magnitude(c: &mut Criterion) {
+    bench_augmenter(c, "scaling", |_| Scaling::new(0.5, 2.0));
+    bench_augmenter(c, "rotation", |_| Rotation::new(0.0));
+    bench_augmenter(c, "reverse", |_| Reverse::new());
+    bench_augmenter(c, "quantize", |_| Quantize::new(16));
+}
+
+fn time(c: &mut Criterion) {
+    bench_augmenter(c, "crop", |len| Crop::new(len / 2));
+    bench_augmenter(c, "resize", |len| Resize::new(len * 2));
+    bench_augmenter(c, "repeat", |_| Repeat::new(2));
+    bench_augmenter(c, "permutate", |len| Permutate::new(len / 8, 8));
+    bench_augmenter(c, "pool", |_| Pool::new(PoolingMethod::Average, 4));
+    bench_augmenter(c, "convolve/direct", |_| {
+        Convolve::new(ConvolveWindow::Gaussian, 7)
+    });
+    bench_augmenter(c, "convolve/fft", |len| {
+        Convolve::new(ConvolveWindow::Gaussian, len / 4 + 1)
+    });
+    bench_augmenter(c, "random_time_warp", |len| {
+        RandomTimeWarpAugmenter::new(len / 4, (0.5, 2.0))
+    });
+}
+
+fn frequency(c: &mut Criterion) {
+    bench_augmenter(c, "amplitude_phase_perturbation", |_| {
+        AmplitudePhasePerturbation::new(0.1, 0.1, true)
+    });
+    bench_augmenter(c, "frequency_mask", |_| FrequencyMask::new(8, true));
+    bench_augmenter(c, "random_filter/butterworth", |_| {
+        RandomFilter::new(
+            FilterType::LowPass,
+            (0.1, 0.3),
+            FilterDesign::Butterworth { order: 4 },
+        )
+    });
+    bench_augmenter(c, "random_filter/windowed_sinc", |_| {
+        RandomFilter::new(
+            FilterType::BandStop,
+            (0.3, 0.6),
+            FilterDesign::WindowedSinc {
+                num_taps: 31,
+                window: WindowFunction::Hamming,
+            },
+        )
+    });
+    bench_augmenter(c, "spec_augment", |_| {
+        SpecAugment::new(
+            2,
+            4,
+            2,
+            4,
+            Some(4),
+            StftParams::new(32, 8, WindowFunction::Hann, true),
+        )
+    });
+    bench_augmenter(c, "wavelet_drop", |_| {
+        WaveletDrop::new(Wavelet::Daubechies(4), vec![1, 2], 0.2)
+    });
+    bench_augmenter(c, "wavelet_perturbation", |_| {
+        WaveletPerturbation::new(Wavelet::Daubechies(4), vec![1, 2], 0.1)
+    });
+}
+
+fn composition(c: &mut Criterion) {
+    bench_augmenter(c, "one_of", |_| {
+        OneOf::new() + Jittering::new(0.1) + Scaling::new(0.5, 2.0) + Reverse::new()
+    });
+    bench_augmenter(c, "some_of", |_| {
+        SomeOf::new(2) + Jittering::new(0.1) + Scaling::new(0.5, 2.0) + Reverse::new()
+    });
+    bench_augmenter(c, "random_order", |_| {
+        RandomOrder::new() + Jittering::new(0.1) + Scaling::new(0.5, 2.0) + Reverse::new()
+    });
+    bench_augmenter(c, "label_conditional", |_| {
+        LabelConditional::for_labels(Reverse::new(), &["0"])
+    });
+    bench_augmenter(c, "rand_augment", |_| RandAugment::new(2, 0.5));
+    bench_augmenter(c, "trivial_augment", |_| TrivialAugment::new());
+}
+
+criterion_group! {
+    name = benches;
+    config = common::config();
+    targets = noise, magnitude, time, frequency, composition
+}
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+//! Datasets and settings shared by the benchmark suites
+
+#![allow(dead_code)]
+
+use criterion::Criterion;
+use rats_rs::{Dataset, Float};
+use std::time::Duration;
+
+/// Numbers of series of the benchmarked datasets
+pub const SAMPLES: [usize; 2] = [100, 1000];
+
+/// Lengths of the series of the benchmarked datasets
+pub const LENGTHS: [usize; 2] = [128, 1024];
+
+/// All combinations of `SAMPLES` and `LENGTHS`
+pub fn shapes() -> impl Iterator<Item = (usize, usize)> {
+    SAMPLES
+        .into_iter()
+        .flat_map(|n| LENGTHS.into_iter().map(move |len| (n, len)))
+}
+
+/// Id of a benchmark on a dataset of `n` series of length `len`
+pub fn shape_id(n: usize, len: usize) -> String {
+    format!("{}x{}", n, len)
+}
+
+/// Dataset of `n` noisy sine waves of length `len` in two classes
+pub fn make_dataset<T: Float>(n: usize, len: usize) -> Dataset<T> {
+    Dataset {
+        features: (0..n)
+            .map(|k| {
+                (0..len)
+                    .map(|i| {
+                        let t = i as f64 / len as f64;
+                        let noise = ((i * 7919 + k * 104_729) % 1000) as f64 / 5000.0;
+                        T::cast((2.0 * std::f64::consts::PI * (k % 5 + 1) as f64 * t).sin() + noise)
+                    })
+                    .collect()
+            })
+            .collect(),
+        labels: (0..n).map(|k| (k % 2).to_string()).collect(),
+    }
+}
+
+/// Fewer and shorter samples than the defaults of criterion, so the whole suite runs in minutes
+pub fn config() -> Criterion {
+    Criterion::default()
+        .sample_size(20)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+}
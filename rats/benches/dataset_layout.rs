@@ -0,0 +1,119 @@
+//! Compares the nested `Dataset` layout with the contiguous `DenseDataset` on the workloads of the
+//! tests: augmenting single augmenters and a pipeline, and the FFT, DCT and filter transforms.
+//!
+//! Both layouts plan the FFT and DCT once per series length, so the transforms compare only the
+//! layouts.
+//!
+//! Run with `cargo bench --bench dataset_layout`.
+
+mod common;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use rats_rs::augmenters::{AugmentationPipeline, Augmenter, Drift, Jittering, Reverse, Scaling};
+use rats_rs::transforms::dct::{dataset_dct, dense_dct};
+use rats_rs::transforms::fastfourier::{dataset_fft, dense_fft};
+use rats_rs::transforms::filter::{Filter, FilterBand, dataset_filtfilt, dense_filtfilt};
+use rats_rs::{Dataset, DenseDataset};
+use std::hint::black_box;
+
+/// Number of series and their length
+const SHAPES: [(usize, usize); 2] = [(1000, 128), (200, 2048)];
+
+/// Benchmarks augmenting both layouts in place, the copies are made outside of the measurement
+fn bench_augmenter<A: Augmenter + Sync>(c: &mut Criterion, name: &str, augmenter: &A) {
+    let mut group = c.benchmark_group(name);
+    for (n, len) in SHAPES {
+        let dataset: Dataset = common::make_dataset(n, len);
+        let dense = DenseDataset::from_dataset(&dataset);
+        let id = common::shape_id(n, len);
+        for parallel in [false, true] {
+            let mode = if parallel { "parallel" } else { "sequential" };
+            group.bench_with_input(
+                BenchmarkId::new(format!("nested/{}", mode), &id),
+                &dataset,
+                |b, dataset| {
+                    b.iter_batched_ref(
+                        || dataset.clone(),
+                        |set| augmenter.augment_batch(set, parallel, false),
+                        BatchSize::LargeInput,
+                    )
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("dense/{}", mode), &id),
+                &dense,
+                |b, dense| {
+                    b.iter_batched_ref(
+                        || dense.clone(),
+                        |set| augmenter.augment_dense(set, parallel),
+                        BatchSize::LargeInput,
+                    )
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn augmenters(c: &mut Criterion) {
+    bench_augmenter(c, "jittering", &Jittering::new(0.1));
+    bench_augmenter(c, "scaling", &Scaling::new(0.5, 2.0));
+    bench_augmenter(c, "reverse", &Reverse::new());
+    bench_augmenter(
+        c,
+        "pipeline",
+        &(AugmentationPipeline::new()
+            + Jittering::new(0.1)
+            + Scaling::new(0.5, 2.0)
+            + Drift::new(0.5, 5)),
+    );
+}
+
+fn transforms(c: &mut Criterion) {
+    let filter = Filter::butterworth(4, &FilterBand::LowPass(0.2));
+    let mut group = c.benchmark_group("transforms");
+    for (n, len) in SHAPES {
+        let dataset: Dataset = common::make_dataset(n, len);
+        let dense = DenseDataset::from_dataset(&dataset);
+        let id = common::shape_id(n, len);
+        group.bench_function(BenchmarkId::new("fft/nested", &id), |b| {
+            b.iter(|| dataset_fft(black_box(&dataset), true))
+        });
+        group.bench_function(BenchmarkId::new("fft/dense", &id), |b| {
+            b.iter(|| dense_fft(black_box(&dense), true))
+        });
+        group.bench_function(BenchmarkId::new("dct/nested", &id), |b| {
+            b.iter(|| dataset_dct(black_box(&dataset), true))
+        });
+        group.bench_function(BenchmarkId::new("dct/dense", &id), |b| {
+            b.iter(|| dense_dct(black_box(&dense), true))
+        });
+        group.bench_function(BenchmarkId::new("filtfilt/nested", &id), |b| {
+            b.iter(|| dataset_filtfilt(black_box(&dataset), &filter, true))
+        });
+        group.bench_function(BenchmarkId::new("filtfilt/dense", &id), |b| {
+            b.iter(|| dense_filtfilt(black_box(&dense), &filter, true))
+        });
+    }
+    group.finish();
+}
+
+/// The cost of switching between the layouts, e.g. at the numpy boundary
+fn conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conversion");
+    for (n, len) in SHAPES {
+        let dataset: Dataset = common::make_dataset(n, len);
+        let dense = DenseDataset::from_dataset(&dataset);
+        let id = common::shape_id(n, len);
+        group.bench_function(BenchmarkId::new("to_dense", &id), |b| {
+            b.iter(|| DenseDataset::from_dataset(black_box(&dataset)))
+        });
+        group.bench_function(BenchmarkId::new("to_nested", &id), |b| {
+            b.iter(|| black_box(&dense).to_dataset())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, augmenters, transforms, conversion);
+criterion_main!(benches);
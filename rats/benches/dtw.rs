@@ -0,0 +1,36 @@
+//! Benchmarks DTW between pairs of series of the benchmark lengths: the full alignment path, the
+//! linear memory distance, and the distance within a Sakoe-Chiba band of a tenth of the length.
+//! The throughput counts the cells of the full cost matrix.
+//!
+//! Run with `cargo bench --bench dtw`.
+
+mod common;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rats_rs::quality_benchmarking::{DtwConstraint, dtw, dtw_distance};
+
+fn dtw_pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dtw");
+    for len in common::LENGTHS {
+        let dataset = common::make_dataset::<f64>(2, len);
+        let (a, b) = (&dataset.features[0], &dataset.features[1]);
+        group.throughput(Throughput::Elements((len * len) as u64));
+        group.bench_function(BenchmarkId::new("path", len), |bench| {
+            bench.iter(|| dtw(a, b))
+        });
+        group.bench_function(BenchmarkId::new("distance", len), |bench| {
+            bench.iter(|| dtw_distance(a, b, DtwConstraint::None, None))
+        });
+        group.bench_function(BenchmarkId::new("sakoe_chiba", len), |bench| {
+            bench.iter(|| dtw_distance(a, b, DtwConstraint::SakoeChiba(len / 10), None))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = common::config();
+    targets = dtw_pairs
+}
+criterion_main!(benches);
@@ -0,0 +1,151 @@
+//! Compares the vectorized in-place elementwise augmenters with their previous implementations,
+//! which collected into a new series and drew one random number at a time.
+//!
+//! Run with `cargo bench --bench elementwise`, add `--features simd` on a nightly compiler to
+//! compare the `std::simd` kernels.
+
+mod common;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::distr::Uniform;
+use rand::prelude::*;
+use rand_distr::Normal;
+use rats_rs::augmenters::{
+    AddNoise, Augmenter, Drift, Drop, Jittering, NoiseType, Rotation, Scaling,
+};
+
+/// Number of series and their length
+const SHAPES: [(usize, usize); 2] = [(1000, 128), (50, 4096)];
+
+fn previous_jittering(x: &[f64], deviation: f64) -> Vec<f64> {
+    let mut rng = rand::rng();
+    let dist = Normal::new(0.0, deviation).unwrap();
+    x.iter().map(|val| *val + dist.sample(&mut rng)).collect()
+}
+
+fn previous_uniform_noise(x: &[f64], bounds: (f64, f64)) -> Vec<f64> {
+    let mut rng = rand::rng();
+    let dist = Uniform::new(bounds.0, bounds.1).unwrap();
+    x.iter().map(|val| *val + dist.sample(&mut rng)).collect()
+}
+
+fn previous_scaling(x: &[f64], min: f64, max: f64) -> Vec<f64> {
+    let scalar = rand::random_range(min..=max);
+    x.iter().map(|val| *val * scalar).collect()
+}
+
+fn previous_rotation(x: &[f64], anchor: f64) -> Vec<f64> {
+    x.iter().map(|val| -(*val - anchor) + anchor).collect()
+}
+
+fn previous_drift(x: &[f64], max_drift: f64, n_drift_points: usize) -> Vec<f64> {
+    let len = x.len();
+    let mut rng = rand::rng();
+    let n = n_drift_points.min(len);
+    let drift_points: Vec<f64> = (0..n)
+        .map(|_| rng.random_range(-max_drift..=max_drift))
+        .collect();
+    let mut drift = vec![0.0; len];
+    let seg_len = len as f64 / (n - 1) as f64;
+    for (i, d) in drift.iter_mut().enumerate() {
+        let pos = i as f64 / seg_len;
+        let left = pos.floor() as usize;
+        let right = pos.ceil() as usize;
+        let alpha = pos - left as f64;
+        *d = (1.0 - alpha) * drift_points[left.min(n - 1)] + alpha * drift_points[right.min(n - 1)];
+    }
+    x.iter()
+        .zip(drift.iter())
+        .map(|(xi, di)| *xi + *di)
+        .collect()
+}
+
+fn previous_drop(x: &[f64], percentage: f64, default: f64) -> Vec<f64> {
+    x.iter()
+        .map(|val| {
+            if rand::random::<f64>() < percentage {
+                default
+            } else {
+                *val
+            }
+        })
+        .collect()
+}
+
+/// Benchmarks the previous implementation against `augment_batch` in double and single precision,
+/// sequentially so that the kernels are measured and not the scheduling of rayon
+fn bench_augmenter<A, F>(c: &mut Criterion, name: &str, augmenter: &A, previous: F)
+where
+    A: Augmenter<f64> + Augmenter<f32> + Sync,
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let mut group = c.benchmark_group(name);
+    for (n, len) in SHAPES {
+        let dataset = common::make_dataset::<f64>(n, len);
+        let single = common::make_dataset::<f32>(n, len);
+        let id = common::shape_id(n, len);
+        group.bench_with_input(BenchmarkId::new("previous", &id), &dataset, |b, dataset| {
+            b.iter_batched_ref(
+                || dataset.clone(),
+                |set| set.features.iter_mut().for_each(|x| *x = previous(x)),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(
+            BenchmarkId::new("vectorized/f64", &id),
+            &dataset,
+            |b, dataset| {
+                b.iter_batched_ref(
+                    || dataset.clone(),
+                    |set| augmenter.augment_batch(set, false, false),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("vectorized/f32", &id),
+            &single,
+            |b, single| {
+                b.iter_batched_ref(
+                    || single.clone(),
+                    |set| augmenter.augment_batch(set, false, false),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn augmenters(c: &mut Criterion) {
+    bench_augmenter(c, "jittering", &Jittering::new(0.1), |x| {
+        previous_jittering(x, 0.1)
+    });
+    bench_augmenter(
+        c,
+        "addnoise_gaussian",
+        &AddNoise::new(NoiseType::Gaussian, None, Some(0.0), Some(0.1)),
+        |x| previous_jittering(x, 0.1),
+    );
+    bench_augmenter(
+        c,
+        "addnoise_uniform",
+        &AddNoise::new(NoiseType::Uniform, Some((-0.1, 0.1)), None, None),
+        |x| previous_uniform_noise(x, (-0.1, 0.1)),
+    );
+    bench_augmenter(c, "scaling", &Scaling::new(0.5, 2.0), |x| {
+        previous_scaling(x, 0.5, 2.0)
+    });
+    bench_augmenter(c, "rotation", &Rotation::new(0.5), |x| {
+        previous_rotation(x, 0.5)
+    });
+    bench_augmenter(c, "drift", &Drift::new(0.5, 5), |x| {
+        previous_drift(x, 0.5, 5)
+    });
+    bench_augmenter(c, "drop", &Drop::new(0.1, None), |x| {
+        previous_drop(x, 0.1, 0.0)
+    });
+}
+
+criterion_group!(benches, augmenters);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+//! Benchmarks an `AugmentationPipeline` in batch and per-sample mode, each sequentially and in
+//! parallel, and creating augmented copies with `augment_copies`.
+//!
+//! Run with `cargo bench --bench pipeline`.
+
+mod common;
+
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rats_rs::augmenters::*;
+
+/// A pipeline of augmenters which all support per-sample chaining and keep the length
+fn make_pipeline() -> AugmentationPipeline {
+    let mut reverse = Reverse::new();
+    reverse.set_probability(0.5);
+    AugmentationPipeline::new()
+        + Jittering::new(0.1)
+        + Scaling::new(0.5, 2.0)
+        + Drift::new(0.5, 5)
+        + Convolve::new(ConvolveWindow::Gaussian, 7)
+        + reverse
+}
+
+fn modes(c: &mut Criterion) {
+    let pipeline = make_pipeline();
+    let mut group = c.benchmark_group("pipeline");
+    for (n, len) in common::shapes() {
+        let dataset = common::make_dataset(n, len);
+        let id = common::shape_id(n, len);
+        group.throughput(Throughput::Elements((n * len) as u64));
+        for per_sample in [false, true] {
+            for parallel in [false, true] {
+                let mode = format!(
+                    "{}/{}",
+                    if per_sample { "per_sample" } else { "batch" },
+                    if parallel { "parallel" } else { "sequential" }
+                );
+                group.bench_with_input(BenchmarkId::new(mode, &id), &dataset, |b, dataset| {
+                    b.iter_batched_ref(
+                        || dataset.clone(),
+                        |set| pipeline.augment_batch(set, parallel, per_sample),
+                        BatchSize::LargeInput,
+                    )
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+fn copies(c: &mut Criterion) {
+    let pipeline = make_pipeline();
+    let mut group = c.benchmark_group("pipeline/augment_copies");
+    for (n, len) in common::shapes() {
+        let dataset = common::make_dataset(n, len);
+        group.throughput(Throughput::Elements((4 * n * len) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(common::shape_id(n, len)),
+            &dataset,
+            |b, dataset| b.iter(|| pipeline.augment_copies(dataset, 4, false, true, true)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = common::config();
+    targets = modes, copies
+}
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+//! Benchmarks the FFT and DCT of whole datasets and their inverses, sequentially and in parallel.
+//!
+//! Run with `cargo bench --bench transforms`.
+
+mod common;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use rats_rs::Dataset;
+use rats_rs::transforms::dct::{dataset_dct, dataset_idct};
+use rats_rs::transforms::fastfourier::{dataset_fft, dataset_ifft};
+
+/// Benchmarks `transform` on the datasets `prepare` makes from the benchmark datasets
+fn bench_transform<P, F>(c: &mut Criterion, name: &str, prepare: P, transform: F)
+where
+    P: Fn(&Dataset) -> Dataset,
+    F: Fn(&Dataset, bool) -> Dataset,
+{
+    let mut group = c.benchmark_group(name);
+    for (n, len) in common::shapes() {
+        let dataset = prepare(&common::make_dataset(n, len));
+        let id = common::shape_id(n, len);
+        group.throughput(Throughput::Elements((n * len) as u64));
+        for parallel in [false, true] {
+            let mode = if parallel { "parallel" } else { "sequential" };
+            group.bench_with_input(BenchmarkId::new(mode, &id), &dataset, |b, dataset| {
+                b.iter(|| transform(dataset, parallel))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn transforms(c: &mut Criterion) {
+    bench_transform(c, "fft", Dataset::clone, dataset_fft);
+    bench_transform(c, "ifft", |d| dataset_fft(d, true), dataset_ifft);
+    bench_transform(c, "dct", Dataset::clone, dataset_dct);
+    bench_transform(c, "idct", |d| dataset_dct(d, true), dataset_idct);
+}
+
+criterion_group! {
+    name = benches;
+    config = common::config();
+    targets = transforms
+}
+criterion_main!(benches);
@@ -1,6 +1,9 @@
-use super::base::Augmenter;
-use rand::{distr::Uniform, prelude::*, random_range};
-use rand_distr::Normal;
+use super::base::{Augmenter, AugmenterBase};
+use super::simd;
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
 use  tracing::{info_span};
 
 /// Augmenter that allows different types of noise injection
@@ -45,56 +48,70 @@ impl AddNoise {
     }
 }
 
-impl Augmenter for AddNoise {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for AddNoise {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         match self.noise_type {
             NoiseType::Uniform => {
                 let bounds = self.bounds.expect("Bounds not specified");
-
-                let mut rng = rand::rng();
-                let dist = Uniform::new(bounds.0, bounds.1)
-                    .expect("Couldn't create uniform distribution from specified bounds");
-                x.iter().map(|val| *val + dist.sample(&mut rng)).collect()
+                assert!(
+                    bounds.0 < bounds.1 && (bounds.1 - bounds.0).is_finite(),
+                    "Couldn't create uniform distribution from specified bounds"
+                );
+                simd::add_uniform(&mut simd::series_rng(), x, bounds.0, bounds.1);
             }
             NoiseType::Gaussian => {
                 let mean = self.mean.expect("Mean not specified");
                 let std_dev = self.std_dev.expect("Standard deviation not specified");
-
-                let mut rng = rand::rng();
-                let dist = Normal::new(mean, std_dev)
-                    .expect("Couldn't create normal distribution from specified mean and standard deviation");
-                x.iter().map(|val| *val + dist.sample(&mut rng)).collect()
+                assert!(
+                    std_dev >= 0.0 && std_dev.is_finite(),
+                    "Couldn't create normal distribution from specified mean and standard deviation"
+                );
+                simd::add_normal(&mut simd::series_rng(), x, mean, std_dev);
             }
             NoiseType::Spike => {
                 let bounds = self.bounds.expect("Bounds not specified");
 
                 // Calculate std dev of x
                 let n = x.len() as f64;
-                let mean = x.iter().sum::<f64>() / n;
-                let std_dev = (x.iter().map(|&val| (val - mean).powi(2)).sum::<f64>() / n).sqrt();
+                let mean = x.iter().map(|&val| val.as_f64()).sum::<f64>() / n;
+                let std_dev = (x
+                    .iter()
+                    .map(|&val| (val.as_f64() - mean).powi(2))
+                    .sum::<f64>()
+                    / n)
+                    .sqrt();
 
                 // Add spike in random location with random magnitude
-                let idx: usize = random_range(0..n as usize);
-                let magnitude: f64 = random_range(bounds.0..bounds.1);
+                let idx: usize = rng().random_range(0..n as usize);
+                let magnitude: f64 = rng().random_range(bounds.0..bounds.1);
+                provenance::record("index", idx);
+                provenance::record("magnitude", magnitude);
 
-                let mut res = x.to_vec();
-                res[idx] = magnitude * std_dev;
-                res
+                x[idx] = T::cast(magnitude * std_dev);
             }
             NoiseType::Slope => {
                 let bounds = self.bounds.expect("Bounds not specified");
 
-                let slope: f64 = random_range(bounds.0..bounds.1);
-                x.iter()
+                let slope: f64 = rng().random_range(bounds.0..bounds.1);
+                provenance::record("slope", slope);
+                x.iter_mut()
                     .enumerate()
-                    .map(|(i, val)| *val + i as f64 * slope)
-                    .collect()
+                    .for_each(|(i, val)| *val += T::cast(i as f64 * slope));
             }
         }
+        true
     }
+}
 
+impl AugmenterBase for AddNoise {
     fn get_probability(&self) -> f64 {
         self.p
     }
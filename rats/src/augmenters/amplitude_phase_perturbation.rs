@@ -1,7 +1,8 @@
-use super::base::Augmenter;
-use crate::Dataset;
+use super::base::{Augmenter, AugmenterBase, BatchRows, gate};
+use crate::{Dataset, Float, provenance};
 use crate::transforms::fastfourier::{dataset_fft, dataset_ifft};
-use rand::{Rng, rng};
+use crate::random::rng;
+use rand::Rng;
 use rand_distr::{Distribution, Normal};
 use tracing::{info_span};
 
@@ -31,32 +32,30 @@ impl AmplitudePhasePerturbation {
     }
 }
 
-impl Augmenter for AmplitudePhasePerturbation {
-    fn augment_batch(&self, data: &mut Dataset, _parallel: bool, _per_sample: bool) {
+impl<T: Float> Augmenter<T> for AmplitudePhasePerturbation {
+    fn augment_batch(&self, data: &mut Dataset<T>, _parallel: bool, _per_sample: bool) {
         // tracing::info!("Rust: augment_batch called with per_sample = {}", per_sample);
         let span = info_span!("", component = self.get_name());
         let _enter = span.enter();
         if self.is_time_domain {
             let mut transformed_dataset = dataset_fft(data, true);
 
-            transformed_dataset.features.iter_mut().for_each(|sample| {
-                if self.get_probability() > rng().random() {
-                    *sample = self.augment_one(sample)
-                }
+            let rows = BatchRows::new();
+            transformed_dataset.features.iter_mut().enumerate().for_each(|(i, sample)| {
+                rows.row(i, || gate(self, || *sample = self.augment_one(sample)))
             });
 
             let inverse_dataset = dataset_ifft(&transformed_dataset, true);
             *data = inverse_dataset;
         } else {
-            data.features.iter_mut().for_each(|sample| {
-                if self.get_probability() > rng().random() {
-                    *sample = self.augment_one(sample)
-                }
+            let rows = BatchRows::new();
+            data.features.iter_mut().enumerate().for_each(|(i, sample)| {
+                rows.row(i, || gate(self, || *sample = self.augment_one(sample)))
             });
         }
     }
 
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         let num_bins = x.len() / 2;
@@ -65,6 +64,10 @@ impl Augmenter for AmplitudePhasePerturbation {
         let phase_noise = Normal::new(0.0, self.phase_std).unwrap();
 
         let mut x = x.to_vec();
+        // The noise of every bin is only kept to report it when the series is recorded
+        let records = provenance::records();
+        let mut mag_noises = Vec::new();
+        let mut phase_noises = Vec::new();
 
         for bin in 0..num_bins {
             let re_idx = 2 * bin;
@@ -77,17 +80,26 @@ impl Augmenter for AmplitudePhasePerturbation {
             let phase = im.atan2(re);
 
             // Add noise
-            let mag_perturbed = (mag + mag_noise.sample(&mut rng)).max(0.0);
-            let phase_perturbed = phase + phase_noise.sample(&mut rng);
+            let (mag_delta, phase_delta) = (mag_noise.sample(&mut rng), phase_noise.sample(&mut rng));
+            if records {
+                mag_noises.push(mag_delta);
+                phase_noises.push(phase_delta);
+            }
+            let mag_perturbed = (mag + T::cast(mag_delta)).max(T::zero());
+            let phase_perturbed = phase + T::cast(phase_delta);
 
             // Convert back to cartesian
             x[re_idx] = mag_perturbed * phase_perturbed.cos();
             x[im_idx] = mag_perturbed * phase_perturbed.sin();
         }
+        provenance::record("magnitude_noise", mag_noises.as_slice());
+        provenance::record("phase_noise", phase_noises.as_slice());
 
         x
     }
+}
 
+impl AugmenterBase for AmplitudePhasePerturbation {
     fn get_probability(&self) -> f64 {
         self.p
     }
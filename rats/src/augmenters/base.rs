@@ -1,59 +1,224 @@
-use crate::Dataset;
+use super::label_conditional::LabelConditional;
+use crate::provenance::{self, BatchLog, Provenance};
+use crate::random::{BatchSeed, rng};
+use crate::{Dataset, DenseDataset, Float};
 use rand::prelude::*;
-use rand::rng;
 use rayon::prelude::*;
+use std::collections::BTreeMap;
 use std::ops::Add;
 use tracing::info_span;
 
+/// Name and probability of an augmenter, which every augmenter implements besides `Augmenter`
+///
+/// Separate from `Augmenter` because they don't depend on the precision of the data.
+pub trait AugmenterBase {
+    /// Get the probability that this augmenter will augment a series in a batch
+    fn get_probability(&self) -> f64;
+
+    /// By setting a probability with this function the augmenter will only augment a series in a
+    /// batch with the specified probability
+    fn set_probability(&mut self, probability: f64);
+
+    fn get_name(&self) -> String;
+
+    /// Indicate whether this augmenter supports per-sample chaining.
+    /// By default, return true. Augmenters that need a batch level view
+    /// should override this to return false.
+    fn supports_per_sample(&self) -> bool {
+        true
+    }
+}
+
 /// Trait for all augmenters, allows for augmentation of one time series or a batch
-pub trait Augmenter {
+///
+/// Generic over the `Float` type of the values, which is `f64` by default. The built-in augmenters
+/// implement it for both `f32` and `f64`.
+pub trait Augmenter<T: Float = f64>: AugmenterBase {
     /// Augment a whole batch
     ///
     /// Parallelized using rayon when `parallell` is set
-    fn augment_batch(&self, input: &mut Dataset, parallel: bool, _per_sample: bool)
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, _per_sample: bool)
     where
         Self: Sync,
     {
         let span = info_span!("", component = self.get_name());
         let _enter = span.enter();
+        let rows = BatchRows::new();
+        let augment = |(i, x): (usize, &mut Vec<T>)| {
+            rows.row(i, || {
+                gate(self, || {
+                    if !self.augment_one_in_place(x) {
+                        *x = self.augment_one(x)
+                    }
+                })
+            })
+        };
         if parallel {
             tracing::info!("Rust: parallel augment_batch called");
-            input.features.par_iter_mut().for_each(|x| {
-                if self.get_probability() > rng().random() {
-                    *x = self.augment_one(x)
-                }
-            });
+            input.features.par_iter_mut().enumerate().for_each(augment);
         } else {
-            input.features.iter_mut().for_each(|x| {
-                if self.get_probability() > rng().random() {
-                    *x = self.augment_one(x)
-                }
-            });
+            input.features.iter_mut().enumerate().for_each(augment);
         }
     }
 
-    /// Augment one time series
+    /// Augment a batch stored contiguously, in place
     ///
-    /// When called, the augmenter will always augment the series no matter what the probability for this augmenter is
-    fn augment_one(&self, x: &[f64]) -> Vec<f64>;
+    /// Each sample is augmented with the probability of this augmenter and each of its channels is
+    /// augmented as a series of its own, so random parameters such as a scaling factor are drawn
+    /// again for every channel. The augmenter must keep the length of the series. Channels are
+    /// written in place by `augment_one_in_place`, augmenters without it allocate a new series per
+    /// channel through `augment_one_with_label`, which is copied back.
+    /// Augmenters that need a batch level view augment a copy of the series as a `Dataset`, which is
+    /// written back. Parallelized over the samples using rayon when `parallel` is set.
+    fn augment_dense(&self, input: &mut DenseDataset<T>, parallel: bool)
+    where
+        Self: Sync,
+    {
+        let span = info_span!("", component = self.get_name());
+        let _enter = span.enter();
+        if !self.supports_per_sample() {
+            let mut dataset = input.to_dataset();
+            self.augment_batch(&mut dataset, parallel, false);
+            input.copy_from_dataset(&dataset);
+            return;
+        }
 
-    /// Get the probability that this augmenter will augment a series in a batch
-    fn get_probability(&self) -> f64;
+        let length = input.series_length();
+        let rows = BatchRows::new();
+        input.for_each_sample(parallel, |i, sample, label| {
+            rows.row(i, || {
+                gate(self, || {
+                    for x in sample.chunks_exact_mut(length) {
+                        if self.augment_one_in_place(x) {
+                            continue;
+                        }
+                        let res = self.augment_one_with_label(x, label);
+                        assert_eq!(
+                            res.len(),
+                            length,
+                            "Augmenter '{}' changes the length of the series and cannot augment a DenseDataset",
+                            self.get_name()
+                        );
+                        x.copy_from_slice(&res);
+                    }
+                })
+            })
+        });
+    }
 
-    /// By setting a probability with this function the augmenter will only augment a series in a
-    /// batch with the specified probability
-    fn set_probability(&mut self, probability: f64);
+    /// Augment a copy of a batch, leaving `input` untouched
+    ///
+    /// Short for `augment_copies(input, 1, false, parallel, per_sample)`
+    fn augment_to_new(
+        &self,
+        input: &Dataset<T>,
+        parallel: bool,
+        per_sample: bool,
+    ) -> AugmentedDataset<T>
+    where
+        Self: Sync,
+    {
+        self.augment_copies(input, 1, false, parallel, per_sample)
+    }
 
-    fn get_name(&self) -> String;
+    /// Create `k` augmented copies of every series of a batch, leaving `input` untouched
+    ///
+    /// The result holds the first copy of all series in their original order, then the second copy
+    /// and so on. If `keep_original` is set, the unaugmented series follow at the end. Every series is
+    /// copied exactly once into a result that is allocated once, so this replaces cloning the dataset
+    /// and adding `Repeat` to the augmenters.
+    ///
+    /// The augmenter must not change the number of series.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rats_rs::Dataset;
+    /// use rats_rs::augmenters::*;
+    ///
+    /// let set = Dataset {
+    ///     features: vec![vec![1.0; 10], vec![2.0; 10]],
+    ///     labels: vec![String::from("a"), String::from("b")],
+    /// };
+    ///
+    /// let augmented = Jittering::new(0.1).augment_copies(&set, 3, true, true, false);
+    ///
+    /// assert_eq!(augmented.dataset.features.len(), 8);
+    /// assert_eq!(augmented.source, vec![0, 1, 0, 1, 0, 1, 0, 1]);
+    /// assert_eq!(augmented.dataset.features[7], set.features[1]);
+    /// ```
+    fn augment_copies(
+        &self,
+        input: &Dataset<T>,
+        k: usize,
+        keep_original: bool,
+        parallel: bool,
+        per_sample: bool,
+    ) -> AugmentedDataset<T>
+    where
+        Self: Sync,
+    {
+        let n = input.features.len();
+        let total = n * (k + usize::from(keep_original));
+        let mut dataset = Dataset {
+            features: Vec::with_capacity(total),
+            labels: Vec::with_capacity(total),
+        };
+        for _ in 0..k {
+            dataset.features.extend(input.features.iter().cloned());
+            dataset.labels.extend(input.labels.iter().cloned());
+        }
 
-    /// Indicate whether this augmenter supports per-sample chaining.
-    /// By default, return true. Augmenters that need a batch level view
-    /// should override this to return false.
-    fn supports_per_sample(&self) -> bool {
-        true
+        self.augment_batch(&mut dataset, parallel, per_sample);
+        assert_eq!(
+            dataset.features.len(),
+            n * k,
+            "Augmenter '{}' changes the number of series, use the number of copies instead",
+            self.get_name()
+        );
+
+        if keep_original {
+            dataset.features.extend(input.features.iter().cloned());
+            dataset.labels.extend(input.labels.iter().cloned());
+        }
+        AugmentedDataset {
+            dataset,
+            source: (0..total).map(|i| i % n.max(1)).collect(),
+        }
+    }
+
+    /// Augment one time series
+    ///
+    /// When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+    fn augment_one(&self, x: &[T]) -> Vec<T>;
+
+    /// Augment one time series in place, keeping its length
+    ///
+    /// Returns whether the series was augmented. Used by `augment_batch`, `augment_dense` and
+    /// per-sample chaining, which call `augment_one` when it returns false, as it does by default.
+    /// Elementwise augmenters override it to write into the series instead of allocating a new one.
+    /// Augmenters that override `augment_one_with_label` must not override it.
+    fn augment_one_in_place(&self, _x: &mut [T]) -> bool {
+        false
+    }
+
+    /// Augment one time series that belongs to the class `label`
+    ///
+    /// Used when chaining augmenters per sample. Augmenters that depend on the label override this,
+    /// all others ignore the label and call `augment_one`.
+    fn augment_one_with_label(&self, x: &[T], _label: &str) -> Vec<T> {
+        self.augment_one(x)
     }
 }
 
+/// A dataset created from another one by `augment_to_new` or `augment_copies`
+///
+/// Series `i` of `dataset` was created from series `source[i]` of the input dataset.
+pub struct AugmentedDataset<T = f64> {
+    pub dataset: Dataset<T>,
+    pub source: Vec<usize>,
+}
+
 /// A pipeline of augmenters
 ///
 /// Executes many augmenters at once
@@ -80,18 +245,20 @@ pub trait Augmenter {
 ///  assert_eq!(set.features.len(), 5);
 ///  assert_eq!(set.features[3].len(), 20);
 /// ```
-pub struct AugmentationPipeline {
+pub struct AugmentationPipeline<T: Float = f64> {
     pub name: String,
-    augmenters: Vec<Box<dyn Augmenter + Sync>>,
+    augmenters: Vec<Box<dyn Augmenter<T> + Sync>>,
+    class_pipelines: BTreeMap<String, AugmentationPipeline<T>>,
     p: f64,
 }
 
-impl AugmentationPipeline {
+impl<T: Float> AugmentationPipeline<T> {
     /// Creates an empty pipeline
     pub fn new() -> Self {
         AugmentationPipeline {
             name: "AugmentationPipeline".to_string(),
             augmenters: Vec::new(),
+            class_pipelines: BTreeMap::new(),
             p: 1.0,
         }
     }
@@ -99,13 +266,75 @@ impl AugmentationPipeline {
     /// Add an augmenter to the pipeline
     ///
     /// Has the same effect as using the `+` operator
-    pub fn add<T: Augmenter + 'static + Sync>(&mut self, augmenter: T) {
+    pub fn add<A: Augmenter<T> + 'static + Sync>(&mut self, augmenter: A) {
         self.augmenters.push(Box::new(augmenter));
     }
-}
 
-impl Augmenter for AugmentationPipeline {
-    fn augment_batch(&self, input: &mut Dataset, parallel: bool, per_sample: bool) {
+    /// Add an augmenter that only augments series whose label is one of `labels`
+    ///
+    /// Short for adding `LabelConditional::for_labels(augmenter, labels)`
+    pub fn add_for_labels<A: Augmenter<T> + 'static + Sync>(&mut self, augmenter: A, labels: &[&str]) {
+        self.add(LabelConditional::for_labels(augmenter, labels));
+    }
+
+    /// Augment all series of class `label` with `pipeline` instead of the augmenters of this pipeline
+    ///
+    /// Class pipelines may change the number of series, e.g. to oversample a rare class with `Repeat`.
+    /// If any of them does, the augmented dataset holds the series without a class pipeline first,
+    /// followed by the series of each class with a class pipeline in the order of their labels.
+    /// Otherwise all series keep their position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rats_rs::Dataset;
+    /// use rats_rs::augmenters::*;
+    ///
+    /// let mut set = Dataset {
+    ///     features: vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]],
+    ///     labels: vec![String::from("rising"), String::from("falling")],
+    /// };
+    ///
+    /// let mut pipeline = AugmentationPipeline::new() + Reverse::new();
+    /// pipeline.set_class_pipeline("rising", AugmentationPipeline::new() + Scaling::new(2.0, 2.0));
+    /// pipeline.augment_batch(&mut set, true, false);
+    ///
+    /// assert_eq!(set.features[0], vec![2.0, 4.0, 6.0]);
+    /// assert_eq!(set.features[1], vec![1.0, 2.0, 3.0]);
+    /// ```
+    pub fn set_class_pipeline(&mut self, label: &str, pipeline: AugmentationPipeline<T>) {
+        self.class_pipelines.insert(label.to_string(), pipeline);
+    }
+
+    /// The augmenters of the pipeline in the order they are applied
+    pub fn augmenters(&self) -> &[Box<dyn Augmenter<T> + Sync>] {
+        &self.augmenters
+    }
+
+    /// Augment a whole batch like `augment_batch` and record what happened to every series
+    ///
+    /// Returns the provenance of every series of the augmented batch: the series of `input` it was
+    /// created from, every augmenter that drew whether to augment it and the parameters it drew. See
+    /// the `provenance` module.
+    pub fn augment_batch_with_provenance(
+        &self,
+        input: &mut Dataset<T>,
+        parallel: bool,
+        per_sample: bool,
+    ) -> Provenance {
+        let (_, rows) = provenance::collect(input.features.len(), || {
+            self.augment_batch(input, parallel, per_sample)
+        });
+        assert_eq!(
+            rows.len(),
+            input.features.len(),
+            "An augmenter changes the number of series without reporting it with `provenance::map_rows`"
+        );
+        Provenance { rows }
+    }
+
+    /// Augments the whole batch with the augmenters of this pipeline, ignoring class pipelines
+    fn augment_all(&self, input: &mut Dataset<T>, parallel: bool, per_sample: bool) {
         if per_sample {
             // Compatibility check : reject if any augmenter has per-sample chaining disabled in pipeline
             for augmenter in &self.augmenters {
@@ -116,27 +345,36 @@ impl Augmenter for AugmentationPipeline {
                     );
                 }
             }
-            tracing::info!("Rust: augment_batch called with per_sample = {}", per_sample);
-            if parallel {
-                input.features.par_iter_mut().for_each(|sample| {
-                    let mut chain = sample.to_vec();
+            tracing::info!(
+                "Rust: augment_batch called with per_sample = {}",
+                per_sample
+            );
+            let labels = &input.labels;
+            let rows = BatchRows::new();
+            let augment_sample = |(i, sample): (usize, &mut Vec<T>)| {
+                let label = labels.get(i).map_or("", String::as_str);
+                rows.row(i, || {
                     for augmenter in self.augmenters.iter() {
-                        if augmenter.get_probability() > rng().random() {
-                            chain = augmenter.augment_one(&chain);
-                        }
+                        gate(augmenter.as_ref(), || {
+                            if !augmenter.augment_one_in_place(sample) {
+                                *sample = augmenter.augment_one_with_label(sample, label);
+                            }
+                        })
                     }
-                    *sample = chain;
-                });
+                })
+            };
+            if parallel {
+                input
+                    .features
+                    .par_iter_mut()
+                    .enumerate()
+                    .for_each(augment_sample);
             } else {
-                input.features.iter_mut().for_each(|sample| {
-                    let mut chain = sample.to_vec();
-                    for augmenter in self.augmenters.iter() {
-                        if augmenter.get_probability() > rng().random() {
-                            chain = augmenter.augment_one(&chain);
-                        }
-                    }
-                    *sample = chain;
-                });
+                input
+                    .features
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(augment_sample);
             }
         } else {
             // Existing batch approach: each augmenter processes the entire dataset in sequence
@@ -145,15 +383,184 @@ impl Augmenter for AugmentationPipeline {
                 .for_each(|augmenter| augmenter.augment_batch(input, parallel, false));
         }
     }
+}
 
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+/// Generators and provenance of the series of one batch
+pub(super) struct BatchRows {
+    seed: BatchSeed,
+    log: BatchLog,
+}
+
+impl BatchRows {
+    /// Starts a new batch on the calling thread
+    pub(super) fn new() -> Self {
+        BatchRows {
+            seed: BatchSeed::next(),
+            log: BatchLog::new(),
+        }
+    }
+
+    /// Runs `f` with the generator of series `row` of this batch, recording its provenance
+    pub(super) fn row<R>(&self, row: usize, f: impl FnOnce() -> R) -> R {
+        self.seed.row(row, || self.log.row(row, f))
+    }
+}
+
+/// Runs `f` on the current series if it passes the probability of `augmenter`, recording the step
+pub(super) fn gate<A: AugmenterBase + ?Sized>(augmenter: &A, f: impl FnOnce()) {
+    let applied = augmenter.get_probability() > rng().random();
+    provenance::step(|| augmenter.get_name(), applied, || {
+        if applied {
+            f()
+        }
+    })
+}
+
+/// Moves the series `rows` of `input` into a new dataset, leaving empty series behind
+pub(super) fn take_rows<T>(input: &mut Dataset<T>, rows: &[usize]) -> Dataset<T> {
+    Dataset {
+        features: rows
+            .iter()
+            .map(|&i| std::mem::take(&mut input.features[i]))
+            .collect(),
+        labels: rows
+            .iter()
+            .map(|&i| input.labels.get(i).cloned().unwrap_or_default())
+            .collect(),
+    }
+}
+
+/// Moves the series of `subset` back to the positions `rows` of `input`
+pub(super) fn put_rows<T>(input: &mut Dataset<T>, rows: &[usize], subset: Dataset<T>) {
+    for (&i, x) in rows.iter().zip(subset.features) {
+        input.features[i] = x;
+    }
+}
+
+impl<T: Float> Augmenter<T> for AugmentationPipeline<T> {
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, per_sample: bool) {
+        if self.class_pipelines.is_empty() {
+            self.augment_all(input, parallel, per_sample);
+            return;
+        }
+
+        // Group 0 holds the series without a class pipeline, group k the series of the k-th class
+        let mut groups = vec![Vec::new(); self.class_pipelines.len() + 1];
+        for i in 0..input.features.len() {
+            let label = input.labels.get(i).map_or("", String::as_str);
+            let group = self
+                .class_pipelines
+                .keys()
+                .position(|l| l == label)
+                .map_or(0, |k| k + 1);
+            groups[group].push(i);
+        }
+        let mut subsets: Vec<Dataset<T>> = groups.iter().map(|rows| take_rows(input, rows)).collect();
+
+        let mut provenances = Vec::with_capacity(subsets.len());
+        let (_, rest) = provenance::subset(groups[0].len(), || {
+            self.augment_all(&mut subsets[0], parallel, per_sample)
+        });
+        provenances.push(rest);
+        for (subset, pipeline) in subsets[1..].iter_mut().zip(self.class_pipelines.values()) {
+            let (_, class) = provenance::subset(subset.features.len(), || {
+                pipeline.augment_batch(subset, parallel, per_sample)
+            });
+            provenances.push(class);
+        }
+
+        if subsets
+            .iter()
+            .zip(&groups)
+            .all(|(subset, rows)| subset.features.len() == rows.len())
+        {
+            for ((rows, subset), provenance) in groups.iter().zip(subsets).zip(provenances) {
+                put_rows(input, rows, subset);
+                provenance::put_rows(rows, provenance);
+            }
+        } else {
+            input.features.clear();
+            input.labels.clear();
+            for subset in subsets {
+                input.features.extend(subset.features);
+                input.labels.extend(subset.labels);
+            }
+            provenance::concat_rows(&groups, provenances);
+        }
+    }
+
+    fn augment_dense(&self, input: &mut DenseDataset<T>, parallel: bool) {
+        let groups: Vec<(&AugmentationPipeline<T>, Vec<usize>)> = self
+            .class_pipelines
+            .iter()
+            .map(|(label, pipeline)| {
+                let rows = (0..input.n_samples())
+                    .filter(|&i| input.labels.get(i) == Some(label))
+                    .collect();
+                (pipeline, rows)
+            })
+            .filter(|(_, rows): &(_, Vec<usize>)| !rows.is_empty())
+            .collect();
+
+        if groups.is_empty() {
+            for augmenter in &self.augmenters {
+                augmenter.augment_dense(input, parallel);
+            }
+            return;
+        }
+
+        // Only the series without a class pipeline are augmented with the augmenters of this pipeline
+        let mut subsets: Vec<DenseDataset<T>> = groups
+            .iter()
+            .map(|(pipeline, rows)| {
+                let mut subset = input.select(rows);
+                pipeline.augment_dense(&mut subset, parallel);
+                subset
+            })
+            .collect();
+        let rest: Vec<usize> = (0..input.n_samples())
+            .filter(|&i| {
+                input
+                    .labels
+                    .get(i)
+                    .is_none_or(|label| !self.class_pipelines.contains_key(label))
+            })
+            .collect();
+        let mut default = input.select(&rest);
+        for augmenter in &self.augmenters {
+            augmenter.augment_dense(&mut default, parallel);
+        }
+        input.set_samples(&rest, &default);
+        for ((_, rows), subset) in groups.iter().zip(subsets.iter_mut()) {
+            input.set_samples(rows, subset);
+        }
+    }
+
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        for augmenter in self.augmenters.iter() {
+            if !augmenter.augment_one_in_place(&mut res) {
+                res = augmenter.augment_one(&res);
+            }
+        }
+        res
+    }
+
+    fn augment_one_with_label(&self, x: &[T], label: &str) -> Vec<T> {
+        if let Some(pipeline) = self.class_pipelines.get(label) {
+            return pipeline.augment_one_with_label(x, label);
+        }
         let mut res = x.to_vec();
         for augmenter in self.augmenters.iter() {
-            res = augmenter.augment_one(&res);
+            if !augmenter.augment_one_in_place(&mut res) {
+                res = augmenter.augment_one_with_label(&res, label);
+            }
         }
         res
     }
+}
 
+impl<T: Float> AugmenterBase for AugmentationPipeline<T> {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -167,16 +574,17 @@ impl Augmenter for AugmentationPipeline {
     }
 }
 
-impl<T: Augmenter + 'static + Sync> Add<T> for AugmentationPipeline {
-    type Output = AugmentationPipeline;
+impl<T: Float, A: Augmenter<T> + 'static + Sync> Add<A> for AugmentationPipeline<T> {
+    type Output = AugmentationPipeline<T>;
 
-    fn add(self, rhs: T) -> Self::Output {
+    fn add(self, rhs: A) -> Self::Output {
         let mut augmenters = self.augmenters;
         augmenters.push(Box::new(rhs));
 
         AugmentationPipeline {
             name: "AugmentationPipeline".to_string(),
             augmenters,
+            class_pipelines: self.class_pipelines,
             p: self.p,
         }
     }
@@ -0,0 +1,444 @@
+use super::base::{Augmenter, AugmenterBase, BatchRows, gate, put_rows, take_rows};
+use crate::provenance;
+use crate::random::rng;
+use crate::{Dataset, Float};
+use rand::distr::Distribution;
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::*;
+use rand::seq::index::sample;
+use rayon::prelude::*;
+use std::ops::Add;
+use tracing::info_span;
+
+/// Applies `augmenter` in batch mode to the rows `rows` of `input` only
+///
+/// The child augmenter decides with its own probability whether to augment each of these rows.
+fn augment_rows<T: Float>(
+    augmenter: &(dyn Augmenter<T> + Sync),
+    input: &mut Dataset<T>,
+    rows: &[usize],
+    parallel: bool,
+) {
+    if rows.is_empty() {
+        return;
+    }
+    let mut subset = take_rows(input, rows);
+    let (_, provenance) = provenance::subset(rows.len(), || {
+        augmenter.augment_batch(&mut subset, parallel, false)
+    });
+    assert_eq!(
+        subset.features.len(),
+        rows.len(),
+        "Augmenter '{}' changes the number of series and cannot be composed",
+        augmenter.get_name()
+    );
+    put_rows(input, rows, subset);
+    provenance::put_rows(rows, provenance);
+}
+
+/// Applies `augmenter` to one series if it passes the probability of the augmenter
+///
+/// Passes the label on if it is known, so label-conditional children work per sample.
+fn augment_gated<T: Float>(
+    augmenter: &(dyn Augmenter<T> + Sync),
+    x: Vec<T>,
+    label: Option<&str>,
+) -> Vec<T> {
+    let mut x = x;
+    gate(augmenter, || {
+        x = match label {
+            Some(label) => augmenter.augment_one_with_label(&x, label),
+            None => augmenter.augment_one(&x),
+        }
+    });
+    x
+}
+
+/// Augments every series with `augment_one` if it passes the probability of `augmenter`
+fn augment_each<T, A, F>(input: &mut Dataset<T>, parallel: bool, augmenter: &A, augment_one: F)
+where
+    T: Float,
+    A: AugmenterBase + Sync,
+    F: Fn(&[T], &str) -> Vec<T> + Sync,
+{
+    let labels = &input.labels;
+    let rows = BatchRows::new();
+    let apply = |(i, x): (usize, &mut Vec<T>)| {
+        rows.row(i, || {
+            gate(augmenter, || {
+                *x = augment_one(x, labels.get(i).map_or("", String::as_str))
+            })
+        })
+    };
+    if parallel {
+        input.features.par_iter_mut().enumerate().for_each(apply);
+    } else {
+        input.features.iter_mut().enumerate().for_each(apply);
+    }
+}
+
+/// Augmenter that applies exactly one of its children, chosen at random for every series
+///
+/// Each child is chosen with a probability proportional to its weight. The chosen child still
+/// augments the series only with its own probability.
+///
+/// In batch mode the chosen child augments all series it was chosen for as one batch, so children
+/// that need a batch level view (e.g. `FrequencyMask` in the time domain) can be used as well.
+/// Children must not change the number of series.
+///
+/// # Example
+///
+/// ```
+/// use rats_rs::Dataset;
+/// use rats_rs::augmenters::*;
+///
+/// let mut set = Dataset {
+///     features: vec![vec![1.0; 100]; 10],
+///     labels: vec![String::from("1"); 10],
+/// };
+///
+/// let mut one_of = OneOf::new();
+/// one_of.add(Scaling::new(2.0, 2.0), 3.0);
+/// one_of.add(Reverse::new(), 1.0);
+///
+/// let pipeline = AugmentationPipeline::new() + one_of + Crop::new(20);
+/// pipeline.augment_batch(&mut set, true, false);
+///
+/// assert_eq!(set.features[0].len(), 20);
+/// ```
+pub struct OneOf<T: Float = f64> {
+    pub name: String,
+    augmenters: Vec<Box<dyn Augmenter<T> + Sync>>,
+    weights: Vec<f64>,
+    p: f64,
+}
+
+impl<T: Float> OneOf<T> {
+    /// Creates an empty `OneOf`
+    pub fn new() -> Self {
+        OneOf {
+            name: "OneOf".to_string(),
+            augmenters: Vec::new(),
+            weights: Vec::new(),
+            p: 1.0,
+        }
+    }
+
+    /// Add a child that is chosen with a probability proportional to `weight`
+    ///
+    /// The `+` operator adds a child with a weight of 1
+    pub fn add<A: Augmenter<T> + 'static + Sync>(&mut self, augmenter: A, weight: f64) {
+        assert!(weight > 0.0, "Weight must be greater than 0");
+        self.augmenters.push(Box::new(augmenter));
+        self.weights.push(weight);
+    }
+
+    fn distribution(&self) -> WeightedIndex<f64> {
+        WeightedIndex::new(&self.weights).expect("Weights must be positive")
+    }
+
+    fn apply(&self, x: &[T], label: Option<&str>) -> Vec<T> {
+        if self.augmenters.is_empty() {
+            return x.to_vec();
+        }
+        let augmenter = &self.augmenters[self.distribution().sample(&mut rng())];
+        augment_gated(augmenter.as_ref(), x.to_vec(), label)
+    }
+}
+
+impl<T: Float> Default for OneOf<T> {
+    fn default() -> Self {
+        OneOf::new()
+    }
+}
+
+impl<T: Float> Augmenter<T> for OneOf<T> {
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, per_sample: bool) {
+        let span = info_span!("", component = self.get_name());
+        let _enter = span.enter();
+        if per_sample {
+            augment_each(input, parallel, self, |x, label| {
+                self.augment_one_with_label(x, label)
+            });
+            return;
+        }
+        if self.augmenters.is_empty() {
+            return;
+        }
+
+        let distribution = self.distribution();
+        let mut rng = rng();
+        let mut rows = vec![Vec::new(); self.augmenters.len()];
+        for i in 0..input.features.len() {
+            if self.p > rng.random() {
+                rows[distribution.sample(&mut rng)].push(i);
+            }
+        }
+        for (augmenter, rows) in self.augmenters.iter().zip(rows) {
+            augment_rows(augmenter.as_ref(), input, &rows, parallel);
+        }
+    }
+
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        self.apply(x, None)
+    }
+
+    fn augment_one_with_label(&self, x: &[T], label: &str) -> Vec<T> {
+        self.apply(x, Some(label))
+    }
+}
+
+impl<T: Float> AugmenterBase for OneOf<T> {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn supports_per_sample(&self) -> bool {
+        self.augmenters.iter().all(|a| a.supports_per_sample())
+    }
+}
+
+impl<T: Float, A: Augmenter<T> + 'static + Sync> Add<A> for OneOf<T> {
+    type Output = OneOf<T>;
+
+    fn add(mut self, rhs: A) -> Self::Output {
+        OneOf::add(&mut self, rhs, 1.0);
+        self
+    }
+}
+
+/// Augmenter that applies `k` of its children, chosen at random for every series
+///
+/// The `k` children are drawn uniformly without replacement and applied in the order they were
+/// added. Each chosen child still augments the series only with its own probability. If there are
+/// fewer than `k` children, all of them are applied.
+///
+/// In batch mode each child augments all series it was chosen for as one batch. Children must not
+/// change the number of series.
+pub struct SomeOf<T: Float = f64> {
+    pub name: String,
+    /// Number of children applied to each series
+    pub k: usize,
+    augmenters: Vec<Box<dyn Augmenter<T> + Sync>>,
+    p: f64,
+}
+
+impl<T: Float> SomeOf<T> {
+    /// Creates an empty `SomeOf` that applies `k` children
+    pub fn new(k: usize) -> Self {
+        SomeOf {
+            name: "SomeOf".to_string(),
+            k,
+            augmenters: Vec::new(),
+            p: 1.0,
+        }
+    }
+
+    /// Add a child
+    ///
+    /// Has the same effect as using the `+` operator
+    pub fn add<A: Augmenter<T> + 'static + Sync>(&mut self, augmenter: A) {
+        self.augmenters.push(Box::new(augmenter));
+    }
+
+    /// Indices of the chosen children in ascending order
+    fn choose(&self, rng: &mut impl Rng) -> Vec<usize> {
+        let n = self.augmenters.len();
+        let mut chosen = sample(rng, n, self.k.min(n)).into_vec();
+        chosen.sort_unstable();
+        chosen
+    }
+
+    fn apply(&self, x: &[T], label: Option<&str>) -> Vec<T> {
+        let mut res = x.to_vec();
+        for j in self.choose(&mut rng()) {
+            res = augment_gated(self.augmenters[j].as_ref(), res, label);
+        }
+        res
+    }
+}
+
+impl<T: Float> Augmenter<T> for SomeOf<T> {
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, per_sample: bool) {
+        let span = info_span!("", component = self.get_name());
+        let _enter = span.enter();
+        if per_sample {
+            augment_each(input, parallel, self, |x, label| {
+                self.augment_one_with_label(x, label)
+            });
+            return;
+        }
+
+        let mut rng = rng();
+        let mut rows = vec![Vec::new(); self.augmenters.len()];
+        for i in 0..input.features.len() {
+            if self.p > rng.random() {
+                for j in self.choose(&mut rng) {
+                    rows[j].push(i);
+                }
+            }
+        }
+        for (augmenter, rows) in self.augmenters.iter().zip(rows) {
+            augment_rows(augmenter.as_ref(), input, &rows, parallel);
+        }
+    }
+
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        self.apply(x, None)
+    }
+
+    fn augment_one_with_label(&self, x: &[T], label: &str) -> Vec<T> {
+        self.apply(x, Some(label))
+    }
+}
+
+impl<T: Float> AugmenterBase for SomeOf<T> {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn supports_per_sample(&self) -> bool {
+        self.augmenters.iter().all(|a| a.supports_per_sample())
+    }
+}
+
+impl<T: Float, A: Augmenter<T> + 'static + Sync> Add<A> for SomeOf<T> {
+    type Output = SomeOf<T>;
+
+    fn add(mut self, rhs: A) -> Self::Output {
+        SomeOf::add(&mut self, rhs);
+        self
+    }
+}
+
+/// Augmenter that applies all of its children in a random order, shuffled for every series
+///
+/// Each child still augments the series only with its own probability.
+///
+/// In batch mode the children are applied step by step: at every step, each child augments all
+/// series that have it at this position of their order as one batch. Children must not change the
+/// number of series.
+pub struct RandomOrder<T: Float = f64> {
+    pub name: String,
+    augmenters: Vec<Box<dyn Augmenter<T> + Sync>>,
+    p: f64,
+}
+
+impl<T: Float> RandomOrder<T> {
+    /// Creates an empty `RandomOrder`
+    pub fn new() -> Self {
+        RandomOrder {
+            name: "RandomOrder".to_string(),
+            augmenters: Vec::new(),
+            p: 1.0,
+        }
+    }
+
+    /// Add a child
+    ///
+    /// Has the same effect as using the `+` operator
+    pub fn add<A: Augmenter<T> + 'static + Sync>(&mut self, augmenter: A) {
+        self.augmenters.push(Box::new(augmenter));
+    }
+
+    fn shuffled(&self, rng: &mut impl Rng) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.augmenters.len()).collect();
+        order.shuffle(rng);
+        order
+    }
+
+    fn apply(&self, x: &[T], label: Option<&str>) -> Vec<T> {
+        let mut res = x.to_vec();
+        for j in self.shuffled(&mut rng()) {
+            res = augment_gated(self.augmenters[j].as_ref(), res, label);
+        }
+        res
+    }
+}
+
+impl<T: Float> Default for RandomOrder<T> {
+    fn default() -> Self {
+        RandomOrder::new()
+    }
+}
+
+impl<T: Float> Augmenter<T> for RandomOrder<T> {
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, per_sample: bool) {
+        let span = info_span!("", component = self.get_name());
+        let _enter = span.enter();
+        if per_sample {
+            augment_each(input, parallel, self, |x, label| {
+                self.augment_one_with_label(x, label)
+            });
+            return;
+        }
+
+        let n = self.augmenters.len();
+        let mut rng = rng();
+        // rows[step][j]: series that apply child j at position `step` of their order
+        let mut rows = vec![vec![Vec::new(); n]; n];
+        for i in 0..input.features.len() {
+            if self.p > rng.random() {
+                for (step, j) in self.shuffled(&mut rng).into_iter().enumerate() {
+                    rows[step][j].push(i);
+                }
+            }
+        }
+        for step_rows in rows {
+            for (augmenter, rows) in self.augmenters.iter().zip(step_rows) {
+                augment_rows(augmenter.as_ref(), input, &rows, parallel);
+            }
+        }
+    }
+
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        self.apply(x, None)
+    }
+
+    fn augment_one_with_label(&self, x: &[T], label: &str) -> Vec<T> {
+        self.apply(x, Some(label))
+    }
+}
+
+impl<T: Float> AugmenterBase for RandomOrder<T> {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn supports_per_sample(&self) -> bool {
+        self.augmenters.iter().all(|a| a.supports_per_sample())
+    }
+}
+
+impl<T: Float, A: Augmenter<T> + 'static + Sync> Add<A> for RandomOrder<T> {
+    type Output = RandomOrder<T>;
+
+    fn add(mut self, rhs: A) -> Self::Output {
+        RandomOrder::add(&mut self, rhs);
+        self
+    }
+}
@@ -1,112 +1,279 @@
-use super::base::Augmenter;
-use rand::{Rng, rng};
+use super::base::{Augmenter, AugmenterBase, BatchRows, gate};
+use crate::{Dataset, Float};
+use crate::transforms::stft::WindowFunction;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
 use rayon::prelude::*;
+use rustfft::{FftPlanner, num_complex::Complex};
 use tracing::{info_span};
 
+/// Kernels of at least this size are applied with FFT convolution instead of the direct sum
+const FFT_THRESHOLD: usize = 64;
+
 /// Enum to specify the kernel window for the `Convolve` augmenter
+///
+/// All windows except `Custom` are normalized to sum to 1, so they smooth the series without changing its level.
+/// - `Exponential(tau)`: symmetric exponential decay `exp(-|n - center| / tau)`
+/// - `Custom(kernel)`: user-supplied kernel, used as is. Its length overrides the kernel size of the augmenter.
 pub enum ConvolveWindow {
     Flat,
     Gaussian,
+    Triangular,
+    Hann,
+    Hamming,
+    Blackman,
+    Exponential(f64),
+    Custom(Vec<f64>),
+}
+
+/// Enum to specify how the `Convolve` augmenter extends the series beyond its borders
+///
+/// Shown for the series `a b c d`:
+/// - `Zero`: `0 0 | a b c d | 0 0`
+/// - `Reflect`: `c b | a b c d | c b` (mirrored without repeating the edge value)
+/// - `Replicate`: `a a | a b c d | d d`
+/// - `Wrap`: `c d | a b c d | a b`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConvolveBoundary {
+    Zero,
+    Reflect,
+    Replicate,
+    Wrap,
 }
 
 /// Usage of this augmenter is to convolve time series data with a kernel
-/// 
-/// The kernel can be flat or Gaussian, and the size of the kernel are the parameters
-/// 
+///
+/// The kernel window and the size of the kernel are the parameters. The size can also be drawn
+/// randomly for each series from a range using `Convolve::with_size_range`.
+///
 /// The convolve operation is applied to each time series in the dataset, and smoothening is achieved
-/// by averaging the values in the kernel window over the time series data.
+/// by averaging the values in the kernel window over the time series data. The output has the same
+/// length as the input; how the series is extended beyond its borders is set with `set_boundary`
+/// and defaults to zero padding. Large kernels are applied using FFT convolution.
 pub struct Convolve {
     pub name: String,
     window: ConvolveWindow,
-    size: usize,
+    size_range: (usize, usize),
+    boundary: ConvolveBoundary,
     p: f64,
 }
 
 impl Convolve {
     pub fn new(window: ConvolveWindow, size: usize) -> Self {
-        assert!(size > 0, "Kernel size must be greater than 0");
+        Convolve::with_size_range(window, (size, size))
+    }
+
+    /// Creates a convolve augmenter that draws the kernel size for each series uniformly from
+    /// `size_range` (inclusive)
+    pub fn with_size_range(window: ConvolveWindow, size_range: (usize, usize)) -> Self {
+        assert!(size_range.0 > 0, "Kernel size must be greater than 0");
+        assert!(
+            size_range.0 <= size_range.1,
+            "Minimum kernel size must not be larger than the maximum kernel size"
+        );
+        match &window {
+            ConvolveWindow::Custom(kernel) => {
+                assert!(!kernel.is_empty(), "Custom kernel must not be empty")
+            }
+            ConvolveWindow::Exponential(tau) => {
+                assert!(*tau > 0.0, "Decay of the exponential window must be greater than 0")
+            }
+            _ => {}
+        }
         Convolve {
             name: "Convolve".to_string(),
             window,
-            size,
+            size_range,
+            boundary: ConvolveBoundary::Zero,
             p: 1.0,
         }
     }
 
-    fn make_kernel(&self) -> Vec<f64> {
-        let n = self.size;
-        match self.window {
+    /// Sets how the series is extended beyond its borders
+    pub fn set_boundary(&mut self, boundary: ConvolveBoundary) {
+        self.boundary = boundary;
+    }
+
+    fn make_kernel(&self, n: usize) -> Vec<f64> {
+        let normalize = |mut kernel: Vec<f64>| {
+            let sum: f64 = kernel.iter().sum();
+            kernel.iter_mut().for_each(|v| *v /= sum);
+            kernel
+        };
+        let mid = (n as f64 - 1.0) / 2.0;
+        match &self.window {
             ConvolveWindow::Flat => vec![1.0 / n as f64; n],
             ConvolveWindow::Gaussian => {
                 let sigma = 0.3 * ((n - 1) as f64) * 0.5 + 0.8;
-                let mid = (n as f64 - 1.0) / 2.0;
-                let mut kernel = Vec::with_capacity(n);
-                for i in 0..n {
-                    let x = i as f64 - mid;
-                    kernel.push((-0.5 * (x / sigma).powi(2)).exp());
+                normalize(
+                    (0..n)
+                        .map(|i| (-0.5 * ((i as f64 - mid) / sigma).powi(2)).exp())
+                        .collect(),
+                )
+            }
+            ConvolveWindow::Triangular => normalize(
+                (0..n)
+                    .map(|i| 1.0 - (i as f64 - mid).abs() / (mid + 1.0))
+                    .collect(),
+            ),
+            // The symmetric windows are zero at both ends, so two samples are added to make
+            // use of the full kernel size
+            ConvolveWindow::Hann => {
+                normalize(WindowFunction::Hann.symmetric_coefficients(n + 2)[1..=n].to_vec())
+            }
+            ConvolveWindow::Hamming => {
+                normalize(WindowFunction::Hamming.symmetric_coefficients(n))
+            }
+            ConvolveWindow::Blackman => {
+                normalize(WindowFunction::Blackman.symmetric_coefficients(n + 2)[1..=n].to_vec())
+            }
+            ConvolveWindow::Exponential(tau) => normalize(
+                (0..n)
+                    .map(|i| (-(i as f64 - mid).abs() / tau).exp())
+                    .collect(),
+            ),
+            ConvolveWindow::Custom(kernel) => kernel.clone(),
+        }
+    }
+
+    fn draw_kernel(&self) -> Vec<f64> {
+        // A custom kernel has its own size, so none is drawn
+        if let ConvolveWindow::Custom(kernel) = &self.window {
+            return kernel.clone();
+        }
+        let size = rng().random_range(self.size_range.0..=self.size_range.1);
+        provenance::record("size", size);
+        self.make_kernel(size)
+    }
+
+    fn has_fixed_kernel(&self) -> bool {
+        self.size_range.0 == self.size_range.1 || matches!(self.window, ConvolveWindow::Custom(_))
+    }
+
+    /// Value of the series at index `i`, which may lie outside of the series
+    fn extended<T: Float>(&self, x: &[T], i: isize) -> T {
+        let len = x.len() as isize;
+        if (0..len).contains(&i) {
+            return x[i as usize];
+        }
+        match self.boundary {
+            ConvolveBoundary::Zero => T::zero(),
+            ConvolveBoundary::Replicate => x[i.clamp(0, len - 1) as usize],
+            ConvolveBoundary::Wrap => x[i.rem_euclid(len) as usize],
+            ConvolveBoundary::Reflect => {
+                if len == 1 {
+                    return x[0];
                 }
-                let sum: f64 = kernel.iter().sum();
-                kernel.iter_mut().for_each(|v| *v /= sum);
-                kernel
+                let period = 2 * (len - 1);
+                let i = i.rem_euclid(period);
+                x[if i < len { i } else { period - i } as usize]
             }
         }
     }
 
-    fn convolve(&self, x: &[f64], kernel: &[f64]) -> Vec<f64> {
+    fn convolve<T: Float>(&self, x: &[T], kernel: &[f64]) -> Vec<T> {
         let n = kernel.len();
         let len = x.len();
-        if len < n {
-            return x.to_vec();
+        if len == 0 {
+            return Vec::new();
         }
-        let mut out = vec![0.0; len];
         let half = n / 2;
-        for i in 0..len {
-            let mut acc = 0.0;
-            for k in 0..n {
-                let idx = if i + k >= half && i + k < len + half {
-                    i + k - half
-                } else {
-                    continue;
-                };
-                if idx < len {
-                    acc += x[idx] * kernel[k];
-                }
-            }
-            out[i] = acc;
+
+        // out[i] = sum_k kernel[k] * padded[i + n - 1 - k]
+        let padded: Vec<T> = (0..len + n - 1)
+            .map(|j| self.extended(x, j as isize - half as isize))
+            .collect();
+
+        if n >= FFT_THRESHOLD {
+            return fft_convolve(&padded, kernel)[n - 1..n - 1 + len].to_vec();
         }
-        out
+
+        (0..len)
+            .map(|i| {
+                kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &w)| T::cast(w) * padded[i + n - 1 - k])
+                    .sum()
+            })
+            .collect()
     }
 }
 
-impl Augmenter for Convolve {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+/// Full linear convolution of `x` and `kernel` computed in the frequency domain
+fn fft_convolve<T: Float>(x: &[T], kernel: &[f64]) -> Vec<T> {
+    let out_len = x.len() + kernel.len() - 1;
+    let fft_len = out_len.next_power_of_two();
+
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(fft_len);
+    let ifft = planner.plan_fft_inverse(fft_len);
+
+    let to_buffer = |values: &mut dyn Iterator<Item = T>| {
+        let mut buffer: Vec<Complex<T>> = values
+            .map(|v| Complex {
+                re: v,
+                im: T::zero(),
+            })
+            .collect();
+        buffer.resize(fft_len, Complex::new(T::zero(), T::zero()));
+        buffer
+    };
+    let mut signal = to_buffer(&mut x.iter().copied());
+    let mut response = to_buffer(&mut kernel.iter().map(|&w| T::cast(w)));
+    fft.process(&mut signal);
+    fft.process(&mut response);
+
+    signal.iter_mut().zip(response.iter()).for_each(|(s, r)| *s *= r);
+    ifft.process(&mut signal);
+
+    signal
+        .iter()
+        .take(out_len)
+        .map(|c| c.re / T::cast(fft_len as f64))
+        .collect()
+}
+
+impl<T: Float> Augmenter<T> for Convolve {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        let kernel = self.make_kernel();
+        let kernel = self.draw_kernel();
         self.convolve(x, &kernel)
     }
 
     // reimplementing augment_batch to make sure kernel is created only once for each batch
-    fn augment_batch(&self, input: &mut crate::Dataset, parallel: bool, _per_sample: bool)
+    // when its size is fixed
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, _per_sample: bool)
     where
         Self: Sync,
     {
-        let kernel = self.make_kernel();
+        let kernel = if self.has_fixed_kernel() {
+            Some(self.draw_kernel())
+        } else {
+            None
+        };
+        let rows = BatchRows::new();
+        let augment = |(i, x): (usize, &mut Vec<T>)| {
+            rows.row(i, || {
+                gate(self, || {
+                    *x = match &kernel {
+                        Some(kernel) => self.convolve(x, kernel),
+                        None => self.convolve(x, &self.draw_kernel()),
+                    }
+                })
+            })
+        };
         if parallel {
-            input.features.par_iter_mut().for_each(|x| {
-                if self.get_probability() > rng().random() {
-                    *x = self.convolve(x, &kernel)
-                }
-            });
+            input.features.par_iter_mut().enumerate().for_each(augment);
         } else {
-            input.features.iter_mut().for_each(|x| {
-                if self.get_probability() > rng().random() {
-                    *x = self.convolve(x, &kernel)
-                }
-            });
+            input.features.iter_mut().enumerate().for_each(augment);
         }
     }
+}
 
+impl AugmenterBase for Convolve {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -1,4 +1,8 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
 use tracing::info_span;
 
 /// Augmenter that crops each series into a random continuous slice of specified `size`
@@ -19,26 +23,29 @@ impl Crop {
         }
     }
 
-    fn get_slice(&self, x: &[f64]) -> Vec<f64> {
+    fn get_slice<T: Float>(&self, x: &[T]) -> Vec<T> {
         let n = x.len();
 
         if self.size >= n {
             return x.to_vec();
         }
 
-        let start: usize = rand::random_range(0..(n - self.size + 1));
+        let start: usize = rng().random_range(0..(n - self.size + 1));
+        provenance::record("start", start);
 
         x[start..(start + self.size)].to_vec()
     }
 }
 
-impl Augmenter for Crop {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Crop {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         self.get_slice(x)
     }
+}
 
+impl AugmenterBase for Crop {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -1,4 +1,7 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
 use rand::Rng;
 use tracing::{info_span};
 /// Drifts the value of a time series by a random value at each point in the series.
@@ -24,37 +27,47 @@ impl Drift {
         }
     }
 
-    fn make_drift(&self, len: usize) -> Vec<f64> {
-        let mut rng = rand::rng();
+    /// Adds the drift, which is linearly interpolated between random drift points, to `x`
+    fn add_drift<T: Float>(&self, x: &mut [T]) {
+        let len = x.len();
+        let mut rng = rng();
         let n = self.n_drift_points.min(len);
         let mut drift_points = Vec::with_capacity(n);
         for _ in 0..n {
             drift_points.push(rng.random_range(-self.max_drift..=self.max_drift));
         }
+        provenance::record("drift_points", drift_points.as_slice());
         // Linear interpolation between drift points
-        let mut drift = vec![0.0; len];
         let seg_len = len as f64 / (n - 1) as f64;
-        for i in 0..len {
+        for (i, val) in x.iter_mut().enumerate() {
             let pos = i as f64 / seg_len;
-            let left = pos.floor() as usize;
-            let right = pos.ceil() as usize;
+            // Same as floor and ceil for the non-negative position, without calling into libm
+            let left = pos as usize;
+            let right = if pos > left as f64 { left + 1 } else { left };
             let alpha = pos - left as f64;
             let left_val = drift_points[left.min(n - 1)];
             let right_val = drift_points[right.min(n - 1)];
-            drift[i] = (1.0 - alpha) * left_val + alpha * right_val;
+            *val += T::cast((1.0 - alpha) * left_val + alpha * right_val);
         }
-        drift
     }
 }
 
-impl Augmenter for Drift {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Drift {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        let drift = self.make_drift(x.len());
-        x.iter().zip(drift.iter()).map(|(xi, di)| xi + di).collect()
+        self.add_drift(x);
+        true
     }
+}
 
+impl AugmenterBase for Drift {
     fn get_probability(&self) -> f64 {
         self.p
     }
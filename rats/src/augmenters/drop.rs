@@ -1,4 +1,6 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use super::simd;
+use crate::{Float, provenance};
 use tracing::{info_span};
 /// Augmenter that drops data points in series
 ///
@@ -24,21 +26,31 @@ impl Drop {
     }
 }
 
-impl Augmenter for Drop {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Drop {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        x.iter()
-            .map(|val| {
-                if rand::random::<f64>() < self.percentage {
-                    self.default
-                } else {
-                    *val
-                }
-            })
-            .collect()
+        let default = T::cast(self.default);
+        if provenance::records() {
+            let mut indices = Vec::new();
+            simd::drop_values(&mut simd::series_rng(), x, self.percentage, default, |i| {
+                indices.push(i as f64)
+            });
+            provenance::record("indices", indices.as_slice());
+        } else {
+            simd::drop_values(&mut simd::series_rng(), x, self.percentage, default, |_| {});
+        }
+        true
     }
+}
 
+impl AugmenterBase for Drop {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -1,7 +1,9 @@
-use super::base::Augmenter;
-use crate::Dataset;
+use super::base::{Augmenter, AugmenterBase, BatchRows, gate};
+use crate::{Dataset, Float};
+use crate::provenance;
 use crate::transforms::fastfourier::{dataset_fft, dataset_ifft};
-use rand::{Rng, rng};
+use crate::random::rng;
+use rand::Rng;
 use tracing::info_span;
 
 
@@ -26,31 +28,29 @@ impl FrequencyMask {
     }
 }
 
-impl Augmenter for FrequencyMask {
-    fn augment_batch(&self, data: &mut Dataset, _parallel: bool, _per_sample: bool) {
+impl<T: Float> Augmenter<T> for FrequencyMask {
+    fn augment_batch(&self, data: &mut Dataset<T>, _parallel: bool, _per_sample: bool) {
         let span = info_span!("", component = self.get_name());
         let _enter = span.enter();
         if self.is_time_domain {
             let mut transformed_dataset = dataset_fft(data, true);
 
-            transformed_dataset.features.iter_mut().for_each(|sample| {
-                if self.get_probability() > rng().random() {
-                    *sample = self.augment_one(sample)
-                }
+            let rows = BatchRows::new();
+            transformed_dataset.features.iter_mut().enumerate().for_each(|(i, sample)| {
+                rows.row(i, || gate(self, || *sample = self.augment_one(sample)))
             });
 
             let inverse_dataset = dataset_ifft(&transformed_dataset, true);
             *data = inverse_dataset;
         } else {
-            data.features.iter_mut().for_each(|sample| {
-                if self.get_probability() > rng().random() {
-                    *sample = self.augment_one(sample)
-                }
+            let rows = BatchRows::new();
+            data.features.iter_mut().enumerate().for_each(|(i, sample)| {
+                rows.row(i, || gate(self, || *sample = self.augment_one(sample)))
             });
         }
     }
 
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         let mut res = x.to_vec();
@@ -60,20 +60,23 @@ impl Augmenter for FrequencyMask {
             return res;
         }
 
-        let mut rng = rand::rng();
+        let mut rng = rng();
         let center = rng.random_range(self.mask_width / 2..(num_bins - self.mask_width / 2));
         let start = center - self.mask_width / 2;
+        provenance::record("start", start);
         let end = start + self.mask_width;
         for bin in start..end {
             let re_idx = 2 * bin;
             let im_idx = 2 * bin + 1;
-            res[re_idx] = 0.0;
-            res[im_idx] = 0.0;
+            res[re_idx] = T::zero();
+            res[im_idx] = T::zero();
         }
 
         res
     }
+}
 
+impl AugmenterBase for FrequencyMask {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -1,6 +1,6 @@
-use super::base::Augmenter;
-use rand::prelude::*;
-use rand_distr::Normal;
+use super::base::{Augmenter, AugmenterBase};
+use super::simd;
+use crate::Float;
 use tracing::{info_span};
 /// Augmenter that adds white gaussian noise of the specified standard deviation and a mean of 0
 /// 
@@ -21,17 +21,26 @@ impl Jittering {
     }
 }
 
-impl Augmenter for Jittering {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Jittering {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        let _enter = span.enter();
-        let mut rng = rand::rng();
-        let dist = Normal::new(0.0, self.deviation)
-            .expect("Couldn't create normal distribution from specified standard deviation");
-        x.iter().map(|val| *val + dist.sample(&mut rng)).collect()
+        assert!(
+            self.deviation >= 0.0 && self.deviation.is_finite(),
+            "Couldn't create normal distribution from specified standard deviation"
+        );
+        simd::add_normal(&mut simd::series_rng(), x, 0.0, self.deviation);
+        true
     }
+}
 
+impl AugmenterBase for Jittering {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -0,0 +1,138 @@
+use super::base::{Augmenter, AugmenterBase, gate, put_rows, take_rows};
+use crate::provenance;
+use crate::random::rng;
+use crate::{Dataset, Float};
+use rand::Rng;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info_span;
+
+/// Decides from the label of a series whether it is augmented
+pub type LabelPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Augmenter that applies another augmenter only to series whose label matches a predicate
+///
+/// Some augmentations destroy the label of certain classes, e.g. `Reverse` turns a rising series
+/// into a falling one. Wrapping them in `LabelConditional` leaves the series of all other classes
+/// untouched. The wrapped augmenter still augments a matching series only with its own probability.
+///
+/// In batch mode the wrapped augmenter augments all matching series as one batch, so it must not
+/// change the number of series. `augment_one` has no label to check and always applies the wrapped
+/// augmenter, the label is checked when chaining per sample.
+///
+/// # Example
+///
+/// ```
+/// use rats_rs::Dataset;
+/// use rats_rs::augmenters::*;
+///
+/// let mut set = Dataset {
+///     features: vec![vec![1.0, -1.0], vec![1.0, -1.0]],
+///     labels: vec![String::from("symmetric"), String::from("signed")],
+/// };
+///
+/// let pipeline = AugmentationPipeline::new()
+///     + LabelConditional::for_labels(Rotation::new(0.0), &["symmetric"]);
+/// pipeline.augment_batch(&mut set, true, true);
+///
+/// assert_eq!(set.features[0], vec![-1.0, 1.0]);
+/// assert_eq!(set.features[1], vec![1.0, -1.0]);
+/// ```
+pub struct LabelConditional<T: Float = f64> {
+    pub name: String,
+    augmenter: Box<dyn Augmenter<T> + Sync>,
+    predicate: LabelPredicate,
+    p: f64,
+}
+
+impl<T: Float> LabelConditional<T> {
+    /// Applies `augmenter` only to series for which `predicate` returns true for their label
+    pub fn new<A, F>(augmenter: A, predicate: F) -> Self
+    where
+        A: Augmenter<T> + 'static + Sync,
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        LabelConditional {
+            name: format!("LabelConditional({})", augmenter.get_name()),
+            augmenter: Box::new(augmenter),
+            predicate: Arc::new(predicate),
+            p: 1.0,
+        }
+    }
+
+    /// Applies `augmenter` only to series whose label is one of `labels`
+    pub fn for_labels<A: Augmenter<T> + 'static + Sync>(augmenter: A, labels: &[&str]) -> Self {
+        let labels: HashSet<String> = labels.iter().map(|l| l.to_string()).collect();
+        LabelConditional::new(augmenter, move |label| labels.contains(label))
+    }
+
+    /// Applies `augmenter` to all series except those whose label is one of `labels`
+    pub fn except_labels<A: Augmenter<T> + 'static + Sync>(augmenter: A, labels: &[&str]) -> Self {
+        let labels: HashSet<String> = labels.iter().map(|l| l.to_string()).collect();
+        LabelConditional::new(augmenter, move |label| !labels.contains(label))
+    }
+
+    /// Whether a series of class `label` is augmented
+    pub fn matches(&self, label: &str) -> bool {
+        (self.predicate)(label)
+    }
+}
+
+impl<T: Float> Augmenter<T> for LabelConditional<T> {
+    fn augment_batch(&self, input: &mut Dataset<T>, parallel: bool, _per_sample: bool) {
+        let span = info_span!("", component = self.get_name());
+        let _enter = span.enter();
+        let rows: Vec<usize> = (0..input.features.len())
+            .filter(|&i| self.matches(input.labels.get(i).map_or("", String::as_str)))
+            .filter(|_| self.p > rng().random())
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut subset = take_rows(input, &rows);
+        let (_, provenance) = provenance::subset(rows.len(), || {
+            self.augmenter.augment_batch(&mut subset, parallel, false)
+        });
+        assert_eq!(
+            subset.features.len(),
+            rows.len(),
+            "Augmenter '{}' changes the number of series and cannot be used label-conditionally",
+            self.augmenter.get_name()
+        );
+        put_rows(input, &rows, subset);
+        provenance::put_rows(&rows, provenance);
+    }
+
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        self.augmenter.augment_one(x)
+    }
+
+    fn augment_one_with_label(&self, x: &[T], label: &str) -> Vec<T> {
+        let mut res = None;
+        if self.matches(label) {
+            gate(self.augmenter.as_ref(), || {
+                res = Some(self.augmenter.augment_one_with_label(x, label))
+            });
+        }
+        res.unwrap_or_else(|| x.to_vec())
+    }
+}
+
+impl<T: Float> AugmenterBase for LabelConditional<T> {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn supports_per_sample(&self) -> bool {
+        self.augmenter.supports_per_sample()
+    }
+}
@@ -0,0 +1,139 @@
+use super::base::Augmenter;
+use super::convolve::{Convolve, ConvolveWindow};
+use super::drift::Drift;
+use super::drop::Drop;
+use super::jittering::Jittering;
+use super::pool::{Pool, PoolingMethod};
+use super::quantize::Quantize;
+use super::random_filter::{FilterType, RandomFilter};
+use super::scaling::Scaling;
+use super::time_warp::RandomTimeWarpAugmenter;
+use super::wavelet_drop::WaveletDrop;
+use super::wavelet_perturbation::WaveletPerturbation;
+use crate::transforms::filter::FilterDesign;
+use crate::transforms::wavelet::Wavelet;
+
+/// Trait for augmenters whose strength can be controlled by a single normalized magnitude
+///
+/// `from_magnitude(0.0)` creates the mildest version of the augmenter, which leaves the series
+/// (nearly) unchanged, and `from_magnitude(1.0)` the strongest one that still preserves the
+/// structure of typical normalized data. Used by `RandAugment` and `TrivialAugment` to draw
+/// augmentations of comparable strength.
+///
+/// The built-in mappings for a magnitude `m` are:
+/// - `Jittering`: standard deviation `0.1 * m`
+/// - `Scaling`: factors from `1 - 0.5 * m` to `1 + 0.5 * m`
+/// - `Drift`: maximum drift `0.5 * m` with 5 drift points
+/// - `Drop`: drops `0.3 * m` of the points
+/// - `Convolve`: Gaussian kernel of size `1 + 2 * round(5 * m)`
+/// - `Quantize`: `round(64 - 60 * m)` levels
+/// - `Pool`: average pooling of size `1 + round(4 * m)`
+/// - `RandomTimeWarpAugmenter`: whole series with speed ratios from `1 / (1 + m)` to `1 + m`
+/// - `WaveletDrop`: drops `m` of the finest detail coefficients of a Daubechies 4 decomposition
+/// - `WaveletPerturbation`: noise of `m` times the coefficient deviation on the three finest levels
+///   of a Daubechies 4 decomposition
+/// - `RandomFilter`: 4th order Butterworth low-pass filter with cutoff `0.9 - 0.8 * m`
+///
+/// Only length-preserving augmenters that work on a single series implement this trait, so any of
+/// them can be chained per sample.
+pub trait Magnitude: Augmenter {
+    /// Creates the augmenter at the given magnitude in `[0, 1]`
+    fn from_magnitude(magnitude: f64) -> Self
+    where
+        Self: Sized;
+}
+
+fn check_magnitude(magnitude: f64) {
+    assert!(
+        (0.0..=1.0).contains(&magnitude),
+        "Magnitude must lie within [0, 1]"
+    );
+}
+
+impl Magnitude for Jittering {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Jittering::new(0.1 * magnitude)
+    }
+}
+
+impl Magnitude for Scaling {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Scaling::new(1.0 - 0.5 * magnitude, 1.0 + 0.5 * magnitude)
+    }
+}
+
+impl Magnitude for Drift {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Drift::new(0.5 * magnitude, 5)
+    }
+}
+
+impl Magnitude for Drop {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Drop::new(0.3 * magnitude, None)
+    }
+}
+
+impl Magnitude for Convolve {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Convolve::new(
+            ConvolveWindow::Gaussian,
+            1 + 2 * (5.0 * magnitude).round() as usize,
+        )
+    }
+}
+
+impl Magnitude for Quantize {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Quantize::new((64.0 - 60.0 * magnitude).round() as usize)
+    }
+}
+
+impl Magnitude for Pool {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        Pool::new(
+            PoolingMethod::Average,
+            1 + (4.0 * magnitude).round() as usize,
+        )
+    }
+}
+
+impl Magnitude for RandomTimeWarpAugmenter {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        RandomTimeWarpAugmenter::new(0, (1.0 / (1.0 + magnitude), 1.0 + magnitude))
+    }
+}
+
+impl Magnitude for WaveletDrop {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        WaveletDrop::new(Wavelet::Daubechies(4), vec![1], magnitude)
+    }
+}
+
+impl Magnitude for WaveletPerturbation {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        WaveletPerturbation::new(Wavelet::Daubechies(4), vec![1, 2, 3], magnitude)
+    }
+}
+
+impl Magnitude for RandomFilter {
+    fn from_magnitude(magnitude: f64) -> Self {
+        check_magnitude(magnitude);
+        let cutoff = 0.9 - 0.8 * magnitude;
+        RandomFilter::new(
+            FilterType::LowPass,
+            (cutoff, cutoff),
+            FilterDesign::Butterworth { order: 4 },
+        )
+    }
+}
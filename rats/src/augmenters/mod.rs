@@ -46,37 +46,56 @@
 mod addnoise;
 mod amplitude_phase_perturbation;
 mod base;
+mod compose;
 mod convolve;
 mod crop;
 mod drift;
 mod drop;
 mod frequency_mask;
 mod jittering;
+mod label_conditional;
+mod magnitude;
 mod permutate;
 mod pool;
 mod quantize;
+mod rand_augment;
+mod random_filter;
 mod repeat;
 mod resize;
 mod reverse;
 mod rotation;
 mod scaling;
+mod simd;
+mod spec_augment;
+mod stream;
 mod time_warp;
+mod wavelet_drop;
+mod wavelet_perturbation;
 
 pub use addnoise::{AddNoise, NoiseType};
 pub use amplitude_phase_perturbation::AmplitudePhasePerturbation;
-pub use base::{AugmentationPipeline, Augmenter};
-pub use convolve::{Convolve, ConvolveWindow};
+pub use base::{AugmentationPipeline, AugmentedDataset, Augmenter, AugmenterBase};
+pub use compose::{OneOf, RandomOrder, SomeOf};
+pub use convolve::{Convolve, ConvolveBoundary, ConvolveWindow};
 pub use crop::Crop;
 pub use drift::Drift;
 pub use drop::Drop;
 pub use frequency_mask::FrequencyMask;
 pub use jittering::Jittering;
+pub use label_conditional::{LabelConditional, LabelPredicate};
+pub use magnitude::Magnitude;
 pub use permutate::Permutate;
 pub use pool::{Pool, PoolingMethod};
 pub use quantize::Quantize;
+pub use rand_augment::{MagnitudeOp, RandAugment, TrivialAugment};
+pub use random_filter::{FilterType, RandomFilter};
 pub use repeat::Repeat;
 pub use resize::Resize;
 pub use reverse::Reverse;
 pub use rotation::Rotation;
 pub use scaling::Scaling;
+pub use spec_augment::SpecAugment;
+pub use stream::{AugmentedBatches, Batch, BatchSampler, StreamConfig};
 pub use time_warp::RandomTimeWarpAugmenter;
+pub use wavelet_drop::WaveletDrop;
+pub use wavelet_perturbation::WaveletPerturbation;
@@ -1,5 +1,6 @@
-use super::base::Augmenter;
-use rand::rng;
+use super::base::{Augmenter, AugmenterBase};
+use crate::{Float, provenance};
+use crate::random::rng;
 use rand::seq::SliceRandom;
 use tracing::{info_span};
 /// Permutate time series
@@ -26,23 +27,32 @@ impl Permutate {
     }
 }
 
-impl Augmenter for Permutate {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Permutate {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        let mut windows = x.chunks(self.window_size).collect::<Vec<_>>();
-        let mut res = Vec::with_capacity(windows.len());
-        
-        for window in &mut windows {
-            let mut segments = window.chunks(self.segment_size).collect::<Vec<_>>();
-            
-            segments.shuffle(&mut rng());
-            res.push(segments.iter().map(|arr| arr.to_vec()).flatten().collect::<Vec<f64>>());
+        let mut res = Vec::with_capacity(x.len());
+        // Index of the segment of the input at every segment of the output, counted over all windows
+        let mut order = Vec::new();
+
+        for window in x.chunks(self.window_size) {
+            let segments = window.chunks(self.segment_size).collect::<Vec<_>>();
+            let mut indices = (0..segments.len()).collect::<Vec<_>>();
+
+            indices.shuffle(&mut rng());
+            for &i in &indices {
+                res.extend_from_slice(segments[i]);
+            }
+            let offset = order.len();
+            order.extend(indices.iter().map(|&i| (offset + i) as f64));
         }
-        
-        res.iter().map(|arr| arr.to_vec()).flatten().collect()
+        provenance::record("order", order.as_slice());
+
+        res
     }
+}
 
+impl AugmenterBase for Permutate {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -1,4 +1,5 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
 use tracing::{info_span};
 /// Reduces the temporal resolution without changing the length by pooling multiple samples together
 pub struct Pool {
@@ -29,8 +30,8 @@ impl Pool {
     }
 }
 
-impl Augmenter for Pool {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Pool {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         let mut res = Vec::with_capacity(x.len());
@@ -54,7 +55,7 @@ impl Augmenter for Pool {
                         .reduce(|a, b| if a < b { a } else { b })
                         .unwrap(),
                     PoolingMethod::Average => {
-                        x[i..i + cur_size].iter().sum::<f64>() / cur_size as f64
+                        x[i..i + cur_size].iter().copied().sum::<T>() / T::cast(cur_size as f64)
                     }
                 }
             };
@@ -68,7 +69,9 @@ impl Augmenter for Pool {
 
         res
     }
+}
 
+impl AugmenterBase for Pool {
     fn get_probability(&self) -> f64 {
         self.p
     }
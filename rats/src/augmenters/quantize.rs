@@ -1,4 +1,5 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
 use tracing::{info_span};
 /// Quantize time series to a level set
 ///
@@ -21,16 +22,16 @@ impl Quantize {
     }
 }
 
-impl Augmenter for Quantize {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Quantize {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
-        let max = x.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-        let min = x.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let max = x.iter().fold(T::neg_infinity(), |a, &b| a.max(b));
+        let min = x.iter().fold(T::infinity(), |a, &b| a.min(b));
         let range = max - min;
-        let step = range / self.levels as f64;
+        let step = range / T::cast(self.levels as f64);
         let level_set = (0..self.levels)
-            .map(|level| min + level as f64 * step)
+            .map(|level| min + T::cast(level as f64) * step)
             .collect::<Vec<_>>();
 
         // Could be faster using e.g. binary search
@@ -41,7 +42,7 @@ impl Augmenter for Quantize {
                     .map(|&l| (l - *v).abs())
                     .enumerate()
                     .fold(
-                        (0, f64::INFINITY),
+                        (0, T::infinity()),
                         |(i, a), (j, b)| if a > b { (j, b) } else { (i, a) },
                     )
                     .0;
@@ -49,7 +50,9 @@ impl Augmenter for Quantize {
             })
             .collect::<Vec<_>>()
     }
+}
 
+impl AugmenterBase for Quantize {
     fn get_probability(&self) -> f64 {
         self.p
     }
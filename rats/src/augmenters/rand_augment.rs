@@ -0,0 +1,212 @@
+use super::base::{Augmenter, AugmenterBase};
+use super::convolve::Convolve;
+use super::drift::Drift;
+use super::drop::Drop;
+use super::jittering::Jittering;
+use super::magnitude::Magnitude;
+use super::pool::Pool;
+use super::quantize::Quantize;
+use super::random_filter::RandomFilter;
+use super::scaling::Scaling;
+use super::time_warp::RandomTimeWarpAugmenter;
+use super::wavelet_drop::WaveletDrop;
+use super::wavelet_perturbation::WaveletPerturbation;
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
+use tracing::info_span;
+
+/// Creates an augmenter at a magnitude in `[0, 1]`, one entry of the op set of `RandAugment` and
+/// `TrivialAugment`
+pub type MagnitudeOp = fn(f64) -> Box<dyn Augmenter + Sync>;
+
+fn boxed<T: Magnitude + Sync + 'static>(magnitude: f64) -> Box<dyn Augmenter + Sync> {
+    Box::new(T::from_magnitude(magnitude))
+}
+
+/// All built-in augmenters that implement `Magnitude`
+fn default_ops() -> Vec<MagnitudeOp> {
+    vec![
+        boxed::<Jittering>,
+        boxed::<Scaling>,
+        boxed::<Drift>,
+        boxed::<Drop>,
+        boxed::<Convolve>,
+        boxed::<Quantize>,
+        boxed::<Pool>,
+        boxed::<RandomTimeWarpAugmenter>,
+        boxed::<WaveletDrop>,
+        boxed::<WaveletPerturbation>,
+        boxed::<RandomFilter>,
+    ]
+}
+
+/// Augmenter that applies `n` ops drawn at random from a set of augmenters, all at the same
+/// `magnitude` (Cubuk et al., 2020)
+///
+/// The ops are drawn uniformly with replacement, independently for every series. By default the set
+/// contains all built-in augmenters that implement `Magnitude`, so the whole search space of the
+/// augmentation policy is reduced to the two parameters `n` and `magnitude`.
+///
+/// # Examples
+/// ```
+/// use rats_rs::augmenters::{Augmenter, Jittering, RandAugment, Scaling};
+///
+/// let mut augmenter = RandAugment::new(2, 0.5);
+/// augmenter.clear_ops();
+/// augmenter.add_op::<Jittering>();
+/// augmenter.add_op::<Scaling>();
+///
+/// let series = augmenter.augment_one(&vec![1.0; 100]);
+/// assert_eq!(series.len(), 100);
+/// ```
+pub struct RandAugment {
+    pub name: String,
+    /// Number of ops applied to each series
+    pub n: usize,
+    /// Magnitude in `[0, 1]` at which all ops are applied
+    pub magnitude: f64,
+    ops: Vec<MagnitudeOp>,
+    p: f64,
+}
+
+impl RandAugment {
+    pub fn new(n: usize, magnitude: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&magnitude),
+            "Magnitude must lie within [0, 1]"
+        );
+        RandAugment {
+            name: "RandAugment".to_string(),
+            n,
+            magnitude,
+            ops: default_ops(),
+            p: 1.0,
+        }
+    }
+
+    /// Adds an augmenter to the set of ops
+    pub fn add_op<T: Magnitude + Sync + 'static>(&mut self) {
+        self.ops.push(boxed::<T>);
+    }
+
+    /// Removes all ops, including the default ones
+    pub fn clear_ops(&mut self) {
+        self.ops.clear();
+    }
+
+    /// Number of ops the augmenters are drawn from
+    pub fn num_ops(&self) -> usize {
+        self.ops.len()
+    }
+}
+
+// The ops are created for `f64`, single precision series are converted for them
+impl<T: Float> Augmenter<T> for RandAugment {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+        if self.ops.is_empty() {
+            return x.to_vec();
+        }
+        let mut res: Vec<f64> = x.iter().map(|v| v.as_f64()).collect();
+        let mut rng = rng();
+        for _ in 0..self.n {
+            let op = self.ops[rng.random_range(0..self.ops.len())](self.magnitude);
+            res = provenance::step(|| op.get_name(), true, || op.augment_one(&res));
+        }
+        res.into_iter().map(T::cast).collect()
+    }
+}
+
+impl AugmenterBase for RandAugment {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// Augmenter that applies a single op drawn at random from a set of augmenters at a random magnitude
+/// (Müller & Hutter, 2021)
+///
+/// For every series, one op is drawn uniformly and applied at a magnitude drawn uniformly from
+/// `[0, 1]`, so there are no parameters to tune. By default the set contains all built-in augmenters
+/// that implement `Magnitude`.
+pub struct TrivialAugment {
+    pub name: String,
+    ops: Vec<MagnitudeOp>,
+    p: f64,
+}
+
+impl TrivialAugment {
+    pub fn new() -> Self {
+        TrivialAugment {
+            name: "TrivialAugment".to_string(),
+            ops: default_ops(),
+            p: 1.0,
+        }
+    }
+
+    /// Adds an augmenter to the set of ops
+    pub fn add_op<T: Magnitude + Sync + 'static>(&mut self) {
+        self.ops.push(boxed::<T>);
+    }
+
+    /// Removes all ops, including the default ones
+    pub fn clear_ops(&mut self) {
+        self.ops.clear();
+    }
+
+    /// Number of ops the augmenter is drawn from
+    pub fn num_ops(&self) -> usize {
+        self.ops.len()
+    }
+}
+
+impl Default for TrivialAugment {
+    fn default() -> Self {
+        TrivialAugment::new()
+    }
+}
+
+impl<T: Float> Augmenter<T> for TrivialAugment {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+        if self.ops.is_empty() {
+            return x.to_vec();
+        }
+        let mut rng = rng();
+        let op = self.ops[rng.random_range(0..self.ops.len())];
+        let magnitude = rng.random_range(0.0..=1.0);
+        provenance::record("magnitude", magnitude);
+        let op = op(magnitude);
+        let x: Vec<f64> = x.iter().map(|v| v.as_f64()).collect();
+        provenance::step(|| op.get_name(), true, || op.augment_one(&x))
+            .into_iter()
+            .map(T::cast)
+            .collect()
+    }
+}
+
+impl AugmenterBase for TrivialAugment {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
@@ -0,0 +1,109 @@
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use crate::transforms::filter::{Filter, FilterBand, FilterDesign};
+use rand::Rng;
+use tracing::info_span;
+
+/// Enum to specify the kind of filter applied by the `RandomFilter` augmenter
+pub enum FilterType {
+    LowPass,
+    HighPass,
+    BandPass,
+    BandStop,
+}
+
+/// Augmenter that applies a filter with a random cutoff frequency to simulate different sensor bandwidths
+///
+/// For every series, a filter of kind `filter_type` is designed according to `design` with a cutoff
+/// drawn uniformly from `cutoff_range`. Band-pass and band-stop filters draw both band edges from
+/// `cutoff_range`. The filter is applied forward and backward, so the series is not shifted in time.
+///
+/// Frequencies are normalized to the Nyquist frequency, so `cutoff_range` must lie within `(0, 1)`.
+/// High-pass and band-stop filters designed with `WindowedSinc` need an odd number of taps.
+pub struct RandomFilter {
+    pub name: String,
+    pub filter_type: FilterType,
+    pub cutoff_range: (f64, f64),
+    pub design: FilterDesign,
+    p: f64,
+}
+
+impl RandomFilter {
+    pub fn new(filter_type: FilterType, cutoff_range: (f64, f64), design: FilterDesign) -> Self {
+        assert!(
+            cutoff_range.0 > 0.0 && cutoff_range.0 <= cutoff_range.1 && cutoff_range.1 < 1.0,
+            "Cutoff range must satisfy 0 < min <= max < 1"
+        );
+        if let FilterDesign::WindowedSinc { num_taps, .. } = design {
+            assert!(num_taps > 0, "Number of taps must be greater than 0");
+            assert!(
+                matches!(filter_type, FilterType::LowPass | FilterType::BandPass)
+                    || num_taps % 2 == 1,
+                "High-pass and band-stop FIR filters need an odd number of taps"
+            );
+        }
+        RandomFilter {
+            name: "RandomFilter".to_string(),
+            filter_type,
+            cutoff_range,
+            design,
+            p: 1.0,
+        }
+    }
+
+    fn make_band(&self, rng: &mut impl Rng) -> FilterBand {
+        let (min, max) = self.cutoff_range;
+        let mut draw = || rng.random_range(min..=max);
+        match self.filter_type {
+            FilterType::LowPass => FilterBand::LowPass(draw()),
+            FilterType::HighPass => FilterBand::HighPass(draw()),
+            FilterType::BandPass | FilterType::BandStop => {
+                let (a, b) = (draw(), draw());
+                let (mut low, mut high) = (a.min(b), a.max(b));
+                if low == high {
+                    // Degenerate band, widen it slightly within (0, 1)
+                    low = (low * 0.99).max(f64::MIN_POSITIVE);
+                    high += (1.0 - high) * 0.01;
+                }
+                match self.filter_type {
+                    FilterType::BandPass => FilterBand::BandPass(low, high),
+                    _ => FilterBand::BandStop(low, high),
+                }
+            }
+        }
+    }
+}
+
+impl<T: Float> Augmenter<T> for RandomFilter {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+        let band = self.make_band(&mut rng());
+        match band {
+            FilterBand::LowPass(cutoff) | FilterBand::HighPass(cutoff) => {
+                provenance::record("cutoff", cutoff)
+            }
+            FilterBand::BandPass(low, high) | FilterBand::BandStop(low, high) => {
+                provenance::record("low", low);
+                provenance::record("high", high);
+            }
+        }
+        Filter::design(&self.design, &band).filtfilt(x)
+    }
+}
+
+impl AugmenterBase for RandomFilter {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
@@ -1,5 +1,5 @@
-use crate::Dataset;
-use super::base::Augmenter;
+use crate::{Dataset, Float};
+use super::base::{Augmenter, AugmenterBase};
 use tracing::info_span;
 
 /// Augmenter that repeats all data rows `n` times
@@ -24,27 +24,30 @@ impl Repeat {
     }
 }
 
-impl Augmenter for Repeat {
-    fn augment_batch(&self, input: &mut Dataset, _parallel: bool, _per_sample: bool) {
+impl<T: Float> Augmenter<T> for Repeat {
+    fn augment_batch(&self, input: &mut Dataset<T>, _parallel: bool, _per_sample: bool) {
         let span = info_span!("", component = self.get_name());
         let _enter = span.enter();
 
-        let features: Vec<Vec<f64>> = input.features.clone();
+        let features: Vec<Vec<T>> = input.features.clone();
         let labels: Vec<String> = input.labels.clone();
 
         for _ in 0..self.n - 1 {
             input.features.append(&mut features.clone());
             input.labels.append(&mut labels.clone());
         }
+        crate::provenance::map_rows(input.features.len(), |i| i % features.len());
     }
 
     /// Not implemented!
-    fn augment_one(&self, _x: &[f64]) -> Vec<f64> {
+    fn augment_one(&self, _x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         unimplemented!("Repeat augmenter only works on a dataset directly!");
     }
+}
 
+impl AugmenterBase for Repeat {
     fn get_probability(&self) -> f64 {
         self.p
     }
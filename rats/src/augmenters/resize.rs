@@ -1,4 +1,5 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
 use tracing::{info_span};
 /// Changes temporal resolution of time series by changing the length
 ///
@@ -21,8 +22,8 @@ impl Resize {
     }
 }
 
-impl Augmenter for Resize {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Resize {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
 
@@ -31,7 +32,9 @@ impl Augmenter for Resize {
             .map(|i| x[(i as f64 * ratio) as usize])
             .collect()
     }
+}
 
+impl AugmenterBase for Resize {
     fn get_probability(&self) -> f64 {
         self.p
     }
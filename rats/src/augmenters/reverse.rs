@@ -1,4 +1,5 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
 use tracing::{info_span};
 /// Reverses time series
 /// 
@@ -17,14 +18,16 @@ impl Reverse {
     }
 }
 
-impl Augmenter for Reverse {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Reverse {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
 
         x.iter().rev().map(|v| *v).collect()
     }
+}
 
+impl AugmenterBase for Reverse {
     fn get_probability(&self) -> f64 {
         self.p
     }
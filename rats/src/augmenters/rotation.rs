@@ -1,4 +1,5 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
 use tracing::{info_span};
 /// Augmenter that rotates the data 180 degrees around `anchor`
 pub struct Rotation {
@@ -17,16 +18,24 @@ impl Rotation {
     }
 }
 
-impl Augmenter for Rotation {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Rotation {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
 
-        x.iter()
-            .map(|val| (*val - self.anchor) * -1.0 + self.anchor)
-            .collect()
+        let anchor = T::cast(self.anchor);
+        x.iter_mut().for_each(|val| *val = -(*val - anchor) + anchor);
+        true
     }
+}
 
+impl AugmenterBase for Rotation {
     fn get_probability(&self) -> f64 {
         self.p
     }
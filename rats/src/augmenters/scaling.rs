@@ -1,4 +1,8 @@
-use super::base::Augmenter;
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
 use tracing::{info_span};
 /// Augmenter that scales a time series with a random scalar within the range specified 
 /// by `min_factor` (inclusive) and `max_factor` (inclusive)
@@ -20,15 +24,26 @@ impl Scaling {
     }
 }
 
-impl Augmenter for Scaling {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for Scaling {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let mut res = x.to_vec();
+        self.augment_one_in_place(&mut res);
+        res
+    }
+
+    fn augment_one_in_place(&self, x: &mut [T]) -> bool {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
 
-        let scalar = rand::random_range(self.min_factor..=self.max_factor);
-        x.iter().map(|val| *val * scalar).collect()
+        let factor = rng().random_range(self.min_factor..=self.max_factor);
+        provenance::record("factor", factor);
+        let scalar = T::cast(factor);
+        x.iter_mut().for_each(|val| *val *= scalar);
+        true
     }
+}
 
+impl AugmenterBase for Scaling {
     fn get_probability(&self) -> f64 {
         self.p
     }
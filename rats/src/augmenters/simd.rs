@@ -0,0 +1,260 @@
+//! Vectorized kernels of the elementwise augmenters
+//!
+//! Random numbers are drawn in blocks of `BLOCK` values instead of one at a time, from a fast
+//! generator that is seeded once per series from the thread-local generator, and turned into
+//! uniform or normal values by branch-free arithmetic, which the compiler vectorizes. Normal values
+//! come from the Box–Muller transform with polynomial approximations of the logarithm, sine and
+//! cosine, accurate to about `1e-13`. With the `simd` feature, which needs a nightly compiler, the
+//! transform is written with `std::simd` instead.
+
+use crate::Float;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// Number of random values drawn at once
+pub(super) const BLOCK: usize = 256;
+
+const ONE_BITS: u64 = 0x3ff0_0000_0000_0000;
+const MANTISSA_BITS: u64 = 0x000f_ffff_ffff_ffff;
+const SIGN_BIT: u64 = 0x8000_0000_0000_0000;
+/// `2^52 + 1023`, the exponent bits of a float below `2^52` are read as the mantissa of this value
+const EXPONENT_MAGIC: f64 = 4_503_599_627_371_519.0;
+const EXPONENT_MAGIC_BITS: u64 = 0x4330_0000_0000_0000;
+
+/// Uniform value in `[0, 1)` from the upper 52 bits
+#[inline(always)]
+fn unit(bits: u64) -> f64 {
+    f64::from_bits((bits >> 12) | ONE_BITS) - 1.0
+}
+
+/// Divisors of the terms of the series of `ln`, `sin` and `cos`, innermost first
+const LN_DIVISORS: [f64; 6] = [11.0, 9.0, 7.0, 5.0, 3.0, 1.0];
+const SIN_DIVISORS: [f64; 6] = [156.0, 110.0, 72.0, 42.0, 20.0, 6.0];
+const COS_DIVISORS: [f64; 7] = [182.0, 132.0, 90.0, 56.0, 30.0, 12.0, 2.0];
+
+/// Natural logarithm of a positive normal value
+#[inline(always)]
+fn ln(x: f64) -> f64 {
+    let bits = x.to_bits();
+    let mut exponent = f64::from_bits(EXPONENT_MAGIC_BITS | (bits >> 52)) - EXPONENT_MAGIC;
+    let mut mantissa = f64::from_bits((bits & MANTISSA_BITS) | ONE_BITS);
+    // Keeps the mantissa within [1/sqrt(2), sqrt(2)) for a fast converging series
+    let large = mantissa > std::f64::consts::SQRT_2;
+    mantissa = if large { mantissa * 0.5 } else { mantissa };
+    exponent = if large { exponent + 1.0 } else { exponent };
+
+    // ln(m) = 2 * (s + s^3 / 3 + s^5 / 5 + ...) with s = (m - 1) / (m + 1)
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let s2 = s * s;
+    let mut series = 1.0 / 13.0;
+    for k in LN_DIVISORS {
+        series = 1.0 / k + s2 * series;
+    }
+    exponent * std::f64::consts::LN_2 + 2.0 * s * series
+}
+
+/// Sine and cosine of an angle within `[-pi/4, pi/4]`
+#[inline(always)]
+fn sin_cos(a: f64) -> (f64, f64) {
+    let a2 = a * a;
+    let mut sin = 1.0;
+    for k in SIN_DIVISORS {
+        sin = 1.0 - a2 * (1.0 / k) * sin;
+    }
+    let mut cos = 1.0;
+    for k in COS_DIVISORS {
+        cos = 1.0 - a2 * (1.0 / k) * cos;
+    }
+    (a * sin, cos)
+}
+
+/// Two independent standard normal values from two random words
+///
+/// The radius comes from `u`. The angle is uniform within `[-pi/4, pi/4]` and reflected onto the
+/// whole circle by the three lowest bits of `v`, which swap the sine and cosine and flip their signs.
+#[inline(always)]
+fn box_muller(u: u64, v: u64) -> (f64, f64) {
+    let radius = (-2.0 * ln(1.0 - unit(u))).sqrt();
+    let (sin, cos) = sin_cos((unit(v) - 0.5) * std::f64::consts::FRAC_PI_2);
+    let swap = v & 1 == 1;
+    let (a, b) = if swap { (cos, sin) } else { (sin, cos) };
+    let a = f64::from_bits((radius * a).to_bits() ^ ((v << 62) & SIGN_BIT));
+    let b = f64::from_bits((radius * b).to_bits() ^ ((v << 61) & SIGN_BIT));
+    (a, b)
+}
+
+#[cfg(feature = "simd")]
+mod lanes {
+    use super::*;
+    use std::simd::StdFloat;
+    use std::simd::prelude::*;
+
+    const LANES: usize = 4;
+    type F = Simd<f64, LANES>;
+    type U = Simd<u64, LANES>;
+
+    fn unit(bits: U) -> F {
+        F::from_bits((bits >> 12) | U::splat(ONE_BITS)) - F::splat(1.0)
+    }
+
+    fn ln(x: F) -> F {
+        let bits = x.to_bits();
+        let exponent =
+            F::from_bits(U::splat(EXPONENT_MAGIC_BITS) | (bits >> 52)) - F::splat(EXPONENT_MAGIC);
+        let mantissa = F::from_bits((bits & U::splat(MANTISSA_BITS)) | U::splat(ONE_BITS));
+        let large = mantissa.simd_gt(F::splat(std::f64::consts::SQRT_2));
+        let mantissa = large.select(mantissa * F::splat(0.5), mantissa);
+        let exponent = large.select(exponent + F::splat(1.0), exponent);
+
+        let one = F::splat(1.0);
+        let s = (mantissa - one) / (mantissa + one);
+        let s2 = s * s;
+        let mut series = F::splat(1.0 / 13.0);
+        for k in LN_DIVISORS {
+            series = F::splat(1.0 / k) + s2 * series;
+        }
+        exponent * F::splat(std::f64::consts::LN_2) + F::splat(2.0) * s * series
+    }
+
+    fn sin_cos(a: F) -> (F, F) {
+        let a2 = a * a;
+        let one = F::splat(1.0);
+        let mut sin = one;
+        for k in SIN_DIVISORS {
+            sin = one - a2 * F::splat(1.0 / k) * sin;
+        }
+        let mut cos = one;
+        for k in COS_DIVISORS {
+            cos = one - a2 * F::splat(1.0 / k) * cos;
+        }
+        (a * sin, cos)
+    }
+
+    /// Same as `super::box_muller` for `LANES` pairs at once
+    fn box_muller(u: U, v: U) -> (F, F) {
+        let radius = (F::splat(-2.0) * ln(F::splat(1.0) - unit(u))).sqrt();
+        let (sin, cos) = sin_cos((unit(v) - F::splat(0.5)) * F::splat(std::f64::consts::FRAC_PI_2));
+        let swap = (v & U::splat(1)).simd_eq(U::splat(1));
+        let a = swap.select(cos, sin);
+        let b = swap.select(sin, cos);
+        let sign = U::splat(SIGN_BIT);
+        let a = F::from_bits((radius * a).to_bits() ^ ((v << 62) & sign));
+        let b = F::from_bits((radius * b).to_bits() ^ ((v << 61) & sign));
+        (a, b)
+    }
+
+    /// Writes the pairs of `u` and `v` to `first` and `second`, returns the number of pairs written
+    pub(super) fn normal_pairs(
+        u: &[u64],
+        v: &[u64],
+        first: &mut [f64],
+        second: &mut [f64],
+    ) -> usize {
+        let n = u.len() / LANES * LANES;
+        for i in (0..n).step_by(LANES) {
+            let (a, b) = box_muller(U::from_slice(&u[i..]), U::from_slice(&v[i..]));
+            a.copy_to_slice(&mut first[i..i + LANES]);
+            b.copy_to_slice(&mut second[i..i + LANES]);
+        }
+        n
+    }
+}
+
+/// Generator for the values of one series, seeded from the thread-local generator
+///
+/// Drawing from the thread-local generator costs several times more per value.
+pub(super) fn series_rng() -> SmallRng {
+    SmallRng::from_rng(&mut crate::random::rng())
+}
+
+/// Fills `out` with uniform values in `[0, 1)`, at most `BLOCK` of them
+#[inline]
+pub(super) fn fill_uniform<R: Rng + ?Sized>(rng: &mut R, out: &mut [f64]) {
+    let mut bits = [0u64; BLOCK];
+    let bits = &mut bits[..out.len()];
+    rng.fill(bits);
+    for (o, b) in out.iter_mut().zip(bits.iter()) {
+        *o = unit(*b);
+    }
+}
+
+/// Fills `out` with standard normal values, at most `BLOCK` of them
+#[inline]
+pub(super) fn fill_normal<R: Rng + ?Sized>(rng: &mut R, out: &mut [f64]) {
+    let pairs = out.len().div_ceil(2);
+    let mut bits = [0u64; BLOCK];
+    let bits = &mut bits[..2 * pairs];
+    rng.fill(bits);
+    let (u, v) = bits.split_at(pairs);
+
+    // The first values of all pairs are written before the second ones
+    let mut values = [0.0; BLOCK];
+    let (first, second) = values[..2 * pairs].split_at_mut(pairs);
+    #[cfg(feature = "simd")]
+    let start = lanes::normal_pairs(u, v, first, second);
+    #[cfg(not(feature = "simd"))]
+    let start = 0;
+    let pairs = first[start..].iter_mut().zip(second[start..].iter_mut());
+    for ((a, b), (u, v)) in pairs.zip(u[start..].iter().zip(&v[start..])) {
+        (*a, *b) = box_muller(*u, *v);
+    }
+    out.copy_from_slice(&values[..out.len()]);
+}
+
+/// Adds normal noise of mean `mean` and standard deviation `std_dev` to every value of `x`
+pub(super) fn add_normal<T: Float, R: Rng + ?Sized>(
+    rng: &mut R,
+    x: &mut [T],
+    mean: f64,
+    std_dev: f64,
+) {
+    let mut noise = [0.0; BLOCK];
+    for chunk in x.chunks_mut(BLOCK) {
+        let noise = &mut noise[..chunk.len()];
+        fill_normal(rng, noise);
+        for (val, n) in chunk.iter_mut().zip(noise.iter()) {
+            *val += T::cast(n * std_dev + mean);
+        }
+    }
+}
+
+/// Adds uniform noise within `[low, high)` to every value of `x`
+pub(super) fn add_uniform<T: Float, R: Rng + ?Sized>(
+    rng: &mut R,
+    x: &mut [T],
+    low: f64,
+    high: f64,
+) {
+    let mut noise = [0.0; BLOCK];
+    for chunk in x.chunks_mut(BLOCK) {
+        let noise = &mut noise[..chunk.len()];
+        fill_uniform(rng, noise);
+        for (val, n) in chunk.iter_mut().zip(noise.iter()) {
+            *val += T::cast(low + n * (high - low));
+        }
+    }
+}
+
+/// Replaces every value of `x` with `default` with probability `percentage`, calling `dropped`
+/// with the index of every replaced value
+pub(super) fn drop_values<T: Float, R: Rng + ?Sized>(
+    rng: &mut R,
+    x: &mut [T],
+    percentage: f64,
+    default: T,
+    mut dropped: impl FnMut(usize),
+) {
+    let mut uniform = [0.0; BLOCK];
+    for (block, chunk) in x.chunks_mut(BLOCK).enumerate() {
+        let uniform = &mut uniform[..chunk.len()];
+        fill_uniform(rng, uniform);
+        for (i, (val, u)) in chunk.iter_mut().zip(uniform.iter()).enumerate() {
+            *val = if *u < percentage {
+                dropped(block * BLOCK + i);
+                default
+            } else {
+                *val
+            };
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::base::{Augmenter, AugmenterBase};
+use crate::{Float, provenance};
+use crate::random::rng;
+use crate::transforms::stft::{Spectrogram, StftParams, istft, stft};
+use rand::Rng;
+use rustfft::num_complex::Complex;
+use tracing::info_span;
+
+/// SpecAugment-style augmenter for time series
+///
+/// Each series is transformed into a spectrogram with the STFT described by `params`. Then
+/// `n_freq_masks` bands of up to `freq_mask_width` frequency bins and `n_time_masks` blocks of up
+/// to `time_mask_width` frames are zeroed out. The width and position of every mask is chosen
+/// randomly for each sample. Finally, the spectrogram is transformed back into a time series of
+/// the original length.
+///
+/// If `time_warp` is set, the spectrogram is additionally warped along the time axis before
+/// masking: a random frame is moved by up to `time_warp` frames and the frames on both sides of
+/// it are stretched or compressed linearly.
+pub struct SpecAugment {
+    pub name: String,
+    pub n_freq_masks: usize,
+    pub freq_mask_width: usize,
+    pub n_time_masks: usize,
+    pub time_mask_width: usize,
+    pub time_warp: Option<usize>,
+    pub params: StftParams,
+    p: f64,
+}
+
+impl SpecAugment {
+    pub fn new(
+        n_freq_masks: usize,
+        freq_mask_width: usize,
+        n_time_masks: usize,
+        time_mask_width: usize,
+        time_warp: Option<usize>,
+        params: StftParams,
+    ) -> Self {
+        SpecAugment {
+            name: "SpecAugment".to_string(),
+            n_freq_masks,
+            freq_mask_width,
+            n_time_masks,
+            time_mask_width,
+            time_warp,
+            params,
+            p: 1.0,
+        }
+    }
+
+    fn warp_time<T: Float>(
+        &self,
+        spectrogram: &mut Spectrogram<T>,
+        max_warp: usize,
+        rng: &mut impl Rng,
+    ) {
+        let n = spectrogram.n_frames();
+        if max_warp == 0 || n < 2 * max_warp + 2 {
+            return;
+        }
+
+        // Frame `center` is moved to `target`, frames in between are linearly interpolated
+        let center = rng.random_range(max_warp..n - max_warp) as f64;
+        let shift = rng.random_range(-(max_warp as f64)..=max_warp as f64);
+        let target = (center + shift).clamp(1.0, n as f64 - 2.0);
+        provenance::record("warp_center", center as usize);
+        provenance::record("warp_shift", shift);
+
+        let frames = &spectrogram.frames;
+        let warped = (0..n)
+            .map(|t| {
+                let t = t as f64;
+                let source = if t <= target {
+                    t * center / target
+                } else {
+                    center + (t - target) * (n as f64 - 1.0 - center) / (n as f64 - 1.0 - target)
+                };
+                let lo = source.floor() as usize;
+                let hi = (lo + 1).min(n - 1);
+                let w = T::cast(source - lo as f64);
+                frames[lo]
+                    .iter()
+                    .zip(frames[hi].iter())
+                    .map(|(&a, &b)| a * (T::one() - w) + b * w)
+                    .collect()
+            })
+            .collect();
+        spectrogram.frames = warped;
+    }
+
+    fn mask_frequencies<T: Float>(&self, spectrogram: &mut Spectrogram<T>, rng: &mut impl Rng) {
+        let n_bins = spectrogram.n_bins();
+        let mut starts = Vec::with_capacity(self.n_freq_masks);
+        let mut widths = Vec::with_capacity(self.n_freq_masks);
+        for _ in 0..self.n_freq_masks {
+            let width = rng.random_range(0..=self.freq_mask_width.min(n_bins));
+            let start = rng.random_range(0..=n_bins - width);
+            starts.push(start as f64);
+            widths.push(width as f64);
+            for frame in spectrogram.frames.iter_mut() {
+                frame[start..start + width].fill(Complex::new(T::zero(), T::zero()));
+            }
+        }
+        provenance::record("freq_mask_starts", starts.as_slice());
+        provenance::record("freq_mask_widths", widths.as_slice());
+    }
+
+    fn mask_time<T: Float>(&self, spectrogram: &mut Spectrogram<T>, rng: &mut impl Rng) {
+        let n_frames = spectrogram.n_frames();
+        let mut starts = Vec::with_capacity(self.n_time_masks);
+        let mut widths = Vec::with_capacity(self.n_time_masks);
+        for _ in 0..self.n_time_masks {
+            let width = rng.random_range(0..=self.time_mask_width.min(n_frames));
+            let start = rng.random_range(0..=n_frames - width);
+            starts.push(start as f64);
+            widths.push(width as f64);
+            for frame in spectrogram.frames[start..start + width].iter_mut() {
+                frame.fill(Complex::new(T::zero(), T::zero()));
+            }
+        }
+        provenance::record("time_mask_starts", starts.as_slice());
+        provenance::record("time_mask_widths", widths.as_slice());
+    }
+}
+
+impl<T: Float> Augmenter<T> for SpecAugment {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+        let mut rng = rng();
+
+        let mut spectrogram = stft(x, &self.params);
+        if let Some(max_warp) = self.time_warp {
+            self.warp_time(&mut spectrogram, max_warp, &mut rng);
+        }
+        self.mask_frequencies(&mut spectrogram, &mut rng);
+        self.mask_time(&mut spectrogram, &mut rng);
+
+        istft(&spectrogram)
+    }
+}
+
+impl AugmenterBase for SpecAugment {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
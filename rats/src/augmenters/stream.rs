@@ -0,0 +1,288 @@
+use super::base::{AugmentationPipeline, Augmenter, AugmenterBase};
+use crate::random;
+use crate::{Dataset, Float};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+use std::marker::PhantomData;
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread::Scope;
+
+/// Settings of the mini-batch stream created by `AugmentationPipeline::stream`
+///
+/// - `batch_size`: number of series per batch
+/// - `shuffle`: draw the series in a new random order every epoch instead of in dataset order
+/// - `seed`: seed for the order of the series. The order of epoch `e` only depends on `seed` and
+///   `e`, so it is reproducible. `AugmentationPipeline::stream` also seeds the augmentations of every
+///   batch by `seed` and the index of the batch, so the whole stream is reproducible.
+/// - `drop_last`: skip the last batch of an epoch if it has fewer than `batch_size` series
+/// - `per_sample`: chain the augmenters per sample, see `Augmenter::augment_batch`
+/// - `prefetch`: number of batches that are augmented at once in parallel and queued ahead
+/// - `epochs`: number of passes over the dataset, `None` streams indefinitely
+#[derive(Clone, Debug)]
+pub struct StreamConfig {
+    pub batch_size: usize,
+    pub shuffle: bool,
+    pub seed: Option<u64>,
+    pub drop_last: bool,
+    pub per_sample: bool,
+    pub prefetch: usize,
+    pub epochs: Option<usize>,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig {
+            batch_size: 32,
+            shuffle: true,
+            seed: None,
+            drop_last: false,
+            per_sample: false,
+            prefetch: rayon::current_num_threads(),
+            epochs: None,
+        }
+    }
+}
+
+/// A freshly augmented mini-batch
+///
+/// Series `i` of `dataset` was created from series `source[i]` of the streamed dataset during
+/// epoch `epoch`.
+pub struct Batch<T = f64> {
+    pub epoch: usize,
+    pub dataset: Dataset<T>,
+    pub source: Vec<usize>,
+}
+
+/// Draws the source rows of successive mini-batches of a dataset with `n` series, epoch by epoch
+///
+/// Yields the epoch and the row indices of every batch according to the `batch_size`, `shuffle`,
+/// `seed`, `drop_last` and `epochs` settings of a `StreamConfig`. Used by `AugmentedBatches` and
+/// useful for feeding other data loaders.
+pub struct BatchSampler {
+    config: StreamConfig,
+    order: Vec<usize>,
+    position: usize,
+    epoch: usize,
+}
+
+impl BatchSampler {
+    pub fn new(n: usize, config: StreamConfig) -> Self {
+        assert!(config.batch_size > 0, "Batch size must be greater than 0");
+        let mut sampler = BatchSampler {
+            config,
+            order: (0..n).collect(),
+            position: 0,
+            epoch: 0,
+        };
+        sampler.shuffle();
+        sampler
+    }
+
+    /// Epoch of the next batch
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// Number of batches in every epoch
+    pub fn batches_per_epoch(&self) -> usize {
+        let n = self.order.len();
+        if self.config.drop_last {
+            n / self.config.batch_size
+        } else {
+            n.div_ceil(self.config.batch_size)
+        }
+    }
+
+    fn shuffle(&mut self) {
+        if !self.config.shuffle {
+            return;
+        }
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(random::mix(seed, self.epoch as u64)),
+            None => StdRng::from_rng(&mut crate::random::rng()),
+        };
+        self.order.sort_unstable();
+        self.order.shuffle(&mut rng);
+    }
+}
+
+impl Iterator for BatchSampler {
+    type Item = (usize, Vec<usize>);
+
+    /// Epoch and source rows of the next batch, `None` once all epochs are done
+    fn next(&mut self) -> Option<(usize, Vec<usize>)> {
+        let n = self.order.len();
+        let min_size = if self.config.drop_last {
+            self.config.batch_size
+        } else {
+            1
+        };
+        if n < min_size {
+            return None;
+        }
+        loop {
+            if self
+                .config
+                .epochs
+                .is_some_and(|epochs| self.epoch >= epochs)
+            {
+                return None;
+            }
+            if n - self.position >= min_size {
+                break;
+            }
+            self.epoch += 1;
+            self.position = 0;
+            self.shuffle();
+        }
+        let end = (self.position + self.config.batch_size).min(n);
+        let rows = self.order[self.position..end].to_vec();
+        self.position = end;
+        Some((self.epoch, rows))
+    }
+}
+
+/// Iterator over augmented mini-batches of a borrowed dataset, created by
+/// `AugmentationPipeline::stream`
+///
+/// The batches are augmented ahead of time on a background thread of the scope passed to `stream`,
+/// while the caller consumes the previous ones: the thread draws the next `prefetch` batches, augments
+/// them in parallel on the rayon thread pool and queues them. At most `2 * prefetch` batches are
+/// held in memory, so every epoch sees new augmentations at constant memory. The thread stops when
+/// the iterator is dropped.
+pub struct AugmentedBatches<'scope, T: Float = f64> {
+    receiver: Receiver<Batch<T>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+/// Copies the series `rows` of `dataset` and augments them with `augmenter`
+fn augment_batch<T: Float>(
+    augmenter: &AugmentationPipeline<T>,
+    dataset: &Dataset<T>,
+    per_sample: bool,
+    epoch: usize,
+    rows: Vec<usize>,
+) -> Batch<T> {
+    let mut batch = Dataset {
+        features: rows.iter().map(|&i| dataset.features[i].clone()).collect(),
+        labels: rows
+            .iter()
+            .map(|&i| dataset.labels.get(i).cloned().unwrap_or_default())
+            .collect(),
+    };
+    // The batches are already augmented in parallel, so each one is augmented sequentially
+    augmenter.augment_batch(&mut batch, false, per_sample);
+    assert_eq!(
+        batch.features.len(),
+        rows.len(),
+        "Augmenter '{}' changes the number of series and cannot be streamed",
+        augmenter.get_name()
+    );
+    Batch {
+        epoch,
+        dataset: batch,
+        source: rows,
+    }
+}
+
+/// Augments the batches drawn by `sampler`, `prefetch` at a time, and sends them to `sender` until
+/// the receiver is dropped
+fn produce<T: Float>(
+    augmenter: &AugmentationPipeline<T>,
+    dataset: &Dataset<T>,
+    config: &StreamConfig,
+    mut sampler: BatchSampler,
+    sender: SyncSender<Batch<T>>,
+) {
+    let prefetch = config.prefetch.max(1);
+    let mut planned = 0;
+    loop {
+        let chunk: Vec<(usize, usize, Vec<usize>)> = sampler
+            .by_ref()
+            .take(prefetch)
+            .map(|(epoch, rows)| {
+                planned += 1;
+                (planned - 1, epoch, rows)
+            })
+            .collect();
+        if chunk.is_empty() {
+            return;
+        }
+        let batches: Vec<Batch<T>> = chunk
+            .into_par_iter()
+            .map(|(index, epoch, rows)| {
+                let augment = || augment_batch(augmenter, dataset, config.per_sample, epoch, rows);
+                match config.seed {
+                    Some(seed) => random::with_seed(random::mix(seed, index as u64), augment),
+                    None => augment(),
+                }
+            })
+            .collect();
+        for batch in batches {
+            if sender.send(batch).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl<T: Float> Iterator for AugmentedBatches<'_, T> {
+    type Item = Batch<T>;
+
+    fn next(&mut self) -> Option<Batch<T>> {
+        // Fails once the background thread is done
+        self.receiver.recv().ok()
+    }
+}
+
+impl<T: Float> AugmentationPipeline<T> {
+    /// Streams freshly augmented mini-batches of `dataset` without changing it
+    ///
+    /// Instead of materializing many augmented copies up front, e.g. with `Repeat`, the batches are
+    /// augmented on the fly, so every epoch sees new augmentations. The batches are augmented ahead of
+    /// time on a thread spawned in `scope`, which borrows the pipeline and the dataset, see
+    /// `AugmentedBatches`. The augmenters must not change the number of series.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rats_rs::Dataset;
+    /// use rats_rs::augmenters::*;
+    ///
+    /// let set = Dataset {
+    ///     features: vec![vec![1.0; 10]; 10],
+    ///     labels: vec![String::from("1"); 10],
+    /// };
+    ///
+    /// let pipeline = AugmentationPipeline::new() + Jittering::new(0.1);
+    /// let config = StreamConfig {
+    ///     batch_size: 4,
+    ///     seed: Some(42),
+    ///     epochs: Some(2),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let sizes: Vec<usize> = std::thread::scope(|scope| {
+    ///     pipeline
+    ///         .stream(scope, &set, config)
+    ///         .map(|batch| batch.dataset.features.len())
+    ///         .collect()
+    /// });
+    /// assert_eq!(sizes, vec![4, 4, 2, 4, 4, 2]);
+    /// ```
+    pub fn stream<'scope, 'env>(
+        &'env self,
+        scope: &'scope Scope<'scope, 'env>,
+        dataset: &'env Dataset<T>,
+        config: StreamConfig,
+    ) -> AugmentedBatches<'scope, T> {
+        let (sender, receiver) = sync_channel(config.prefetch.max(1));
+        let sampler = BatchSampler::new(dataset.features.len(), config.clone());
+        scope.spawn(move || produce(self, dataset, &config, sampler, sender));
+        AugmentedBatches {
+            receiver,
+            _scope: PhantomData,
+        }
+    }
+}
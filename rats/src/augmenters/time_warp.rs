@@ -1,5 +1,8 @@
-use super::base::Augmenter;
-use rand::{Rng, rng};
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::provenance;
+use crate::random::rng;
+use rand::Rng;
 use tracing:: {info, info_span};
 
 /// Augmenter that applies random time warping to the dataset
@@ -28,12 +31,13 @@ impl RandomTimeWarpAugmenter {
         }
     }
 
-    fn warp_series(series: &[f64],speed_ratio_range: (f64, f64),rng: &mut impl Rng)-> Vec<f64> {
+    fn warp_series<T: Float>(series: &[T],speed_ratio_range: (f64, f64),rng: &mut impl Rng)-> Vec<T> {
         let len = series.len();
         if len < 2 { return series.to_vec(); }
 
         // random number between the min anfd max speeed is picked to warp
         let warp_ratio = rng.random_range(speed_ratio_range.0..=speed_ratio_range.1);
+        provenance::record("warp_ratio", warp_ratio);
 
         let times: Vec<f64> = (0..len).map(|i| (i as f64) / warp_ratio).collect();
 
@@ -45,14 +49,14 @@ impl RandomTimeWarpAugmenter {
                 series[lo]
             } else {
                 let w = t - lo as f64;
-                series[lo] * (1.0 - w) + series[hi] * w
+                series[lo] * T::cast(1.0 - w) + series[hi] * T::cast(w)
             }
             }).collect()
         }
 }
 
-impl Augmenter for RandomTimeWarpAugmenter {
-    fn augment_one(&self, x: &[f64]) -> Vec<f64> {
+impl<T: Float> Augmenter<T> for RandomTimeWarpAugmenter {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
         let span = info_span!("", step = "augment_one");
         let _enter = span.enter();
         let mut rng = rng();
@@ -65,6 +69,7 @@ impl Augmenter for RandomTimeWarpAugmenter {
             let start_index = rng.random_range(0..len - self.window_size);
             (start_index, start_index + self.window_size)
         };
+        provenance::record("window_start", window_start);
         info!("window selected from : {:?} to {:?} ", window_start, window_end);
         let warped_series = Self::warp_series(
             &series[window_start..=window_end],
@@ -76,7 +81,9 @@ impl Augmenter for RandomTimeWarpAugmenter {
 
         series
     }
+}
 
+impl AugmenterBase for RandomTimeWarpAugmenter {
     fn get_probability(&self) -> f64 {
         self.p
     }
@@ -0,0 +1,85 @@
+use super::base::{Augmenter, AugmenterBase};
+use crate::{Float, provenance};
+use crate::random::rng;
+use crate::transforms::wavelet::{Wavelet, wavedec, waverec};
+use rand::Rng;
+use tracing::info_span;
+
+/// Augmenter that drops detail coefficients of a discrete wavelet decomposition
+///
+/// Each series is decomposed with the wavelet `wavelet` down to the deepest level listed in
+/// `levels`. Of the detail coefficients of every level in `levels` (level 1 being the finest
+/// scale), a fraction `percentage` of the coefficients is set to zero, then the series is
+/// reconstructed. With a `percentage` of 1.0, the selected levels are removed completely.
+///
+/// Levels deeper than the series length allows are ignored.
+pub struct WaveletDrop {
+    pub name: String,
+    pub wavelet: Wavelet,
+    pub levels: Vec<usize>,
+    pub percentage: f64,
+    p: f64,
+}
+
+impl WaveletDrop {
+    pub fn new(wavelet: Wavelet, levels: Vec<usize>, percentage: f64) -> Self {
+        assert!(wavelet.is_supported(), "Unsupported wavelet: {:?}", wavelet);
+        assert!(
+            (0.0..=1.0).contains(&percentage),
+            "Percentage must be in [0, 1]"
+        );
+        WaveletDrop {
+            name: "WaveletDrop".to_string(),
+            wavelet,
+            levels,
+            percentage,
+            p: 1.0,
+        }
+    }
+}
+
+impl<T: Float> Augmenter<T> for WaveletDrop {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+
+        let depth = self.levels.iter().copied().max().unwrap_or(0);
+        let mut coefficients = wavedec(x, &self.wavelet, depth);
+
+        // The levels that were dropped from and the number of coefficients dropped of each
+        let mut levels = Vec::new();
+        let mut dropped = Vec::new();
+        for &level in &self.levels {
+            if level == 0 || level > coefficients.level() {
+                continue;
+            }
+            let mut count = 0;
+            coefficients.details[level - 1].iter_mut().for_each(|v| {
+                if rng().random::<f64>() < self.percentage {
+                    *v = T::zero();
+                    count += 1;
+                }
+            });
+            levels.push(level as f64);
+            dropped.push(count as f64);
+        }
+        provenance::record("levels", levels.as_slice());
+        provenance::record("dropped", dropped.as_slice());
+
+        waverec(&coefficients)
+    }
+}
+
+impl AugmenterBase for WaveletDrop {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
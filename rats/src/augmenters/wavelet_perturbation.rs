@@ -0,0 +1,79 @@
+use super::base::{Augmenter, AugmenterBase};
+use crate::Float;
+use crate::random::rng;
+use crate::transforms::wavelet::{Wavelet, wavedec, waverec};
+use rand_distr::{Distribution, Normal};
+use tracing::info_span;
+
+/// Augmenter that perturbs the detail coefficients of a discrete wavelet decomposition
+///
+/// Each series is decomposed with the wavelet `wavelet` down to the deepest level listed in
+/// `levels`. Gaussian noise is added to the detail coefficients of every level in `levels`
+/// (level 1 being the finest scale), then the series is reconstructed. The standard deviation of
+/// the noise is `std_dev` times the standard deviation of the coefficients of that level, so the
+/// perturbation adapts to the energy present at each scale.
+///
+/// Levels deeper than the series length allows are ignored.
+pub struct WaveletPerturbation {
+    pub name: String,
+    pub wavelet: Wavelet,
+    pub levels: Vec<usize>,
+    pub std_dev: f64,
+    p: f64,
+}
+
+impl WaveletPerturbation {
+    pub fn new(wavelet: Wavelet, levels: Vec<usize>, std_dev: f64) -> Self {
+        assert!(wavelet.is_supported(), "Unsupported wavelet: {:?}", wavelet);
+        assert!(std_dev >= 0.0, "Standard deviation must not be negative");
+        WaveletPerturbation {
+            name: "WaveletPerturbation".to_string(),
+            wavelet,
+            levels,
+            std_dev,
+            p: 1.0,
+        }
+    }
+}
+
+impl<T: Float> Augmenter<T> for WaveletPerturbation {
+    fn augment_one(&self, x: &[T]) -> Vec<T> {
+        let span = info_span!("", step = "augment_one");
+        let _enter = span.enter();
+        let mut rng = rng();
+
+        let depth = self.levels.iter().copied().max().unwrap_or(0);
+        let mut coefficients = wavedec(x, &self.wavelet, depth);
+
+        for &level in &self.levels {
+            if level == 0 || level > coefficients.level() {
+                continue;
+            }
+            let detail = &mut coefficients.details[level - 1];
+            let n = detail.len() as f64;
+            let mean = detail.iter().map(|v| v.as_f64()).sum::<f64>() / n;
+            let level_std =
+                (detail.iter().map(|v| (v.as_f64() - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+            let dist = Normal::new(0.0, self.std_dev * level_std)
+                .expect("Couldn't create normal distribution from specified standard deviation");
+            detail.iter_mut().for_each(|v| *v += T::cast(dist.sample(&mut rng)));
+        }
+
+        waverec(&coefficients)
+    }
+}
+
+impl AugmenterBase for WaveletPerturbation {
+    fn get_probability(&self) -> f64 {
+        self.p
+    }
+
+    fn set_probability(&mut self, probability: f64) {
+        self.p = probability;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+}
@@ -0,0 +1,143 @@
+use super::CliError;
+use std::str::FromStr;
+
+/// An option a command accepts, `--long` or `-short`, which either takes a value or is a flag
+pub struct Opt {
+    pub long: &'static str,
+    pub short: Option<char>,
+    pub takes_value: bool,
+}
+
+impl Opt {
+    pub const fn value(long: &'static str, short: Option<char>) -> Self {
+        Opt {
+            long,
+            short,
+            takes_value: true,
+        }
+    }
+
+    pub const fn flag(long: &'static str) -> Self {
+        Opt {
+            long,
+            short: None,
+            takes_value: false,
+        }
+    }
+}
+
+/// Options accepted by every command
+const COMMON: [Opt; 1] = [Opt::flag("sequential")];
+
+/// Arguments of a command, split into positional arguments and options
+///
+/// Options are given as `--long value`, `--long=value` or `-s value`. A flag has the value `None`.
+pub struct Args {
+    positional: Vec<String>,
+    options: Vec<(&'static str, Option<String>)>,
+}
+
+impl Args {
+    /// Splits `args` according to the options `opts` of the command
+    pub fn parse(args: &[String], opts: &[Opt]) -> Result<Self, CliError> {
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (opt, inline) = if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let opt = opts
+                    .iter()
+                    .chain(&COMMON)
+                    .find(|o| o.long == name)
+                    .ok_or_else(|| CliError::Usage(format!("Unknown option '--{}'", name)))?;
+                (opt, inline)
+            } else if arg.len() == 2 && arg.starts_with('-') && arg != "--" {
+                let short = arg.chars().nth(1);
+                let opt = opts
+                    .iter()
+                    .find(|o| o.short.is_some() && o.short == short)
+                    .ok_or_else(|| CliError::Usage(format!("Unknown option '{}'", arg)))?;
+                (opt, None)
+            } else {
+                positional.push(arg.clone());
+                continue;
+            };
+
+            let value = match (opt.takes_value, inline) {
+                (true, Some(value)) => Some(value),
+                (true, None) => Some(args.next().cloned().ok_or_else(|| {
+                    CliError::Usage(format!("Option '--{}' needs a value", opt.long))
+                })?),
+                (false, Some(_)) => {
+                    return Err(CliError::Usage(format!(
+                        "Option '--{}' does not take a value",
+                        opt.long
+                    )));
+                }
+                (false, None) => None,
+            };
+            options.push((opt.long, value));
+        }
+        Ok(Args {
+            positional,
+            options,
+        })
+    }
+
+    /// The positional arguments, which must be exactly as many as `names`
+    pub fn positional(&self, names: &[&str]) -> Result<&[String], CliError> {
+        if self.positional.len() < names.len() {
+            return Err(CliError::Usage(format!(
+                "Missing argument <{}>",
+                names[self.positional.len()]
+            )));
+        }
+        if self.positional.len() > names.len() {
+            return Err(CliError::Usage(format!(
+                "Unexpected argument '{}'",
+                self.positional[names.len()]
+            )));
+        }
+        Ok(&self.positional)
+    }
+
+    /// Whether the flag `long` was given
+    pub fn flag(&self, long: &str) -> bool {
+        self.options.iter().any(|(name, _)| *name == long)
+    }
+
+    /// The value of the option `long`, the last one if it was given repeatedly
+    pub fn value(&self, long: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == long)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// The value of the option `long`, which must be given
+    pub fn required(&self, long: &str) -> Result<&str, CliError> {
+        self.value(long)
+            .ok_or_else(|| CliError::Usage(format!("Missing option '--{}'", long)))
+    }
+
+    /// The value of the option `long` parsed as `N`, if it was given
+    pub fn parsed<N: FromStr>(&self, long: &str) -> Result<Option<N>, CliError> {
+        self.value(long)
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    CliError::Usage(format!("Invalid value '{}' for option '--{}'", value, long))
+                })
+            })
+            .transpose()
+    }
+
+    /// Whether the command may use multiple threads
+    pub fn parallel(&self) -> bool {
+        !self.flag("sequential")
+    }
+}
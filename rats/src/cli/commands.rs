@@ -0,0 +1,305 @@
+use super::CliError;
+use super::args::{Args, Opt};
+use super::io::{read_dataset, write_dataset};
+use super::pipeline::parse_pipeline;
+use rats_rs::Dataset;
+use rats_rs::augmenters::Augmenter;
+use rats_rs::quality_benchmarking::knn::KnnClassifier;
+use rats_rs::quality_benchmarking::report::Summary;
+use rats_rs::quality_benchmarking::*;
+use rats_rs::transforms::dct::{dataset_dct, dataset_idct};
+use rats_rs::transforms::fastfourier::{dataset_fft, dataset_ifft};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+
+/// Fails if the dataset has no series, which none of the commands can work with
+fn non_empty(dataset: Dataset, path: &str) -> Result<Dataset, CliError> {
+    if dataset.features.is_empty() {
+        return Err(CliError::Failure(format!("'{}' contains no series", path)));
+    }
+    Ok(dataset)
+}
+
+/// Whether all series of the datasets have the same length
+fn equal_lengths(datasets: &[&Dataset]) -> bool {
+    let mut lengths = datasets
+        .iter()
+        .flat_map(|d| d.features.iter().map(Vec::len));
+    let first = lengths.next();
+    lengths.all(|len| Some(len) == first)
+}
+
+/// `rats augment <input> -p <pipeline> -o <output>`
+pub fn augment(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(
+        args,
+        &[
+            Opt::value("pipeline", Some('p')),
+            Opt::value("output", Some('o')),
+            Opt::value("seed", Some('s')),
+            Opt::value("copies", Some('n')),
+            Opt::flag("keep-original"),
+            Opt::flag("per-sample"),
+        ],
+    )?;
+    let input = &args.positional(&["input"])?[0];
+    let output = args.required("output")?;
+    let spec = args.required("pipeline")?;
+    let copies = args.parsed::<usize>("copies")?.unwrap_or(1);
+    let per_sample = args.flag("per-sample");
+
+    // The spec is read from a file if there is one with this name
+    let spec = if Path::new(spec).is_file() {
+        std::fs::read_to_string(spec)
+            .map_err(|e| CliError::Failure(format!("Cannot read '{}': {}", spec, e)))?
+    } else {
+        spec.to_string()
+    };
+    let pipeline = parse_pipeline(&spec).map_err(CliError::Usage)?;
+    if per_sample
+        && let Some(augmenter) = pipeline
+            .augmenters()
+            .iter()
+            .find(|a| !a.supports_per_sample())
+    {
+        return Err(CliError::Usage(format!(
+            "Augmenter '{}' does not support --per-sample",
+            augmenter.get_name()
+        )));
+    }
+    let data = non_empty(read_dataset(input)?, input)?;
+
+    rats_rs::random::set_seed(args.parsed("seed")?);
+    let start = Instant::now();
+    let mut augmented = Dataset {
+        features: Vec::new(),
+        labels: Vec::new(),
+    };
+    for _ in 0..copies {
+        let mut copy = data.clone();
+        pipeline.augment_batch(&mut copy, args.parallel(), per_sample);
+        augmented.features.extend(copy.features);
+        augmented.labels.extend(copy.labels);
+    }
+    if args.flag("keep-original") {
+        augmented.features.extend(data.features.iter().cloned());
+        augmented.labels.extend(data.labels.iter().cloned());
+    }
+    let elapsed = start.elapsed();
+
+    write_dataset(output, &augmented)?;
+    println!(
+        "Augmented {} series into {} series in {:?}, written to {}",
+        data.features.len(),
+        augmented.features.len(),
+        elapsed,
+        output
+    );
+    Ok(())
+}
+
+/// `rats transform <fft|dct> <input> -o <output>`
+pub fn transform(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(
+        args,
+        &[Opt::value("output", Some('o')), Opt::flag("inverse")],
+    )?;
+    let positional = args.positional(&["transform", "input"])?;
+    let (kind, input) = (positional[0].to_lowercase(), &positional[1]);
+    let output = args.required("output")?;
+    let transform = match (kind.as_str(), args.flag("inverse")) {
+        ("fft", false) => dataset_fft,
+        ("fft", true) => dataset_ifft,
+        ("dct", false) => dataset_dct,
+        ("dct", true) => dataset_idct,
+        _ => {
+            return Err(CliError::Usage(format!(
+                "Unknown transform '{}', expected fft or dct",
+                kind
+            )));
+        }
+    };
+    let data = non_empty(read_dataset(input)?, input)?;
+    if kind == "fft" && args.flag("inverse") && data.features.iter().any(|x| x.len() % 2 != 0) {
+        return Err(CliError::Failure(
+            "The inverse FFT needs interleaved real and imaginary parts, but a series has an odd length"
+                .to_string(),
+        ));
+    }
+
+    let transformed = transform(&data, args.parallel());
+    write_dataset(output, &transformed)?;
+    println!(
+        "Transformed {} series, written to {}",
+        transformed.features.len(),
+        output
+    );
+    Ok(())
+}
+
+/// `rats benchmark <original> <augmented>`
+///
+/// Augmented series `i` is compared to original series `i % n`, which is the series it was created
+/// from if the augmented dataset consists of copies of the original one, as written by `augment`.
+pub fn benchmark(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(
+        args,
+        &[
+            Opt::value("radius", Some('r')),
+            Opt::value("neighbors", Some('k')),
+        ],
+    )?;
+    let positional = args.positional(&["original", "augmented"])?;
+    let constraint = match args.parsed("radius")? {
+        Some(radius) => DtwConstraint::SakoeChiba(radius),
+        None => DtwConstraint::None,
+    };
+    let k = args.parsed::<usize>("neighbors")?.unwrap_or(1);
+    if k == 0 {
+        return Err(CliError::Usage(
+            "The number of neighbors must be positive".to_string(),
+        ));
+    }
+    let parallel = args.parallel();
+    let original = non_empty(read_dataset(&positional[0])?, &positional[0])?;
+    let augmented = non_empty(read_dataset(&positional[1])?, &positional[1])?;
+
+    let n = original.features.len();
+    let distances: Vec<f64> = augmented
+        .features
+        .iter()
+        .enumerate()
+        .map(|(i, x)| dtw_distance(&original.features[i % n], x, constraint, None))
+        .collect();
+    let dtw = Summary::new(&distances);
+
+    let equal = equal_lengths(&[&original, &augmented]);
+    let metric = if equal {
+        DistanceMetric::Euclidean
+    } else {
+        DistanceMetric::Dtw(constraint)
+    };
+    let min_length = original
+        .features
+        .iter()
+        .chain(&augmented.features)
+        .map(Vec::len)
+        .min()
+        .unwrap_or(0);
+
+    println!("original:  {} series", n);
+    println!("augmented: {} series", augmented.features.len());
+    println!();
+    println!(
+        "DTW distance     mean {:.6}  std {:.6}  min {:.6}  median {:.6}  max {:.6}",
+        dtw.mean, dtw.std, dtw.min, dtw.median, dtw.max
+    );
+    if equal {
+        let ks = |results: Vec<KsResult>| {
+            let statistics: Vec<f64> = results.iter().map(|r| r.statistic).collect();
+            let rejected = results.iter().filter(|r| r.p_value < 0.05).count();
+            (Summary::new(&statistics).mean, rejected, results.len())
+        };
+        let (statistic, rejected, total) = ks(per_timestep_ks(&original, &augmented, parallel));
+        println!(
+            "KS per time step mean statistic {:.6}, p < 0.05 at {} of {} time steps",
+            statistic, rejected, total
+        );
+        let (statistic, rejected, total) = ks(spectral_ks(&original, &augmented, parallel));
+        println!(
+            "KS per frequency mean statistic {:.6}, p < 0.05 at {} of {} frequencies",
+            statistic, rejected, total
+        );
+        println!(
+            "MMD (RBF)        {:.6}",
+            mmd_rbf(&original, &augmented, None, parallel)
+        );
+        println!(
+            "PSD distance     {:.6}",
+            psd_distance(&original, &augmented, parallel)
+        );
+    } else {
+        println!("The series differ in length, skipping the KS tests, MMD and PSD distance");
+    }
+    if min_length > 1 {
+        let max_lag = (min_length - 1).min(10);
+        println!(
+            "ACF distance     {:.6} (lags 1 to {})",
+            acf_distance(&original, &augmented, max_lag, parallel),
+            max_lag
+        );
+    }
+    println!(
+        "Diversity        original {:.6}, augmented {:.6}",
+        diversity(&original, metric, parallel),
+        diversity(&augmented, metric, parallel)
+    );
+    let knn = KnnClassifier::new(k, metric);
+    println!(
+        "{}-NN accuracy     trained on augmented, tested on original {:.4}",
+        k,
+        knn.accuracy(&augmented, &original, parallel)
+    );
+    Ok(())
+}
+
+/// `rats inspect <input>`
+pub fn inspect(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(args, &[])?;
+    let input = &args.positional(&["input"])?[0];
+    let data = read_dataset(input)?;
+
+    let lengths: Vec<f64> = data.features.iter().map(|x| x.len() as f64).collect();
+    let lengths = Summary::new(&lengths);
+    let values: Vec<f64> = data
+        .features
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|v| !v.is_nan())
+        .collect();
+    let total: usize = data.features.iter().map(Vec::len).sum();
+    let rows_with_nan = data
+        .features
+        .iter()
+        .filter(|x| x.iter().any(|v| v.is_nan()))
+        .count();
+    let mut classes: BTreeMap<&str, usize> = BTreeMap::new();
+    for label in &data.labels {
+        *classes.entry(label).or_default() += 1;
+    }
+
+    println!("file:     {}", input);
+    println!("series:   {}", data.features.len());
+    if lengths.min == lengths.max {
+        println!("length:   {}", lengths.min);
+    } else {
+        println!(
+            "length:   min {}, median {}, max {}",
+            lengths.min, lengths.median, lengths.max
+        );
+    }
+    println!("classes:  {}", classes.len());
+    for (label, count) in &classes {
+        println!(
+            "  {:<12} {:>8} ({:.1}%)",
+            label,
+            count,
+            100.0 * *count as f64 / data.labels.len() as f64
+        );
+    }
+    if !values.is_empty() {
+        let stats = Summary::new(&values);
+        println!(
+            "values:   min {}, max {}, mean {:.6}, std {:.6}",
+            stats.min, stats.max, stats.mean, stats.std
+        );
+    }
+    println!(
+        "missing:  {} NaN values in {} series",
+        total - values.len(),
+        rows_with_nan
+    );
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use super::CliError;
+use rats_rs::Dataset;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// File format of a dataset, detected from the file extension
+///
+/// - `Csv`: comma-separated with a header row, the label in the last column
+/// - `Tsv`: tab-separated without a header, the label in the first column, as in the UCR archive
+/// - `Txt`: separated by any whitespace without a header, the label in the first column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Tsv,
+    Txt,
+}
+
+impl Format {
+    pub fn from_path(path: &str) -> Result<Self, CliError> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("tsv") => Ok(Format::Tsv),
+            Some("txt") => Ok(Format::Txt),
+            _ => Err(CliError::Usage(format!(
+                "Cannot detect the format of '{}', expected a .csv, .tsv or .txt file",
+                path
+            ))),
+        }
+    }
+}
+
+/// Fields of every non-empty line of a file with their line numbers, without the CSV header
+fn records(path: &str, format: Format) -> Result<Vec<(usize, Vec<String>)>, CliError> {
+    let error =
+        |e: &dyn std::fmt::Display| CliError::Failure(format!("Cannot read '{}': {}", path, e));
+    let file = File::open(path).map_err(|e| error(&e))?;
+
+    let mut records = Vec::new();
+    if format == Format::Csv {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(file);
+        for record in reader.records() {
+            let record = record.map_err(|e| error(&e))?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            records.push((line, record.iter().map(|f| f.trim().to_string()).collect()));
+        }
+    } else {
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| error(&e))?;
+            let fields: Vec<String> = match format {
+                Format::Tsv => line.split('\t').map(|f| f.trim().to_string()).collect(),
+                _ => line.split_whitespace().map(str::to_string).collect(),
+            };
+            records.push((i + 1, fields));
+        }
+    }
+    records.retain(|(_, fields)| fields.iter().any(|f| !f.is_empty()));
+    Ok(records)
+}
+
+/// Reads a dataset, detecting its format from the extension of `path`
+///
+/// Empty lines are skipped. Values that are not numbers are reported with their line.
+pub fn read_dataset(path: &str) -> Result<Dataset, CliError> {
+    let format = Format::from_path(path)?;
+    let mut dataset = Dataset {
+        features: Vec::new(),
+        labels: Vec::new(),
+    };
+    for (line, mut fields) in records(path, format)? {
+        let label = match format {
+            Format::Csv => fields.pop().unwrap(),
+            Format::Tsv | Format::Txt => fields.remove(0),
+        };
+        let series = fields
+            .iter()
+            .map(|v| {
+                v.parse::<f64>().map_err(|_| {
+                    CliError::Failure(format!("{}:{}: invalid value '{}'", path, line, v))
+                })
+            })
+            .collect::<Result<Vec<f64>, _>>()?;
+        dataset.features.push(series);
+        dataset.labels.push(label);
+    }
+    Ok(dataset)
+}
+
+/// Writes a dataset in the format given by the extension of `path`
+///
+/// CSV files get the header `t_0,...,t_{n-1},label` of the longest series.
+pub fn write_dataset(path: &str, dataset: &Dataset) -> Result<(), CliError> {
+    let format = Format::from_path(path)?;
+    let error =
+        |e: &dyn std::fmt::Display| CliError::Failure(format!("Cannot write '{}': {}", path, e));
+    let file = File::create(path).map_err(|e| error(&e))?;
+
+    if format == Format::Csv {
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(file);
+        let length = dataset.features.iter().map(Vec::len).max().unwrap_or(0);
+        let mut header: Vec<String> = (0..length).map(|i| format!("t_{}", i)).collect();
+        header.push("label".to_string());
+        writer.write_record(&header).map_err(|e| error(&e))?;
+        for (series, label) in dataset.features.iter().zip(&dataset.labels) {
+            let mut record: Vec<String> = series.iter().map(f64::to_string).collect();
+            record.push(label.clone());
+            writer.write_record(&record).map_err(|e| error(&e))?;
+        }
+        return writer.flush().map_err(|e| error(&e));
+    }
+
+    let separator = if format == Format::Tsv { "\t" } else { " " };
+    let mut out = BufWriter::new(file);
+    for (series, label) in dataset.features.iter().zip(&dataset.labels) {
+        let values: Vec<String> = series.iter().map(f64::to_string).collect();
+        writeln!(out, "{}{}{}", label, separator, values.join(separator)).map_err(|e| error(&e))?;
+    }
+    out.flush().map_err(|e| error(&e))
+}
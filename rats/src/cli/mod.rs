@@ -0,0 +1,143 @@
+//! Subcommands of the `rats` binary and their shared error handling
+//!
+//! Every command returns a `CliError` instead of exiting, `run` maps it to the exit code: 0 on
+//! success, 1 if a command failed on the data or the file system and 2 for invalid usage. Panics of
+//! the library, e.g. an augmenter rejecting the length of the series, are reported like failures,
+//! except for panics of the constructors of augmenters, which reject the parameters of the pipeline.
+
+mod args;
+mod commands;
+mod io;
+mod pipeline;
+
+use std::any::Any;
+use std::fmt;
+use std::panic;
+use std::process::ExitCode;
+
+/// Error of a command
+///
+/// - `Usage`: the command line is invalid, e.g. an unknown option, file format or augmenter
+/// - `Failure`: the command could not be completed, e.g. a file could not be read
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Failure(String),
+}
+
+impl CliError {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            CliError::Usage(_) => ExitCode::from(2),
+            CliError::Failure(_) => ExitCode::from(1),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n\nRun 'rats help' for usage.", message),
+            CliError::Failure(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+const USAGE: &str = "\
+Rapid Augmentations for Time Series
+
+Usage: rats <command> [options]
+
+Commands:
+  augment <input> -p <pipeline> -o <output>
+        Augment a dataset with a pipeline of augmenters
+        -p, --pipeline <spec>   pipeline spec, or a file containing one
+        -o, --output <file>     file to write the augmented dataset to
+        -s, --seed <n>          seed for reproducible augmentation
+        -n, --copies <k>        number of augmented copies of every series [default: 1]
+        --keep-original         append the unaugmented series to the output
+        --per-sample            chain the augmenters per series instead of per batch
+  transform <fft|dct> <input> -o <output>
+        Transform every series to the frequency domain
+        -o, --output <file>     file to write the transformed dataset to
+        --inverse               transform back to the time domain instead
+  benchmark <original> <augmented>
+        Compare an augmented dataset to the original one with DTW and distribution metrics
+        -r, --radius <n>        radius of the Sakoe-Chiba band for DTW [default: none]
+        -k, --neighbors <k>     neighbors of the kNN classifier [default: 1]
+  inspect <input>
+        Print summary statistics of a dataset
+  help
+        Print this message
+
+Options of all commands:
+  --sequential            do not use multiple threads
+  -h, --help              print this message
+  -V, --version           print the version
+
+Formats are detected from the file extension:
+  .csv    comma-separated with a header row, the label in the last column
+  .tsv    tab-separated without a header, the label in the first column (UCR archive)
+  .txt    whitespace-separated without a header, the label in the first column
+
+Pipelines are augmenters joined by '+' or newlines, with '#' starting a comment. Parameters are
+given by the names of the fields of the augmenter, e.g.
+  \"Crop(size=100) + Jittering(deviation=0.1, p=0.5) + Scaling(min_factor=0.5, max_factor=2)\"
+Pairs and lists are written in brackets, e.g. 'speed_ratio_range=[0.5, 2]'. Every augmenter accepts
+the probability 'p'.
+
+Exit codes: 0 on success, 1 if the command failed, 2 for invalid usage.
+";
+
+/// Message of a panic, the library panics with a `&str` or a `String`
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown error")
+}
+
+/// Runs the command given by the arguments, without the name of the binary
+pub fn run(args: &[String]) -> ExitCode {
+    // Panics are reported once below instead of with a backtrace hint. The hook would run for every
+    // rayon thread that panics, while only the first panic reaches `catch_unwind`.
+    panic::set_hook(Box::new(|_| {}));
+
+    match panic::catch_unwind(|| dispatch(args)) {
+        Ok(Ok(())) => ExitCode::SUCCESS,
+        Ok(Err(error)) => {
+            eprintln!("error: {}", error);
+            error.exit_code()
+        }
+        Err(payload) => {
+            eprintln!("error: {}", panic_message(&*payload));
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn dispatch(args: &[String]) -> Result<(), CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(CliError::Usage("No command given".to_string()));
+    };
+    if rest.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    match command.as_str() {
+        "augment" => commands::augment(rest),
+        "transform" => commands::transform(rest),
+        "benchmark" => commands::benchmark(rest),
+        "inspect" => commands::inspect(rest),
+        "help" | "-h" | "--help" => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        "-V" | "--version" => {
+            println!("rats {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        }
+        other => Err(CliError::Usage(format!("Unknown command '{}'", other))),
+    }
+}
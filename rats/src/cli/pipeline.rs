@@ -0,0 +1,508 @@
+//! Parses the pipeline spec of the `augment` command
+//!
+//! A spec lists augmenters joined by `+` or newlines, e.g.
+//! `Crop(size=100) + Jittering(deviation=0.1, p=0.5)`. Everything after `#` on a line is a comment.
+//! Augmenter names are matched ignoring case and underscores, parameters are given by the names of
+//! the fields of the augmenter. Pairs and lists are written as `[a, b]`.
+
+use super::panic_message;
+use rats_rs::augmenters::*;
+use rats_rs::transforms::filter::FilterDesign;
+use rats_rs::transforms::stft::WindowFunction;
+use rats_rs::transforms::wavelet::Wavelet;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+/// Splits `s` at every `separator` that is not nested in brackets or parentheses
+fn split_top_level(s: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            c if depth == 0 && separator(c) => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parameters of one augmenter in the spec, removed as they are read so leftovers can be reported
+struct Params {
+    augmenter: String,
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    fn take(&mut self, key: &str) -> Option<String> {
+        let i = self.values.iter().position(|(k, _)| k == key)?;
+        Some(self.values.remove(i).1)
+    }
+
+    fn parse<N: FromStr>(&self, key: &str, value: &str) -> Result<N, String> {
+        value.parse().map_err(|_| {
+            format!(
+                "{}: invalid value '{}' for '{}'",
+                self.augmenter, value, key
+            )
+        })
+    }
+
+    fn optional<N: FromStr>(&mut self, key: &str) -> Result<Option<N>, String> {
+        self.take(key)
+            .map(|value| self.parse(key, &value))
+            .transpose()
+    }
+
+    fn required<N: FromStr>(&mut self, key: &str) -> Result<N, String> {
+        self.optional(key)?
+            .ok_or_else(|| format!("{}: missing parameter '{}'", self.augmenter, key))
+    }
+
+    fn list<N: FromStr>(&mut self, key: &str) -> Result<Option<Vec<N>>, String> {
+        let Some(value) = self.take(key) else {
+            return Ok(None);
+        };
+        let inner = value
+            .strip_prefix('[')
+            .and_then(|v| v.strip_suffix(']'))
+            .ok_or_else(|| format!("{}: '{}' must be a list like [a, b]", self.augmenter, key))?;
+        inner
+            .split(',')
+            .map(|v| self.parse(key, v.trim()))
+            .collect::<Result<Vec<N>, _>>()
+            .map(Some)
+    }
+
+    fn pair(&mut self, key: &str) -> Result<Option<(f64, f64)>, String> {
+        match self.list(key)? {
+            Some(values) if values.len() == 2 => Ok(Some((values[0], values[1]))),
+            Some(_) => Err(format!(
+                "{}: '{}' must be a pair like [a, b]",
+                self.augmenter, key
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn required_pair(&mut self, key: &str) -> Result<(f64, f64), String> {
+        self.pair(key)?
+            .ok_or_else(|| format!("{}: missing parameter '{}'", self.augmenter, key))
+    }
+
+    /// A word out of `choices`, matched ignoring case and underscores
+    fn choice(&mut self, key: &str, choices: &[&'static str]) -> Result<&'static str, String> {
+        let value = self
+            .take(key)
+            .ok_or_else(|| format!("{}: missing parameter '{}'", self.augmenter, key))?;
+        let normalized = normalize(&value);
+        choices
+            .iter()
+            .find(|&&choice| choice == normalized)
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "{}: unknown {} '{}', expected one of {}",
+                    self.augmenter,
+                    key,
+                    value,
+                    choices.join(", ")
+                )
+            })
+    }
+
+    /// Fails with `message` unless the parameters of the augmenter are `valid`
+    fn check(&self, valid: bool, message: &str) -> Result<(), String> {
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("{}: {}", self.augmenter, message))
+        }
+    }
+
+    /// Fails if any parameter was not read
+    fn finish(self) -> Result<(), String> {
+        match self.values.first() {
+            Some((key, _)) => Err(format!("{}: unknown parameter '{}'", self.augmenter, key)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Lowercase name without underscores, so `AddNoise`, `add_noise` and `addnoise` are the same
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|&c| c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Sets the probability `p` of the augmenter if given and adds it to the pipeline
+fn push<A: Augmenter + Sync + 'static>(
+    pipeline: &mut AugmentationPipeline,
+    mut augmenter: A,
+    p: Option<f64>,
+) {
+    if let Some(p) = p {
+        augmenter.set_probability(p);
+    }
+    pipeline.add(augmenter);
+}
+
+/// Parses one augmenter like `Jittering(deviation=0.1)` and adds it to the pipeline
+fn add_augmenter(pipeline: &mut AugmentationPipeline, item: &str) -> Result<(), String> {
+    let (name, arguments) = match item.split_once('(') {
+        Some((name, rest)) => {
+            let arguments = rest
+                .trim_end()
+                .strip_suffix(')')
+                .ok_or_else(|| format!("Missing ')' in '{}'", item))?;
+            (name.trim(), arguments)
+        }
+        None => (item, ""),
+    };
+    let mut params = Params {
+        augmenter: name.to_string(),
+        values: Vec::new(),
+    };
+    for argument in split_top_level(arguments, |c| c == ',') {
+        if argument.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = argument
+            .split_once('=')
+            .ok_or_else(|| format!("{}: expected key=value, got '{}'", name, argument.trim()))?;
+        params
+            .values
+            .push((key.trim().to_string(), value.trim().to_string()));
+    }
+    let p = params.optional::<f64>("p")?;
+    if p.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+        return Err(format!("{}: probability 'p' must be in [0, 1]", name));
+    }
+
+    match normalize(name).as_str() {
+        "addnoise" => {
+            let noise_type =
+                match params.choice("noise_type", &["uniform", "gaussian", "spike", "slope"])? {
+                    "uniform" => NoiseType::Uniform,
+                    "gaussian" => NoiseType::Gaussian,
+                    "spike" => NoiseType::Spike,
+                    _ => NoiseType::Slope,
+                };
+            let bounds = params.pair("bounds")?;
+            let mean = params.optional("mean")?;
+            let std_dev = params.optional::<f64>("std_dev")?;
+            if let NoiseType::Gaussian = noise_type {
+                params.check(
+                    mean.is_some() && std_dev.is_some(),
+                    "Gaussian noise needs 'mean' and 'std_dev'",
+                )?;
+                params.check(
+                    std_dev.is_some_and(|s| s >= 0.0 && s.is_finite()),
+                    "'std_dev' must not be negative",
+                )?;
+            } else {
+                params.check(
+                    bounds.is_some_and(|(low, high)| low < high && (high - low).is_finite()),
+                    "'bounds' must be given as [low, high] with low < high",
+                )?;
+            }
+            push(
+                pipeline,
+                AddNoise::new(noise_type, bounds, mean, std_dev),
+                p,
+            );
+        }
+        "amplitudephaseperturbation" => {
+            let magnitude_std: f64 = params.required("magnitude_std")?;
+            let phase_std: f64 = params.required("phase_std")?;
+            params.check(
+                magnitude_std >= 0.0 && phase_std >= 0.0,
+                "'magnitude_std' and 'phase_std' must not be negative",
+            )?;
+            let augmenter = AmplitudePhasePerturbation::new(
+                magnitude_std,
+                phase_std,
+                params.optional("is_time_domain")?.unwrap_or(true),
+            );
+            push(pipeline, augmenter, p);
+        }
+        "convolve" => {
+            let windows = [
+                "flat",
+                "gaussian",
+                "triangular",
+                "hann",
+                "hamming",
+                "blackman",
+                "exponential",
+            ];
+            let window = match params.choice("window", &windows)? {
+                "flat" => ConvolveWindow::Flat,
+                "gaussian" => ConvolveWindow::Gaussian,
+                "triangular" => ConvolveWindow::Triangular,
+                "hann" => ConvolveWindow::Hann,
+                "hamming" => ConvolveWindow::Hamming,
+                "blackman" => ConvolveWindow::Blackman,
+                _ => ConvolveWindow::Exponential(params.required("tau")?),
+            };
+            push(pipeline, Convolve::new(window, params.required("size")?), p);
+        }
+        "crop" => {
+            let size = params.required("size")?;
+            params.check(size > 0, "'size' must be greater than 0")?;
+            push(pipeline, Crop::new(size), p);
+        }
+        "drift" => {
+            let max_drift: f64 = params.required("max_drift")?;
+            params.check(
+                max_drift >= 0.0 && max_drift.is_finite(),
+                "'max_drift' must not be negative",
+            )?;
+            let augmenter = Drift::new(max_drift, params.required("n_drift_points")?);
+            push(pipeline, augmenter, p);
+        }
+        "drop" => {
+            let percentage = params.required("percentage")?;
+            params.check(
+                (0.0..=1.0).contains(&percentage),
+                "'percentage' must be in [0, 1]",
+            )?;
+            let augmenter = Drop::new(percentage, params.optional("default")?);
+            push(pipeline, augmenter, p);
+        }
+        "frequencymask" => {
+            let augmenter = FrequencyMask::new(
+                params.required("mask_width")?,
+                params.optional("is_time_domain")?.unwrap_or(true),
+            );
+            push(pipeline, augmenter, p);
+        }
+        "jittering" => {
+            let deviation: f64 = params.required("deviation")?;
+            params.check(
+                deviation >= 0.0 && deviation.is_finite(),
+                "'deviation' must not be negative",
+            )?;
+            push(pipeline, Jittering::new(deviation), p);
+        }
+        "permutate" => {
+            let window_size = params.required("window_size")?;
+            let segment_size = params.required("segment_size")?;
+            params.check(
+                window_size > 0 && segment_size > 0,
+                "'window_size' and 'segment_size' must be greater than 0",
+            )?;
+            push(pipeline, Permutate::new(window_size, segment_size), p);
+        }
+        "pool" => {
+            let kind = match params.choice("kind", &["max", "min", "average"])? {
+                "max" => PoolingMethod::Max,
+                "min" => PoolingMethod::Min,
+                _ => PoolingMethod::Average,
+            };
+            let size = params.required("size")?;
+            params.check(size > 0, "'size' must be greater than 0")?;
+            push(pipeline, Pool::new(kind, size), p);
+        }
+        "quantize" => {
+            let levels = params.required("levels")?;
+            params.check(levels > 0, "'levels' must be greater than 0")?;
+            push(pipeline, Quantize::new(levels), p);
+        }
+        "randomfilter" => {
+            let filter_types = ["lowpass", "highpass", "bandpass", "bandstop"];
+            let filter_type = match params.choice("filter_type", &filter_types)? {
+                "lowpass" => FilterType::LowPass,
+                "highpass" => FilterType::HighPass,
+                "bandpass" => FilterType::BandPass,
+                _ => FilterType::BandStop,
+            };
+            let cutoff_range = params.required_pair("cutoff_range")?;
+            let design = match (params.optional("order")?, params.optional("num_taps")?) {
+                (Some(_), Some(_)) => {
+                    return Err(format!("{}: give either 'order' or 'num_taps'", name));
+                }
+                (_, Some(num_taps)) => FilterDesign::WindowedSinc {
+                    num_taps,
+                    window: WindowFunction::Hamming,
+                },
+                (order, None) => FilterDesign::Butterworth {
+                    order: order.unwrap_or(4),
+                },
+            };
+            push(
+                pipeline,
+                RandomFilter::new(filter_type, cutoff_range, design),
+                p,
+            );
+        }
+        "repeat" => push(pipeline, Repeat::new(params.required("n")?), p),
+        "resize" => {
+            let size = params.required("size")?;
+            params.check(size > 0, "'size' must be greater than 0")?;
+            push(pipeline, Resize::new(size), p);
+        }
+        "reverse" => push(pipeline, Reverse::new(), p),
+        "rotation" => push(pipeline, Rotation::new(params.required("anchor")?), p),
+        "scaling" => {
+            let min_factor: f64 = params.required("min_factor")?;
+            let max_factor: f64 = params.required("max_factor")?;
+            params.check(
+                min_factor <= max_factor,
+                "'min_factor' must not be greater than 'max_factor'",
+            )?;
+            push(pipeline, Scaling::new(min_factor, max_factor), p);
+        }
+        "randomtimewarpaugmenter" | "timewarp" => {
+            let window_size = params.required("window_size")?;
+            let (min, max) = params.required_pair("speed_ratio_range")?;
+            params.check(
+                0.0 < min && min <= max && max.is_finite(),
+                "'speed_ratio_range' must be [min, max] with 0 < min <= max",
+            )?;
+            push(pipeline, RandomTimeWarpAugmenter::new(window_size, (min, max)), p);
+        }
+        "waveletdrop" | "waveletperturbation" => {
+            let wavelet: Wavelet = params
+                .take("wavelet")
+                .ok_or_else(|| format!("{}: missing parameter 'wavelet'", name))?
+                .parse()
+                .map_err(|e| format!("{}: {}", name, e))?;
+            let levels = params
+                .list("levels")?
+                .ok_or_else(|| format!("{}: missing parameter 'levels'", name))?;
+            if normalize(name) == "waveletdrop" {
+                let percentage = params.required("percentage")?;
+                params.check(
+                    (0.0..=1.0).contains(&percentage),
+                    "'percentage' must be in [0, 1]",
+                )?;
+                let augmenter = WaveletDrop::new(wavelet, levels, percentage);
+                push(pipeline, augmenter, p);
+            } else {
+                let augmenter =
+                    WaveletPerturbation::new(wavelet, levels, params.required("std_dev")?);
+                push(pipeline, augmenter, p);
+            }
+        }
+        "randaugment" => {
+            let augmenter = RandAugment::new(params.required("n")?, params.required("magnitude")?);
+            push(pipeline, augmenter, p);
+        }
+        "trivialaugment" => push(pipeline, TrivialAugment::new(), p),
+        _ => return Err(format!("Unknown augmenter '{}'", name)),
+    }
+    params.finish()
+}
+
+/// Parses a pipeline spec into a pipeline
+///
+/// The constructors of the augmenters validate the parameters by panicking, such panics are
+/// returned as errors.
+pub fn parse_pipeline(spec: &str) -> Result<AugmentationPipeline, String> {
+    let spec: Vec<&str> = spec
+        .lines()
+        .map(|line| line.split_once('#').map_or(line, |(code, _)| code))
+        .collect();
+    let spec = spec.join("\n");
+
+    let mut pipeline = AugmentationPipeline::new();
+    for item in split_top_level(&spec, |c| c == '+' || c == '\n') {
+        let item = item.trim();
+        if !item.is_empty() {
+            panic::catch_unwind(AssertUnwindSafe(|| add_augmenter(&mut pipeline, item)))
+                .map_err(|payload| format!("{}: {}", item, panic_message(&*payload)))??;
+        }
+    }
+    if pipeline.augmenters().is_empty() {
+        return Err("The pipeline contains no augmenters".to_string());
+    }
+    Ok(pipeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(pipeline: &AugmentationPipeline) -> Vec<String> {
+        pipeline
+            .augmenters()
+            .iter()
+            .map(|a| a.get_name())
+            .collect()
+    }
+
+    #[test]
+    fn augmenters_joined_by_plus_and_newlines() {
+        let pipeline = parse_pipeline(
+            "Crop(size=10) + jittering(deviation=0.1)\n# a comment\nadd_noise(noise_type=Gaussian, mean=0, std_dev=1) # trailing\n",
+        )
+        .unwrap();
+        assert_eq!(names(&pipeline), ["Crop", "Jittering", "AddNoise"]);
+    }
+
+    #[test]
+    fn bracketed_pairs_and_lists() {
+        let pipeline = parse_pipeline(
+            "TimeWarp(window_size=4, speed_ratio_range=[0.5, 2]) + WaveletDrop(wavelet=db2, levels=[1, 2, 3], percentage=0.5) + RandomFilter(filter_type=lowpass, cutoff_range=[0.1,0.3])",
+        )
+        .unwrap();
+        assert_eq!(pipeline.augmenters().len(), 3);
+
+        let error = parse_pipeline("TimeWarp(window_size=4, speed_ratio_range=[0.5, 1, 2])")
+            .err()
+            .unwrap();
+        assert!(error.contains("must be a pair"), "{}", error);
+        let error = parse_pipeline("TimeWarp(window_size=4, speed_ratio_range=0.5)")
+            .err()
+            .unwrap();
+        assert!(error.contains("must be a list"), "{}", error);
+        let error = parse_pipeline("WaveletDrop(wavelet=db2, levels=[1, x], percentage=0.5)")
+            .err()
+            .unwrap();
+        assert!(error.contains("invalid value 'x'"), "{}", error);
+    }
+
+    #[test]
+    fn probability() {
+        let pipeline = parse_pipeline("Reverse(p=0.25) + Reverse").unwrap();
+        let probabilities: Vec<f64> = pipeline
+            .augmenters()
+            .iter()
+            .map(|a| a.get_probability())
+            .collect();
+        assert_eq!(probabilities, [0.25, 1.0]);
+
+        assert!(parse_pipeline("Reverse(p=1.5)").is_err());
+        assert!(parse_pipeline("Reverse(p=high)").is_err());
+    }
+
+    #[test]
+    fn invalid_specs() {
+        for (spec, message) in [
+            ("", "no augmenters"),
+            ("Foo(size=3)", "Unknown augmenter 'Foo'"),
+            ("Crop(size=3, width=4)", "unknown parameter 'width'"),
+            ("Crop()", "missing parameter 'size'"),
+            ("Crop(size)", "expected key=value"),
+            ("Crop(size=3", "Missing ')'"),
+            ("Pool(kind=median, size=3)", "unknown kind 'median'"),
+            ("Jittering(deviation=-1)", "'deviation' must not be negative"),
+            ("Scaling(min_factor=2, max_factor=1)", "must not be greater"),
+            ("AddNoise(noise_type=uniform)", "'bounds' must be given"),
+            ("Pool(kind=max, size=0)", "'size' must be greater than 0"),
+            ("Repeat(n=0)", "Repeat(n=0): "),
+        ] {
+            let error = parse_pipeline(spec).err().unwrap();
+            assert!(error.contains(message), "{}: {}", spec, error);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Contiguous storage for datasets of equal-length time series
+//!
+//! `Dataset` stores every series in its own allocation, so series of different lengths can be
+//! mixed and augmenters may change the length or number of series. When all series have the same
+//! length, `DenseDataset` stores them in one contiguous buffer of shape `(samples, channels, length)`
+//! instead, which is friendlier to the cache and can be shared with `ndarray` and numpy without
+//! copying.
+//!
+//! # Examples
+//! ```
+//! use rats_rs::DenseDataset;
+//! use rats_rs::augmenters::{Augmenter, Scaling};
+//! use rats_rs::transforms::fastfourier::dense_fft;
+//!
+//! let mut set = DenseDataset::new(vec![1.0; 40], 4, vec![String::from("1"); 4]);
+//! assert_eq!(set.shape(), (4, 1, 10));
+//!
+//! Scaling::new(2.0, 2.0).augment_dense(&mut set, true);
+//! assert_eq!(set.series(3, 0), &[2.0; 10]);
+//!
+//! let spectrum = dense_fft(&set, true);
+//! assert_eq!(spectrum.series_length(), 20);
+//! ```
+
+use crate::{Dataset, Float};
+use ndarray::{Array2, Array3, ArrayView2, ArrayView3, ArrayViewMut3};
+use rayon::prelude::*;
+
+/// Holds equal-length time series with their labels in one contiguous buffer
+///
+/// The buffer has the row-major shape `(samples, channels, length)`, so the channels of a sample and
+/// the samples themselves follow each other. Univariate datasets have one channel. Each sample has
+/// one label.
+///
+/// Augmenters work on it with `Augmenter::augment_dense`, the transforms with the `dense_*`
+/// functions of their modules.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseDataset<T = f64> {
+    data: Vec<T>,
+    samples: usize,
+    channels: usize,
+    length: usize,
+    pub labels: Vec<String>,
+}
+
+impl<T: Float> DenseDataset<T> {
+    /// Creates a univariate dataset from `data` holding `samples` series one after the other
+    pub fn new(data: Vec<T>, samples: usize, labels: Vec<String>) -> Self {
+        let length = data.len().checked_div(samples).unwrap_or(0);
+        DenseDataset::with_channels(data, samples, 1, length, labels)
+    }
+
+    /// Creates a dataset from `data` in the row-major shape `(samples, channels, length)`
+    pub fn with_channels(
+        data: Vec<T>,
+        samples: usize,
+        channels: usize,
+        length: usize,
+        labels: Vec<String>,
+    ) -> Self {
+        assert_eq!(
+            data.len(),
+            samples * channels * length,
+            "Data does not have the shape ({}, {}, {})",
+            samples,
+            channels,
+            length
+        );
+        DenseDataset {
+            data,
+            samples,
+            channels,
+            length,
+            labels,
+        }
+    }
+
+    /// Copies a dataset of univariate series, which must all have the same length
+    pub fn from_dataset(dataset: &Dataset<T>) -> Self {
+        let length = dataset.features.first().map_or(0, Vec::len);
+        assert!(
+            dataset.features.iter().all(|x| x.len() == length),
+            "All series must have the same length"
+        );
+        DenseDataset::with_channels(
+            dataset.features.concat(),
+            dataset.features.len(),
+            1,
+            length,
+            dataset.labels.clone(),
+        )
+    }
+
+    /// Copies the series into a `Dataset` with one row per channel of every sample
+    ///
+    /// The label of a sample is repeated for each of its channels.
+    pub fn to_dataset(&self) -> Dataset<T> {
+        Dataset {
+            features: self.all_series().map(<[T]>::to_vec).collect(),
+            labels: (0..self.samples * self.channels)
+                .map(|i| {
+                    self.labels
+                        .get(i / self.channels.max(1))
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .collect(),
+        }
+    }
+
+    /// Takes over a univariate dataset of shape `(samples, length)` without copying if it is in
+    /// standard layout
+    pub fn from_array2(array: Array2<T>, labels: Vec<String>) -> Self {
+        let (samples, length) = array.dim();
+        DenseDataset::with_channels(into_vec(array), samples, 1, length, labels)
+    }
+
+    /// Takes over a dataset of shape `(samples, channels, length)` without copying if it is in
+    /// standard layout
+    pub fn from_array3(array: Array3<T>, labels: Vec<String>) -> Self {
+        let (samples, channels, length) = array.dim();
+        DenseDataset::with_channels(into_vec(array), samples, channels, length, labels)
+    }
+
+    /// Moves the data into an array of shape `(samples, channels, length)` without copying
+    pub fn into_array3(self) -> Array3<T> {
+        Array3::from_shape_vec((self.samples, self.channels, self.length), self.data).unwrap()
+    }
+
+    /// Borrows the data as an array of shape `(samples, channels, length)`
+    pub fn view(&self) -> ArrayView3<'_, T> {
+        ArrayView3::from_shape((self.samples, self.channels, self.length), &self.data).unwrap()
+    }
+
+    /// Borrows the data mutably as an array of shape `(samples, channels, length)`
+    pub fn view_mut(&mut self) -> ArrayViewMut3<'_, T> {
+        ArrayViewMut3::from_shape((self.samples, self.channels, self.length), &mut self.data)
+            .unwrap()
+    }
+
+    /// Borrows the data as an array of shape `(samples * channels, length)` with one row per series
+    pub fn series_view(&self) -> ArrayView2<'_, T> {
+        ArrayView2::from_shape((self.samples * self.channels, self.length), &self.data).unwrap()
+    }
+
+    /// Shape `(samples, channels, length)` of the data
+    pub fn shape(&self) -> (usize, usize, usize) {
+        (self.samples, self.channels, self.length)
+    }
+
+    pub fn n_samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn n_channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of values in each series
+    pub fn series_length(&self) -> usize {
+        self.length
+    }
+
+    /// The whole buffer in row-major order
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// All channels of sample `i`, one after the other
+    pub fn sample(&self, i: usize) -> &[T] {
+        let size = self.channels * self.length;
+        &self.data[i * size..(i + 1) * size]
+    }
+
+    pub fn sample_mut(&mut self, i: usize) -> &mut [T] {
+        let size = self.channels * self.length;
+        &mut self.data[i * size..(i + 1) * size]
+    }
+
+    /// Channel `c` of sample `i`
+    pub fn series(&self, i: usize, c: usize) -> &[T] {
+        let start = (i * self.channels + c) * self.length;
+        &self.data[start..start + self.length]
+    }
+
+    pub fn series_mut(&mut self, i: usize, c: usize) -> &mut [T] {
+        let start = (i * self.channels + c) * self.length;
+        &mut self.data[start..start + self.length]
+    }
+
+    /// Iterates over all series, channel by channel and sample by sample
+    pub fn all_series(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.samples * self.channels)
+            .map(|k| &self.data[k * self.length..(k + 1) * self.length])
+    }
+
+    /// Calls `f` with the index, channels and label of every sample
+    ///
+    /// The channels of a sample are passed as one slice of `channels * length` values. Samples
+    /// without a label get an empty one. Parallelized using rayon when `parallel` is set.
+    pub fn for_each_sample<F>(&mut self, parallel: bool, f: F)
+    where
+        F: Fn(usize, &mut [T], &str) + Sync,
+    {
+        if self.data.is_empty() {
+            return;
+        }
+        let size = self.channels * self.length;
+        let labels = &self.labels;
+        let apply =
+            |(i, sample): (usize, &mut [T])| f(i, sample, labels.get(i).map_or("", String::as_str));
+        if parallel {
+            self.data.par_chunks_mut(size).enumerate().for_each(apply);
+        } else {
+            self.data.chunks_mut(size).enumerate().for_each(apply);
+        }
+    }
+
+    /// Transforms every series into a series of `length` values
+    ///
+    /// `f` is called with each series and writes its result into the output slice. The labels are
+    /// kept. Parallelized using rayon when `parallel` is set.
+    pub fn map_series<F>(&self, length: usize, parallel: bool, f: F) -> DenseDataset<T>
+    where
+        F: Fn(&[T], &mut [T]) + Sync,
+    {
+        let series = self.samples * self.channels;
+        let mut data = vec![T::zero(); series * length];
+        if series > 0 && length > 0 {
+            let input = self.length.max(1);
+            if parallel {
+                data.par_chunks_mut(length)
+                    .zip(self.data.par_chunks(input))
+                    .for_each(|(out, x)| f(x, out));
+            } else {
+                data.chunks_mut(length)
+                    .zip(self.data.chunks(input))
+                    .for_each(|(out, x)| f(x, out));
+            }
+        }
+        DenseDataset::with_channels(
+            data,
+            self.samples,
+            self.channels,
+            length,
+            self.labels.clone(),
+        )
+    }
+
+    /// Copies the samples `rows` into a new dataset
+    pub fn select(&self, rows: &[usize]) -> DenseDataset<T> {
+        let mut data = Vec::with_capacity(rows.len() * self.channels * self.length);
+        for &i in rows {
+            data.extend_from_slice(self.sample(i));
+        }
+        DenseDataset::with_channels(
+            data,
+            rows.len(),
+            self.channels,
+            self.length,
+            rows.iter()
+                .map(|&i| self.labels.get(i).cloned().unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// Copies the samples of `subset` back to the positions `rows`
+    pub fn set_samples(&mut self, rows: &[usize], subset: &DenseDataset<T>) {
+        assert_eq!(
+            (subset.channels, subset.length),
+            (self.channels, self.length),
+            "The subset must have the same number of channels and length"
+        );
+        for (k, &i) in rows.iter().enumerate() {
+            self.sample_mut(i).copy_from_slice(subset.sample(k));
+        }
+    }
+
+    /// Overwrites the series with those of `dataset` in the order of `to_dataset`
+    ///
+    /// Panics if `dataset` does not hold as many series of the same length.
+    pub fn copy_from_dataset(&mut self, dataset: &Dataset<T>) {
+        assert_eq!(
+            dataset.features.len(),
+            self.samples * self.channels,
+            "The number of series changed"
+        );
+        for (i, x) in dataset.features.iter().enumerate() {
+            assert_eq!(x.len(), self.length, "The length of the series changed");
+            self.data[i * self.length..(i + 1) * self.length].copy_from_slice(x);
+        }
+    }
+}
+
+impl<T: Float> From<&Dataset<T>> for DenseDataset<T> {
+    fn from(dataset: &Dataset<T>) -> Self {
+        DenseDataset::from_dataset(dataset)
+    }
+}
+
+impl<T: Float> From<&DenseDataset<T>> for Dataset<T> {
+    fn from(dataset: &DenseDataset<T>) -> Self {
+        dataset.to_dataset()
+    }
+}
+
+/// Moves the elements of an array into a vector in row-major order, copying only if necessary
+fn into_vec<T: Clone, D: ndarray::Dimension>(array: ndarray::Array<T, D>) -> Vec<T> {
+    if array.is_standard_layout() {
+        let len = array.len();
+        let (data, offset) = array.into_raw_vec_and_offset();
+        let offset = offset.unwrap_or(0);
+        if offset == 0 && data.len() == len {
+            data
+        } else {
+            data[offset..offset + len].to_vec()
+        }
+    } else {
+        array.iter().cloned().collect()
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+
+/// Floating point type of the values of time series, implemented for `f32` and `f64`
+///
+/// Datasets, augmenters and transforms are generic over it, so data can be kept in single precision
+/// instead of being converted. Parameters of augmenters, like standard deviations or factors, are
+/// always `f64` and converted with `cast` where they are applied. `f64` is the default everywhere.
+pub trait Float:
+    num_traits::Float
+    + num_traits::FloatConst
+    + num_traits::NumAssign
+    + num_traits::FromPrimitive
+    + rustfft::FftNum
+    + Sum
+    + Default
+    + Debug
+    + Display
+    + Send
+    + Sync
+    + 'static
+{
+    /// Converts an `f64`, rounding to the nearest value of this type
+    fn cast(x: f64) -> Self;
+
+    /// Converts to `f64` without loss
+    fn as_f64(self) -> f64;
+}
+
+impl Float for f32 {
+    fn cast(x: f64) -> Self {
+        x as f32
+    }
+
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Float for f64 {
+    fn cast(x: f64) -> Self {
+        x
+    }
+
+    fn as_f64(self) -> f64 {
+        self
+    }
+}
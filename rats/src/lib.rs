@@ -6,15 +6,26 @@
 //! and passing it around is `Dataset`.
 //! 
 //! Python bindings for this crate exist as well under `RATSpy`.
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 pub mod augmenters;
 pub mod transforms;
 pub mod quality_benchmarking;
+pub mod provenance;
+pub mod random;
+mod dense;
+mod float;
+
+pub use dense::DenseDataset;
+pub use float::Float;
 
 /// Holds multiple univariate time series with their labels
 /// 
-/// Passed to the `augment_batch` function from augmenters
-pub struct Dataset {
-    pub features: Vec<Vec<f64>>,
+/// Passed to the `augment_batch` function from augmenters. Equal-length series can be stored
+/// contiguously in a `DenseDataset` instead. The values are `f64` unless another `Float` type is
+/// given, e.g. `Dataset<f32>`.
+#[derive(Clone)]
+pub struct Dataset<T = f64> {
+    pub features: Vec<Vec<T>>,
     pub labels: Vec<String>,
 }
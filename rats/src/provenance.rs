@@ -0,0 +1,355 @@
+//! Records which augmenters changed every series and with which random parameters
+//!
+//! `AugmentationPipeline::augment_batch_with_provenance` augments a batch like `augment_batch` and
+//! returns the provenance of every output series: the input series it was created from and one
+//! `Step` for every augmenter that drew whether to augment it, with the parameters the augmenter drew
+//! for it, e.g. the start of a crop or the factor of a scaling. The provenance can be exported as
+//! JSON to trace back suspicious series or to compare augmented data with and without an augmenter.
+//!
+//! Augmenters report their parameters with `record`, which does nothing unless the series is
+//! recorded, so the augmentations cost the same outside of provenance mode. Augmenters that change
+//! the number of series report where the new series come from with `map_rows`.
+//!
+//! # Example
+//!
+//! ```
+//! use rats_rs::Dataset;
+//! use rats_rs::augmenters::*;
+//! use rats_rs::provenance::Value;
+//!
+//! let mut set = Dataset {
+//!     features: vec![vec![1.0; 100]; 4],
+//!     labels: vec![String::from("1"); 4],
+//! };
+//!
+//! let pipeline = AugmentationPipeline::new() + Repeat::new(2) + Crop::new(20) + Scaling::new(0.5, 2.0);
+//! let provenance = pipeline.augment_batch_with_provenance(&mut set, true, false);
+//!
+//! assert_eq!(provenance.rows.len(), 8);
+//! assert_eq!(provenance.rows[5].source, 1);
+//! let scaling = &provenance.rows[5].steps[1];
+//! assert_eq!(scaling.augmenter, "Scaling");
+//! let Value::Number(factor) = scaling.params[0].1 else { panic!() };
+//! assert_eq!(set.features[5][0], factor);
+//! # assert!(provenance.to_json().contains("\"start\""));
+//! ```
+
+use crate::quality_benchmarking::report::{json_array, json_number, json_string};
+use std::cell::RefCell;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+/// A parameter an augmenter drew for one series
+///
+/// - `Number`: e.g. a scaling factor or a cutoff frequency
+/// - `Integer`: e.g. the start of a crop or the index of a spike
+/// - `List`: e.g. the drift points of `Drift`
+/// - `Text`: e.g. the op chosen by `TrivialAugment`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Integer(i64),
+    List(Vec<f64>),
+    Text(String),
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Integer(value as i64)
+    }
+}
+
+impl From<&[f64]> for Value {
+    fn from(value: &[f64]) -> Self {
+        Value::List(value.to_vec())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+/// What one augmenter did to one series
+///
+/// `applied` is false if the augmenter drew not to augment the series because of its probability.
+/// `params` are the parameters the augmenter drew, in the order it drew them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step {
+    pub augmenter: String,
+    pub applied: bool,
+    pub params: Vec<(String, Value)>,
+}
+
+/// Provenance of one output series: the index of the input series it was created from and the
+/// steps of all augmenters in the order they were applied
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowProvenance {
+    pub source: usize,
+    pub steps: Vec<Step>,
+}
+
+/// Provenance of every series of an augmented batch, in the order of the series
+#[derive(Clone, Debug, PartialEq)]
+pub struct Provenance {
+    pub rows: Vec<RowProvenance>,
+}
+
+impl RowProvenance {
+    /// The steps of the augmenter named `augmenter` that augmented the series
+    pub fn applied<'a>(&'a self, augmenter: &'a str) -> impl Iterator<Item = &'a Step> + 'a {
+        self.steps
+            .iter()
+            .filter(move |step| step.applied && step.augmenter == augmenter)
+    }
+}
+
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::Number(v) => json_number(*v),
+        Value::Integer(v) => v.to_string(),
+        Value::List(v) => json_array(v),
+        Value::Text(v) => json_string(v),
+    }
+}
+
+impl Provenance {
+    /// Serializes the provenance as JSON, one object with the source and steps of every series
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let steps: Vec<String> = row
+                    .steps
+                    .iter()
+                    .map(|step| {
+                        let params: Vec<String> = step
+                            .params
+                            .iter()
+                            .map(|(name, value)| format!("{}: {}", json_string(name), json_value(value)))
+                            .collect();
+                        format!(
+                            "{{\"augmenter\": {}, \"applied\": {}, \"params\": {{{}}}}}",
+                            json_string(&step.augmenter),
+                            step.applied,
+                            params.join(", ")
+                        )
+                    })
+                    .collect();
+                format!(
+                    "    {{\"row\": {}, \"source\": {}, \"steps\": [{}]}}",
+                    i,
+                    row.source,
+                    steps.join(", ")
+                )
+            })
+            .collect();
+        format!("{{\n  \"rows\": [\n{}\n  ]\n}}\n", rows.join(",\n"))
+    }
+
+    /// Writes the JSON provenance to a file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// Steps of the series the thread augments at the moment, with the indices of the steps that have
+/// not finished yet, innermost last
+#[derive(Default)]
+struct SeriesLog {
+    steps: Vec<Step>,
+    open: Vec<usize>,
+}
+
+thread_local! {
+    /// The series the thread augments at the moment, `None` unless it is recorded
+    static SERIES: RefCell<Option<SeriesLog>> = const { RefCell::new(None) };
+    /// Provenance of the batches the thread augments, the innermost subset last, empty unless recording
+    static FRAMES: RefCell<Vec<Vec<RowProvenance>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reports a parameter the running augmenter drew for the current series
+///
+/// Does nothing unless the series is recorded, the value is only converted then.
+pub fn record<V: Into<Value>>(name: &str, value: V) {
+    SERIES.with(|series| {
+        if let Some(log) = series.borrow_mut().as_mut()
+            && let Some(&step) = log.open.last()
+        {
+            log.steps[step].params.push((name.to_string(), value.into()));
+        }
+    })
+}
+
+/// Whether `record` keeps the parameters of the current series
+///
+/// For augmenters that would otherwise collect many parameters, e.g. one for every value, only to
+/// report them.
+pub(crate) fn records() -> bool {
+    SERIES.with(|series| {
+        series
+            .borrow()
+            .as_ref()
+            .is_some_and(|log| !log.open.is_empty())
+    })
+}
+
+/// Reports that the augmenter changed the number of series of the batch to `n`, series `i` being
+/// created from series `source(i)`
+///
+/// Needed for augmenters that override `augment_batch` to add or remove series, like `Repeat`.
+/// Does nothing unless the batch is recorded.
+pub fn map_rows(n: usize, source: impl Fn(usize) -> usize) {
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            *frame = (0..n).map(|i| frame[source(i)].clone()).collect();
+        }
+    })
+}
+
+/// Runs `f` as the step of an augmenter on the current series, recording it if the series is recorded
+///
+/// `name` is only called when recording. Steps can be nested, e.g. for the children of `OneOf`,
+/// parameters are reported to the innermost one.
+pub(crate) fn step<R>(name: impl FnOnce() -> String, applied: bool, f: impl FnOnce() -> R) -> R {
+    let recording = SERIES.with(|series| match series.borrow_mut().as_mut() {
+        Some(log) => {
+            log.open.push(log.steps.len());
+            log.steps.push(Step {
+                augmenter: name(),
+                applied,
+                params: Vec::new(),
+            });
+            true
+        }
+        None => false,
+    });
+    let result = f();
+    if recording {
+        SERIES.with(|series| {
+            if let Some(log) = series.borrow_mut().as_mut() {
+                log.open.pop();
+            }
+        });
+    }
+    result
+}
+
+/// Whether the calling thread records the batch it augments
+fn recording() -> bool {
+    FRAMES.with(|frames| !frames.borrow().is_empty())
+}
+
+/// Runs `f`, recording the batch of `n` series it augments, and returns the provenance of the
+/// series of the batch afterwards
+pub(crate) fn collect<R>(n: usize, f: impl FnOnce() -> R) -> (R, Vec<RowProvenance>) {
+    let frame = (0..n)
+        .map(|source| RowProvenance {
+            source,
+            steps: Vec::new(),
+        })
+        .collect();
+    FRAMES.with(|frames| frames.borrow_mut().push(frame));
+    let result = f();
+    let frame = FRAMES.with(|frames| frames.borrow_mut().pop()).unwrap_or_default();
+    (result, frame)
+}
+
+/// Runs `f` on a subset of `n` series of the recorded batch, returning its provenance if the batch
+/// is recorded
+pub(crate) fn subset<R>(n: usize, f: impl FnOnce() -> R) -> (R, Option<Vec<RowProvenance>>) {
+    if recording() {
+        let (result, frame) = collect(n, f);
+        (result, Some(frame))
+    } else {
+        (f(), None)
+    }
+}
+
+/// Appends the steps of a subset that was put back to the positions `rows` of the batch
+pub(crate) fn put_rows(rows: &[usize], subset: Option<Vec<RowProvenance>>) {
+    let Some(subset) = subset else {
+        return;
+    };
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            for row in subset {
+                frame[rows[row.source]].steps.extend(row.steps);
+            }
+        }
+    })
+}
+
+/// Replaces the provenance of the batch by that of the subsets taken from the positions `groups`
+/// and concatenated in this order
+pub(crate) fn concat_rows(groups: &[Vec<usize>], subsets: Vec<Option<Vec<RowProvenance>>>) {
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            let mut rows = Vec::new();
+            for (group, subset) in groups.iter().zip(subsets) {
+                for row in subset.unwrap_or_default() {
+                    let mut parent = frame[group[row.source]].clone();
+                    parent.steps.extend(row.steps);
+                    rows.push(parent);
+                }
+            }
+            *frame = rows;
+        }
+    })
+}
+
+/// The steps of every series of a batch that has been augmented, with the index of the series
+type BatchSteps = Vec<(usize, Vec<Step>)>;
+
+/// Steps of the series of one batch, which may be augmented on other threads, collected while
+/// the batch is recorded and added to the provenance of the batch when dropped
+pub(crate) struct BatchLog(Option<Mutex<BatchSteps>>);
+
+impl BatchLog {
+    /// Starts a new batch on the calling thread
+    pub(crate) fn new() -> Self {
+        BatchLog(recording().then(|| Mutex::new(Vec::new())))
+    }
+
+    /// Runs `f` on series `row` of this batch, recording its steps
+    pub(crate) fn row<R>(&self, row: usize, f: impl FnOnce() -> R) -> R {
+        let Some(rows) = &self.0 else {
+            return f();
+        };
+        let previous = SERIES.with(|series| series.replace(Some(SeriesLog::default())));
+        let result = f();
+        let log = SERIES.with(|series| series.replace(previous)).unwrap_or_default();
+        rows.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((row, log.steps));
+        result
+    }
+}
+
+impl Drop for BatchLog {
+    fn drop(&mut self) {
+        let Some(rows) = self.0.take() else {
+            return;
+        };
+        let mut rows = rows.into_inner().unwrap_or_else(PoisonError::into_inner);
+        rows.sort_by_key(|(row, _)| *row);
+        FRAMES.with(|frames| {
+            if let Some(frame) = frames.borrow_mut().last_mut() {
+                for (row, steps) in rows {
+                    if let Some(provenance) = frame.get_mut(row) {
+                        provenance.steps.extend(steps);
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use super::dtw::{DtwConstraint, dtw_distance};
+use crate::Dataset;
+use rayon::prelude::*;
+
+/// Enum to specify the metric used to compare two time series
+///
+/// - `Euclidean`: Euclidean distance, needs series of the same length
+/// - `Dtw(constraint)`: DTW distance normalized by the length of the first series, like `dtw`
+/// - `Correlation`: `1 - r` with the Pearson correlation `r`, needs series of the same length.
+///   Ranges from 0 (perfectly correlated) to 2 (perfectly anti-correlated). A constant series is
+///   treated as uncorrelated to everything, giving a distance of 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DistanceMetric {
+    Euclidean,
+    Dtw(DtwConstraint),
+    Correlation,
+}
+
+impl DistanceMetric {
+    /// Computes the distance between two time series
+    pub fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            DistanceMetric::Euclidean => {
+                assert_eq!(a.len(), b.len(), "Euclidean distance needs series of the same length");
+                a.iter()
+                    .zip(b)
+                    .map(|(x, y)| (x - y).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            }
+            DistanceMetric::Dtw(constraint) => dtw_distance(a, b, *constraint, None),
+            DistanceMetric::Correlation => {
+                assert_eq!(a.len(), b.len(), "Correlation distance needs series of the same length");
+                let n = a.len() as f64;
+                let mean_a = a.iter().sum::<f64>() / n;
+                let mean_b = b.iter().sum::<f64>() / n;
+                let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+                for (x, y) in a.iter().zip(b) {
+                    cov += (x - mean_a) * (y - mean_b);
+                    var_a += (x - mean_a).powi(2);
+                    var_b += (y - mean_b).powi(2);
+                }
+                if var_a == 0.0 || var_b == 0.0 {
+                    return 1.0;
+                }
+                1.0 - cov / (var_a * var_b).sqrt()
+            }
+        }
+    }
+}
+
+/// Computes the distance between every series of `a` and every series of `b`
+///
+/// Entry `[i][j]` of the result is the distance between `a.features[i]` and `b.features[j]`.
+/// Typically used to compare the original dataset with its augmented version.
+/// # Examples
+/// ```
+/// use rats_rs::Dataset;
+/// use rats_rs::augmenters::{Augmenter, Jittering};
+/// use rats_rs::quality_benchmarking::{DistanceMetric, distance_matrix};
+///
+/// let original = Dataset {
+///     features: vec![vec![1.0, 2.0, 3.0, 4.0], vec![4.0, 3.0, 2.0, 1.0]],
+///     labels: vec!["up".to_string(), "down".to_string()],
+/// };
+/// let mut augmented = Dataset {
+///     features: original.features.clone(),
+///     labels: original.labels.clone(),
+/// };
+/// Jittering::new(0.1).augment_batch(&mut augmented, true, false);
+///
+/// let distances = distance_matrix(&original, &augmented, DistanceMetric::Euclidean, true);
+/// assert_eq!(distances.len(), 2);
+/// assert!(distances[0][0] < distances[0][1]);
+/// ```
+pub fn distance_matrix(
+    a: &Dataset,
+    b: &Dataset,
+    metric: DistanceMetric,
+    parallel: bool,
+) -> Vec<Vec<f64>> {
+    let row = |x: &Vec<f64>| -> Vec<f64> {
+        b.features.iter().map(|y| metric.distance(x, y)).collect()
+    };
+    if parallel {
+        a.features.par_iter().map(row).collect()
+    } else {
+        a.features.iter().map(row).collect()
+    }
+}
+
+/// Computes the distance between all pairs of series within one dataset
+///
+/// The result is a symmetric matrix with zeros on the diagonal. Only the upper triangle is computed,
+/// so this is about twice as fast as `distance_matrix(dataset, dataset, ...)`. For DTW the distance
+/// of a pair is computed with the series of smaller index first.
+pub fn pairwise_distance_matrix(
+    dataset: &Dataset,
+    metric: DistanceMetric,
+    parallel: bool,
+) -> Vec<Vec<f64>> {
+    let features = &dataset.features;
+    let n = features.len();
+    let upper_row = |i: usize| -> Vec<f64> {
+        features[i + 1..]
+            .iter()
+            .map(|y| metric.distance(&features[i], y))
+            .collect()
+    };
+    let upper: Vec<Vec<f64>> = if parallel {
+        (0..n).into_par_iter().map(upper_row).collect()
+    } else {
+        (0..n).map(upper_row).collect()
+    };
+
+    let mut matrix = vec![vec![0.0; n]; n];
+    for (i, row) in upper.iter().enumerate() {
+        for (k, &d) in row.iter().enumerate() {
+            matrix[i][i + 1 + k] = d;
+            matrix[i + 1 + k][i] = d;
+        }
+    }
+    matrix
+}
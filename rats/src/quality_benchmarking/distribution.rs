@@ -0,0 +1,310 @@
+use super::distance::{DistanceMetric, pairwise_distance_matrix};
+use crate::Dataset;
+use rayon::prelude::*;
+use rustfft::{FftPlanner, num_complex::Complex};
+
+/// Result of a two-sample Kolmogorov-Smirnov test
+///
+/// - `statistic`: maximum distance between the two empirical distribution functions
+/// - `p_value`: asymptotic probability of a statistic at least this large if both samples come
+///   from the same distribution
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KsResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// Two-sample Kolmogorov-Smirnov test
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::ks_test;
+/// let x: Vec<f64> = (0..100).map(|i| i as f64).collect();
+/// let y: Vec<f64> = (0..100).map(|i| i as f64 + 50.0).collect();
+/// let result = ks_test(&x, &y);
+/// assert_eq!(result.statistic, 0.5);
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn ks_test(x: &[f64], y: &[f64]) -> KsResult {
+    if x.is_empty() || y.is_empty() {
+        return KsResult {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let mut x = x.to_vec();
+    let mut y = y.to_vec();
+    x.sort_by(|a, b| a.total_cmp(b));
+    y.sort_by(|a, b| a.total_cmp(b));
+    let (n, m) = (x.len() as f64, y.len() as f64);
+
+    let (mut i, mut j) = (0, 0);
+    let mut statistic: f64 = 0.0;
+    while i < x.len() && j < y.len() {
+        // Step over all values equal to the smaller one in both samples, so ties are handled
+        let value = x[i].min(y[j]);
+        while i < x.len() && x[i] <= value {
+            i += 1;
+        }
+        while j < y.len() && y[j] <= value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+
+    let effective = (n * m / (n + m)).sqrt();
+    let lambda = (effective + 0.12 + 0.11 / effective) * statistic;
+    KsResult {
+        statistic,
+        p_value: kolmogorov_survival(lambda),
+    }
+}
+
+/// Survival function of the Kolmogorov distribution
+fn kolmogorov_survival(lambda: f64) -> f64 {
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    for k in 1..=100 {
+        let term = (-2.0 * (k * k) as f64 * lambda * lambda).exp();
+        sum += if k % 2 == 1 { term } else { -term };
+        if term < 1e-12 {
+            break;
+        }
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Common length of all series of both datasets
+fn common_length(a: &Dataset, b: &Dataset) -> usize {
+    let mut features = a.features.iter().chain(b.features.iter());
+    let length = features.clone().next().map_or(0, |x| x.len());
+    assert!(
+        features.all(|x| x.len() == length),
+        "All series of both datasets must have the same length"
+    );
+    length
+}
+
+/// Tests the values of all series at every index of `0..length`
+fn ks_per_index(a: &[Vec<f64>], b: &[Vec<f64>], length: usize, parallel: bool) -> Vec<KsResult> {
+    let test = |t: usize| {
+        let x: Vec<f64> = a.iter().map(|series| series[t]).collect();
+        let y: Vec<f64> = b.iter().map(|series| series[t]).collect();
+        ks_test(&x, &y)
+    };
+    if parallel {
+        (0..length).into_par_iter().map(test).collect()
+    } else {
+        (0..length).map(test).collect()
+    }
+}
+
+/// Kolmogorov-Smirnov test between the values of both datasets at every time step
+///
+/// Tests whether the marginal distribution of the augmented data at each time step still matches the
+/// original data. All series must have the same length.
+pub fn per_timestep_ks(a: &Dataset, b: &Dataset, parallel: bool) -> Vec<KsResult> {
+    let length = common_length(a, b);
+    ks_per_index(&a.features, &b.features, length, parallel)
+}
+
+/// Magnitude of the non-negative frequencies of the FFT of every series
+fn magnitude_spectra(features: &[Vec<f64>], parallel: bool) -> Vec<Vec<f64>> {
+    let len = features.first().map_or(0, |x| x.len());
+    let fft = FftPlanner::new().plan_fft_forward(len);
+    let spectrum = |x: &Vec<f64>| -> Vec<f64> {
+        let mut buffer: Vec<Complex<f64>> = x.iter().map(|&v| Complex { re: v, im: 0.0 }).collect();
+        fft.process(&mut buffer);
+        buffer.iter().take(len / 2 + 1).map(|c| c.norm()).collect()
+    };
+    if parallel {
+        features.par_iter().map(spectrum).collect()
+    } else {
+        features.iter().map(spectrum).collect()
+    }
+}
+
+/// Kolmogorov-Smirnov test between the FFT magnitudes of both datasets at every frequency bin
+///
+/// Returns one result for each of the `length / 2 + 1` non-negative frequencies. All series must have
+/// the same length.
+pub fn spectral_ks(a: &Dataset, b: &Dataset, parallel: bool) -> Vec<KsResult> {
+    let length = common_length(a, b);
+    if length == 0 {
+        return Vec::new();
+    }
+    let spectra_a = magnitude_spectra(&a.features, parallel);
+    let spectra_b = magnitude_spectra(&b.features, parallel);
+    ks_per_index(&spectra_a, &spectra_b, length / 2 + 1, parallel)
+}
+
+/// Median of the pairwise Euclidean distances of all series, a common choice for the RBF bandwidth
+fn median_distance(features: &[&Vec<f64>]) -> f64 {
+    let mut distances: Vec<f64> = (0..features.len())
+        .flat_map(|i| {
+            (i + 1..features.len())
+                .map(move |j| DistanceMetric::Euclidean.distance(features[i], features[j]))
+        })
+        .filter(|&d| d > 0.0)
+        .collect();
+    if distances.is_empty() {
+        return 1.0;
+    }
+    distances.sort_by(|a, b| a.total_cmp(b));
+    distances[distances.len() / 2]
+}
+
+/// Maximum mean discrepancy between two datasets with a Gaussian RBF kernel
+///
+/// Every series is treated as one point of its dataset, and the kernel is
+/// `exp(-|x - y|^2 / (2 * sigma^2))`. If no `sigma` is given, the median pairwise distance of all
+/// series of both datasets is used. Returns the square root of the biased estimate of MMD², which
+/// is 0 for identical datasets. All series must have the same length.
+/// # Examples
+/// ```
+/// use rats_rs::Dataset;
+/// use rats_rs::quality_benchmarking::mmd_rbf;
+///
+/// let a = Dataset {
+///     features: vec![vec![1.0, 2.0, 3.0], vec![2.0, 3.0, 4.0]],
+///     labels: vec!["a".to_string(), "a".to_string()],
+/// };
+/// assert_eq!(mmd_rbf(&a, &a, None, false), 0.0);
+/// ```
+pub fn mmd_rbf(a: &Dataset, b: &Dataset, sigma: Option<f64>, parallel: bool) -> f64 {
+    common_length(a, b);
+    if a.features.is_empty() || b.features.is_empty() {
+        return 0.0;
+    }
+    let sigma = sigma.unwrap_or_else(|| {
+        median_distance(
+            &a.features
+                .iter()
+                .chain(b.features.iter())
+                .collect::<Vec<_>>(),
+        )
+    });
+    assert!(sigma > 0.0, "sigma must be greater than 0");
+
+    let kernel = |x: &Vec<f64>, y: &Vec<f64>| {
+        let squared: f64 = x.iter().zip(y).map(|(u, v)| (u - v).powi(2)).sum();
+        (-squared / (2.0 * sigma * sigma)).exp()
+    };
+    let mean_kernel = |xs: &[Vec<f64>], ys: &[Vec<f64>]| -> f64 {
+        let row = |x: &Vec<f64>| ys.iter().map(|y| kernel(x, y)).sum::<f64>();
+        let sum: f64 = if parallel {
+            xs.par_iter().map(row).sum()
+        } else {
+            xs.iter().map(row).sum()
+        };
+        sum / (xs.len() * ys.len()) as f64
+    };
+
+    let squared = mean_kernel(&a.features, &a.features) + mean_kernel(&b.features, &b.features)
+        - 2.0 * mean_kernel(&a.features, &b.features);
+    squared.max(0.0).sqrt()
+}
+
+/// Autocorrelation of a series at lags `1..=max_lag`, zero for a constant series
+fn autocorrelation(x: &[f64], max_lag: usize) -> Vec<f64> {
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    let variance: f64 = x.iter().map(|v| (v - mean).powi(2)).sum();
+    (1..=max_lag)
+        .map(|lag| {
+            if variance == 0.0 || lag >= x.len() {
+                return 0.0;
+            }
+            x.iter()
+                .zip(&x[lag..])
+                .map(|(u, v)| (u - mean) * (v - mean))
+                .sum::<f64>()
+                / variance
+        })
+        .collect()
+}
+
+/// Averages the vectors produced by `f` for every series of the dataset
+fn mean_over<F>(features: &[Vec<f64>], f: F, parallel: bool) -> Vec<f64>
+where
+    F: Fn(&Vec<f64>) -> Vec<f64> + Sync,
+{
+    let sum = |mut acc: Vec<f64>, v: Vec<f64>| {
+        if acc.is_empty() {
+            return v;
+        }
+        acc.iter_mut().zip(v).for_each(|(a, b)| *a += b);
+        acc
+    };
+    let total = if parallel {
+        features.par_iter().map(&f).reduce(Vec::new, sum)
+    } else {
+        features.iter().map(&f).fold(Vec::new(), sum)
+    };
+    total
+        .into_iter()
+        .map(|v| v / features.len() as f64)
+        .collect()
+}
+
+/// Distance between the mean autocorrelation functions of two datasets
+///
+/// Computes the autocorrelation at lags `1..=max_lag` of every series, averages it over each dataset
+/// and returns the Euclidean distance between the two averages. Measures whether augmentation
+/// preserves the temporal structure of the data. The series may have different lengths. Returns 0
+/// if one of the datasets is empty.
+pub fn acf_distance(a: &Dataset, b: &Dataset, max_lag: usize, parallel: bool) -> f64 {
+    if a.features.is_empty() || b.features.is_empty() {
+        return 0.0;
+    }
+    let acf = |x: &Vec<f64>| autocorrelation(x, max_lag);
+    let mean_a = mean_over(&a.features, acf, parallel);
+    let mean_b = mean_over(&b.features, acf, parallel);
+    DistanceMetric::Euclidean.distance(&mean_a, &mean_b)
+}
+
+/// Distance between the mean power spectral densities of two datasets
+///
+/// Averages the periodograms of the series of each dataset, normalizes both averages to sum to 1 and
+/// returns their total variation distance, i.e. half of the absolute differences summed over all
+/// frequencies. Ranges from 0 (same distribution of power over the frequencies) to 1. All series must
+/// have the same length.
+pub fn psd_distance(a: &Dataset, b: &Dataset, parallel: bool) -> f64 {
+    if common_length(a, b) == 0 {
+        return 0.0;
+    }
+    let psd = |features: &[Vec<f64>]| {
+        let spectra = magnitude_spectra(features, parallel);
+        let mean = mean_over(&spectra, |s| s.iter().map(|m| m * m).collect(), parallel);
+        let total: f64 = mean.iter().sum();
+        if total == 0.0 {
+            return mean;
+        }
+        mean.into_iter().map(|p| p / total).collect::<Vec<f64>>()
+    };
+    let psd_a = psd(&a.features);
+    let psd_b = psd(&b.features);
+    psd_a
+        .iter()
+        .zip(&psd_b)
+        .map(|(x, y)| (x - y).abs())
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Diversity of a dataset, the mean distance between all pairs of its series
+///
+/// Augmentation that collapses the data onto few distinct series results in a low diversity.
+pub fn diversity(dataset: &Dataset, metric: DistanceMetric, parallel: bool) -> f64 {
+    let n = dataset.features.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let matrix = pairwise_distance_matrix(dataset, metric, parallel);
+    let sum: f64 = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| row[i + 1..].iter().sum::<f64>())
+        .sum();
+    sum / (n * (n - 1) / 2) as f64
+}
@@ -1,9 +1,63 @@
+use crate::Float;
+
+/// Enum to specify a global constraint on the warping path of DTW
+///
+/// Constraints prevent pathological warpings and speed up the computation, because only the
+/// cells inside the constraint region have to be evaluated.
+/// - `None`: unconstrained DTW
+/// - `SakoeChiba(radius)`: the path must stay within `radius` cells of the diagonal. For series of
+///   different lengths, the band is widened by the length difference so that a path always exists.
+/// - `Itakura(max_slope)`: the path must lie in a parallelogram whose sides have slopes
+///   `max_slope` and `1 / max_slope` (`max_slope > 1`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DtwConstraint {
+    None,
+    SakoeChiba(usize),
+    Itakura(f64),
+}
+
+impl DtwConstraint {
+    /// Inclusive range of columns `j` of `b` that row `i` of `a` may be matched to
+    fn column_range(&self, i: usize, n: usize, m: usize) -> (usize, usize) {
+        match *self {
+            DtwConstraint::None => (0, m - 1),
+            DtwConstraint::SakoeChiba(radius) => {
+                if n <= m {
+                    (i.saturating_sub(radius), (i + m - n + radius).min(m - 1))
+                } else {
+                    (i.saturating_sub(n - m + radius), (i + radius).min(m - 1))
+                }
+            }
+            DtwConstraint::Itakura(max_slope) => {
+                assert!(max_slope > 1.0, "Maximum slope must be greater than 1");
+                if n == 1 || m == 1 {
+                    return (0, m - 1);
+                }
+                // Normalized coordinates of the cell, both sequences run from 0 to 1
+                let x = i as f64 / (n - 1) as f64;
+                let lo = (x / max_slope).max(1.0 - max_slope * (1.0 - x));
+                let hi = (x * max_slope).min(1.0 - (1.0 - x) / max_slope);
+
+                // Always allow one step around the diagonal, so a path exists near the corners
+                let step = 1.0 / (n.min(m) - 1) as f64;
+                let lo = lo.min(x - step);
+                let hi = hi.max(x + step);
+
+                let scale = (m - 1) as f64;
+                let lo = (lo * scale - 1e-9).ceil().max(0.0) as usize;
+                let hi = ((hi * scale + 1e-9).floor() as usize).min(m - 1);
+                (lo, hi)
+            }
+        }
+    }
+}
+
 /// Implementation of Dynamic Time Warping (DTW) algorithm.
 /// This function computes the DTW distance between two sequences and returns the distance
 /// along with the optimal path.
 /// # Arguments
-/// * `a` - First sequence as a slice of f64 values.
-/// * `b` - Second sequence as a slice of f64 values.
+/// * `a` - First sequence as a slice of f32 or f64 values.
+/// * `b` - Second sequence as a slice of the same type.
 /// # Returns
 /// A tuple containing the DTW distance (f64) and a vector of tuples representing the
 /// optimal path as pairs of indices (usize, usize).
@@ -15,14 +69,43 @@
 /// let (distance, path) = dtw(&a, &b);
 /// ```
 
-pub fn dtw(a: &[f64], b: &[f64]) -> (f64, Vec<(usize, usize)>) {
-    let n = a.len();
-    let m = b.len();
+pub fn dtw<T: Float>(a: &[T], b: &[T]) -> (f64, Vec<(usize, usize)>) {
+    dtw_constrained(a, b, DtwConstraint::None)
+}
+
+/// DTW with a global constraint on the warping path
+///
+/// Same as `dtw`, but only cells allowed by `constraint` are considered. The distance is normalized
+/// by the length of `a` like in `dtw`.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw_constrained, DtwConstraint};
+/// let a = vec![1.0, 2.0, 3.0, 4.0];
+/// let b = vec![1.0, 1.0, 2.0, 3.0];
+/// let (distance, path) = dtw_constrained(&a, &b, DtwConstraint::SakoeChiba(1));
+/// ```
+pub fn dtw_constrained<T: Float>(
+    a: &[T],
+    b: &[T],
+    constraint: DtwConstraint,
+) -> (f64, Vec<(usize, usize)>) {
+    dtw_path(a.len(), b.len(), constraint, |i, j| (a[i] - b[j]).abs().as_f64())
+}
+
+/// Full DTW recursion on two sequences of lengths `n` and `m`, where `cost(i, j)` is the local cost
+/// of matching point `i` of the first to point `j` of the second sequence
+pub(super) fn dtw_path(
+    n: usize,
+    m: usize,
+    constraint: DtwConstraint,
+    local_cost: impl Fn(usize, usize) -> f64,
+) -> (f64, Vec<(usize, usize)>) {
     let mut cost = vec![vec![f64::INFINITY; m + 1]; n + 1];
     cost[0][0] = 0.0;
     for i in 1..=n {
-        for j in 1..=m {
-            let diff = (a[i - 1] - b[j - 1]).abs();
+        let (lo, hi) = constraint.column_range(i - 1, n, m);
+        for j in lo + 1..=hi + 1 {
+            let diff = local_cost(i - 1, j - 1);
             let min_prev = cost[i - 1][j].min(cost[i][j - 1]).min(cost[i - 1][j - 1]);
             cost[i][j] = diff + min_prev;
         }
@@ -54,3 +137,85 @@ pub fn dtw(a: &[f64], b: &[f64]) -> (f64, Vec<(usize, usize)>) {
     path.reverse();
     (distance / n as f64, path)
 }
+
+/// Computes only the DTW distance, without the warping path
+///
+/// Needs only two rows of the cost matrix, i.e. `O(min(n, m))` memory. The distance is normalized
+/// by the length of `a` like in `dtw`.
+///
+/// If an `upper_bound` is given, the computation is abandoned as soon as the distance is known to
+/// exceed it, and `f64::INFINITY` is returned. This speeds up nearest neighbor searches, where only
+/// distances smaller than the best one found so far are of interest.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw, dtw_distance, DtwConstraint};
+/// let a = vec![1.0, 2.0, 3.0, 4.0];
+/// let b = vec![1.0, 1.0, 2.0, 3.0];
+///
+/// let distance = dtw_distance(&a, &b, DtwConstraint::None, None);
+/// assert_eq!(distance, dtw(&a, &b).0);
+///
+/// let abandoned = dtw_distance(&a, &b, DtwConstraint::None, Some(0.1));
+/// assert_eq!(abandoned, f64::INFINITY);
+/// ```
+pub fn dtw_distance<T: Float>(
+    a: &[T],
+    b: &[T],
+    constraint: DtwConstraint,
+    upper_bound: Option<f64>,
+) -> f64 {
+    dtw_bounded_distance(a.len(), b.len(), constraint, upper_bound, |i, j| {
+        (a[i] - b[j]).abs().as_f64()
+    })
+}
+
+/// Linear memory DTW recursion with early abandoning, see `dtw_path` for the arguments
+pub(super) fn dtw_bounded_distance(
+    n: usize,
+    m: usize,
+    constraint: DtwConstraint,
+    upper_bound: Option<f64>,
+    local_cost: impl Fn(usize, usize) -> f64,
+) -> f64 {
+    if n == 0 || m == 0 {
+        return f64::INFINITY;
+    }
+    let normalization = n as f64;
+    let bound = upper_bound.map_or(f64::INFINITY, |bound| bound * normalization);
+    // All constraints are symmetric, so the shorter sequence can be used for the columns
+    let transposed = m > n;
+    let (rows, cols) = if transposed { (m, n) } else { (n, m) };
+    let local_cost = |i: usize, j: usize| {
+        if transposed {
+            local_cost(j, i)
+        } else {
+            local_cost(i, j)
+        }
+    };
+
+    let mut previous = vec![f64::INFINITY; cols + 1];
+    let mut current = vec![f64::INFINITY; cols + 1];
+    previous[0] = 0.0;
+    for i in 1..=rows {
+        let (lo, hi) = constraint.column_range(i - 1, rows, cols);
+        current.fill(f64::INFINITY);
+        let mut row_min = f64::INFINITY;
+        for j in lo + 1..=hi + 1 {
+            let diff = local_cost(i - 1, j - 1);
+            let min_prev = previous[j].min(current[j - 1]).min(previous[j - 1]);
+            current[j] = diff + min_prev;
+            row_min = row_min.min(current[j]);
+        }
+        if row_min > bound {
+            return f64::INFINITY;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[cols];
+    if distance > bound {
+        f64::INFINITY
+    } else {
+        distance / normalization
+    }
+}
@@ -0,0 +1,230 @@
+//! k-nearest-neighbor classification for train-on-augmented, test-on-real evaluation
+//!
+//! The usual way to judge an augmentation on UCR-style data is to train a 1-NN classifier on the
+//! augmented training set and report its accuracy on the real test set. `KnnClassifier` classifies
+//! time series by the labels of their nearest neighbors, and `evaluate_augmentation` runs the whole
+//! experiment with and without augmentation, repeated with the given seeds.
+//! # Examples
+//! ```
+//! use rats_rs::Dataset;
+//! use rats_rs::augmenters::{AugmentationPipeline, Jittering};
+//! use rats_rs::quality_benchmarking::DistanceMetric;
+//! use rats_rs::quality_benchmarking::knn::{KnnClassifier, evaluate_augmentation};
+//!
+//! let make = |phase: f64| -> Vec<f64> { (0..50).map(|i| (i as f64 * 0.2 + phase).sin()).collect() };
+//! let train = Dataset {
+//!     features: vec![make(0.0), make(3.0)],
+//!     labels: vec!["a".to_string(), "b".to_string()],
+//! };
+//! let test = Dataset {
+//!     features: vec![make(0.1), make(2.9)],
+//!     labels: vec!["a".to_string(), "b".to_string()],
+//! };
+//!
+//! let classifier = KnnClassifier::new(1, DistanceMetric::Euclidean);
+//! assert_eq!(classifier.accuracy(&train, &test, true), 1.0);
+//!
+//! let pipeline = AugmentationPipeline::new() + Jittering::new(0.05);
+//! let evaluation = evaluate_augmentation(&train, &test, &pipeline, &classifier, &[1, 2, 3], true, true);
+//! assert_eq!(evaluation.augmented_accuracies.len(), 3);
+//! ```
+
+use super::distance::DistanceMetric;
+use super::dtw::{DtwConstraint, dtw_distance};
+use super::lower_bounds::lb_keogh;
+use crate::Dataset;
+use crate::augmenters::Augmenter;
+use crate::random;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Classifies time series by a majority vote of the labels of their `k` nearest training series
+///
+/// Ties in the vote are broken in favor of the label whose closest neighbor is nearest. With DTW,
+/// candidates are pruned with LB_Keogh (for a Sakoe-Chiba band and series of equal length) and the
+/// DTW computation is abandoned early once it exceeds the distance of the current k-th neighbor,
+/// which gives the same result as the exhaustive search.
+pub struct KnnClassifier {
+    pub k: usize,
+    pub metric: DistanceMetric,
+}
+
+impl KnnClassifier {
+    pub fn new(k: usize, metric: DistanceMetric) -> Self {
+        assert!(k > 0, "k must be greater than 0");
+        KnnClassifier { k, metric }
+    }
+
+    /// Distance from `x` to `y`, or infinity if it is known to be larger than `bound`
+    fn bounded_distance(&self, x: &[f64], y: &[f64], bound: f64) -> f64 {
+        match self.metric {
+            DistanceMetric::Dtw(constraint) => {
+                if let DtwConstraint::SakoeChiba(radius) = constraint
+                    && x.len() == y.len()
+                    && lb_keogh(x, y, radius) > bound
+                {
+                    return f64::INFINITY;
+                }
+                let upper_bound = if bound.is_finite() { Some(bound) } else { None };
+                dtw_distance(x, y, constraint, upper_bound)
+            }
+            metric => metric.distance(x, y),
+        }
+    }
+
+    /// Indices of the `k` nearest training series and their distances, nearest first
+    pub fn neighbors(&self, train: &Dataset, x: &[f64]) -> Vec<(usize, f64)> {
+        let mut nearest: Vec<(usize, f64)> = Vec::with_capacity(self.k + 1);
+        for (i, y) in train.features.iter().enumerate() {
+            let bound = if nearest.len() == self.k {
+                nearest[self.k - 1].1
+            } else {
+                f64::INFINITY
+            };
+            let distance = self.bounded_distance(x, y, bound);
+            if distance < bound {
+                let position = nearest.partition_point(|&(_, d)| d <= distance);
+                nearest.insert(position, (i, distance));
+                nearest.truncate(self.k);
+            }
+        }
+        nearest
+    }
+
+    /// Predicts the label of one time series
+    ///
+    /// Returns `None` if no training series is at a finite distance, e.g. for an empty series, which
+    /// DTW cannot align, or a series with NaN values.
+    pub fn predict(&self, train: &Dataset, x: &[f64]) -> Option<String> {
+        assert!(!train.features.is_empty(), "Training set must not be empty");
+        let neighbors = self.neighbors(train, x);
+
+        // Votes of each label and the rank of its nearest neighbor
+        let mut votes: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (rank, &(i, _)) in neighbors.iter().enumerate() {
+            let entry = votes.entry(train.labels[i].as_str()).or_insert((0, rank));
+            entry.0 += 1;
+        }
+        votes
+            .into_iter()
+            .max_by(|a, b| a.1.0.cmp(&b.1.0).then(b.1.1.cmp(&a.1.1)))
+            .map(|(label, _)| label.to_string())
+    }
+
+    /// Predicts the labels of all time series of the test set, see `predict`
+    pub fn predict_batch(
+        &self,
+        train: &Dataset,
+        test: &Dataset,
+        parallel: bool,
+    ) -> Vec<Option<String>> {
+        if parallel {
+            test.features
+                .par_iter()
+                .map(|x| self.predict(train, x))
+                .collect()
+        } else {
+            test.features
+                .iter()
+                .map(|x| self.predict(train, x))
+                .collect()
+        }
+    }
+
+    /// Fraction of the test set that is classified correctly
+    ///
+    /// Test series without a prediction, see `predict`, count as classified incorrectly.
+    pub fn accuracy(&self, train: &Dataset, test: &Dataset, parallel: bool) -> f64 {
+        if test.features.is_empty() {
+            return 0.0;
+        }
+        let predictions = self.predict_batch(train, test, parallel);
+        let correct = predictions
+            .iter()
+            .zip(test.labels.iter())
+            .filter(|(predicted, label)| predicted.as_ref() == Some(*label))
+            .count();
+        correct as f64 / test.features.len() as f64
+    }
+}
+
+/// Result of `evaluate_augmentation`
+///
+/// - `baseline_accuracy`: accuracy of the classifier trained on the original training set
+/// - `augmented_accuracies`: accuracy of the classifier trained on the augmented training set, once
+///   for every seed
+pub struct AugmentationEvaluation {
+    pub baseline_accuracy: f64,
+    pub augmented_accuracies: Vec<f64>,
+}
+
+impl AugmentationEvaluation {
+    /// Mean accuracy over all repetitions with augmentation
+    pub fn mean_augmented_accuracy(&self) -> f64 {
+        if self.augmented_accuracies.is_empty() {
+            return 0.0;
+        }
+        self.augmented_accuracies.iter().sum::<f64>() / self.augmented_accuracies.len() as f64
+    }
+
+    /// Standard deviation of the accuracy over all repetitions with augmentation
+    pub fn std_augmented_accuracy(&self) -> f64 {
+        if self.augmented_accuracies.is_empty() {
+            return 0.0;
+        }
+        let mean = self.mean_augmented_accuracy();
+        let variance = self
+            .augmented_accuracies
+            .iter()
+            .map(|a| (a - mean).powi(2))
+            .sum::<f64>()
+            / self.augmented_accuracies.len() as f64;
+        variance.sqrt()
+    }
+
+    /// Difference between the mean accuracy with augmentation and the baseline accuracy
+    pub fn improvement(&self) -> f64 {
+        self.mean_augmented_accuracy() - self.baseline_accuracy
+    }
+}
+
+/// Trains the classifier on the original and on the augmented training set and tests both on the
+/// real test set
+///
+/// The augmentation is repeated on a fresh copy of the training set for every seed of `seeds`,
+/// seeded by `random::with_seed`, so every accuracy can be reproduced with its seed. The seed and
+/// generator of the calling thread are left as they were. If `include_original` is set, the
+/// classifier is trained on the original series together with the augmented ones, otherwise on the
+/// augmented series only.
+pub fn evaluate_augmentation<A: Augmenter + Sync>(
+    train: &Dataset,
+    test: &Dataset,
+    augmenter: &A,
+    classifier: &KnnClassifier,
+    seeds: &[u64],
+    include_original: bool,
+    parallel: bool,
+) -> AugmentationEvaluation {
+    let baseline_accuracy = classifier.accuracy(train, test, parallel);
+
+    let augmented_accuracies = seeds
+        .iter()
+        .map(|&seed| {
+            let mut augmented = Dataset {
+                features: train.features.clone(),
+                labels: train.labels.clone(),
+            };
+            random::with_seed(seed, || augmenter.augment_batch(&mut augmented, parallel, false));
+            if include_original {
+                augmented.features.extend(train.features.iter().cloned());
+                augmented.labels.extend(train.labels.iter().cloned());
+            }
+            classifier.accuracy(&augmented, test, parallel)
+        })
+        .collect();
+
+    AugmentationEvaluation {
+        baseline_accuracy,
+        augmented_accuracies,
+    }
+}
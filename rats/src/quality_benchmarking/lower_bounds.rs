@@ -0,0 +1,64 @@
+use crate::Float;
+
+/// LB_Kim lower bound of the DTW distance
+///
+/// Every warping path matches the first and the last points of both sequences, so their distances
+/// bound the DTW distance from below. Costs `O(1)`. Normalized by the length of `a` like `dtw`.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw, lb_kim};
+/// let a = vec![1.0, 2.0, 3.0, 4.0];
+/// let b = vec![2.0, 1.0, 2.0, 6.0];
+/// assert!(lb_kim(&a, &b) <= dtw(&a, &b).0);
+/// ```
+pub fn lb_kim<T: Float>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let first = (a[0] - b[0]).abs().as_f64();
+    if a.len() == 1 && b.len() == 1 {
+        return first;
+    }
+    let last = (a[a.len() - 1] - b[b.len() - 1]).abs().as_f64();
+    (first + last) / a.len() as f64
+}
+
+/// LB_Keogh lower bound of the DTW distance constrained by a Sakoe-Chiba band of `radius`
+///
+/// Computes the upper and lower envelope of `b` within the band and sums up how far each point of
+/// `a` lies outside of it. Costs `O(n * radius)`. Also a lower bound for wider bands, but less tight.
+/// Normalized by the length of `a` like `dtw`.
+///
+/// Both sequences must have the same length.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw_constrained, lb_keogh, DtwConstraint};
+/// let a = vec![1.0, 2.0, 3.0, 4.0];
+/// let b = vec![2.0, 1.0, 2.0, 6.0];
+/// assert!(lb_keogh(&a, &b, 1) <= dtw_constrained(&a, &b, DtwConstraint::SakoeChiba(1)).0);
+/// ```
+pub fn lb_keogh<T: Float>(a: &[T], b: &[T], radius: usize) -> f64 {
+    assert_eq!(a.len(), b.len(), "LB_Keogh needs sequences of the same length");
+    if a.is_empty() {
+        return 0.0;
+    }
+    let n = b.len();
+    let sum: f64 = a
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let window = &b[i.saturating_sub(radius)..(i + radius + 1).min(n)];
+            let upper = window.iter().fold(T::neg_infinity(), |acc, &x| acc.max(x));
+            let lower = window.iter().fold(T::infinity(), |acc, &x| acc.min(x));
+            let v = if v > upper {
+                v - upper
+            } else if v < lower {
+                lower - v
+            } else {
+                T::zero()
+            };
+            v.as_f64()
+        })
+        .sum();
+    sum / n as f64
+}
@@ -1,6 +1,16 @@
 //! Module to perform quality benchmarking of augmenters
 //! This module provides functionality to evaluate and compare the quality of different data augmentation techniques.
 //! Currently, it includes using the Dynamic Time Warping (DTW) algorithm to measure the similarity between original and augmented time series data.
+//! DTW can be constrained with a Sakoe-Chiba band or an Itakura parallelogram, computed in linear memory with early
+//! abandoning (`dtw_distance`), and bounded from below cheaply with `lb_kim` and `lb_keogh`.
+//! Multichannel series are compared with the dependent (`dtw_dependent`) and independent (`dtw_independent`) variants
+//! of DTW, with a pluggable `LocalCost`.
+//! The differentiable soft-DTW and its gradient are available with `soft_dtw` and `soft_dtw_gradient`.
+//! Distance matrices between whole datasets are computed in parallel with `distance_matrix` and `pairwise_distance_matrix`.
+//! Whether augmented data still looks like the original data as a whole is measured with distribution-level metrics:
+//! `mmd_rbf`, `per_timestep_ks`, `spectral_ks`, `acf_distance`, `psd_distance` and `diversity`.
+//! The `knn` module evaluates augmenters by training a nearest neighbor classifier on augmented data.
+//! The `report` module summarizes all of this per augmenter in JSON and HTML reports.
 //! # Examples
 //! ```
 //! use rats_rs::quality_benchmarking::dtw;
@@ -18,5 +28,17 @@
 
 #![allow(dead_code)]
 #![allow(unused_imports)]
+mod distance;
+mod distribution;
 mod dtw;
-pub use dtw::*;
\ No newline at end of file
+pub mod knn;
+mod lower_bounds;
+mod multivariate;
+pub mod report;
+mod soft_dtw;
+pub use distance::*;
+pub use distribution::*;
+pub use dtw::*;
+pub use lower_bounds::*;
+pub use multivariate::*;
+pub use soft_dtw::*;
\ No newline at end of file
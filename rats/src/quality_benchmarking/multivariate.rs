@@ -0,0 +1,132 @@
+use super::dtw::{DtwConstraint, dtw_bounded_distance, dtw_path};
+use std::sync::Arc;
+
+/// Function computing the local cost of two points
+pub type CostFn = Arc<dyn Fn(&[f64], &[f64]) -> f64 + Send + Sync>;
+
+/// Enum to specify the local cost of matching two points in DTW
+///
+/// A point holds one value per channel, so univariate series have points of length 1.
+/// - `Absolute`: sum of absolute differences over the channels (the cost used by `dtw`)
+/// - `Squared`: sum of squared differences over the channels
+/// - `Euclidean`: Euclidean distance between the points
+/// - `Custom(f)`: user-supplied function of the two points
+#[derive(Clone)]
+pub enum LocalCost {
+    Absolute,
+    Squared,
+    Euclidean,
+    Custom(CostFn),
+}
+
+impl LocalCost {
+    /// Computes the cost of matching point `x` to point `y`
+    pub fn cost(&self, x: &[f64], y: &[f64]) -> f64 {
+        match self {
+            LocalCost::Absolute => x.iter().zip(y).map(|(a, b)| (a - b).abs()).sum(),
+            LocalCost::Squared => x.iter().zip(y).map(|(a, b)| (a - b).powi(2)).sum(),
+            LocalCost::Euclidean => LocalCost::Squared.cost(x, y).sqrt(),
+            LocalCost::Custom(f) => f(x, y),
+        }
+    }
+}
+
+/// DTW of two univariate sequences with a pluggable local cost
+///
+/// Same as `dtw_constrained`, which uses `LocalCost::Absolute`.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw_with_cost, DtwConstraint, LocalCost};
+/// use std::sync::Arc;
+/// let a = vec![1.0, 2.0, 3.0];
+/// let b = vec![2.0, 3.0, 4.0];
+/// let (squared, path) = dtw_with_cost(&a, &b, &LocalCost::Squared, DtwConstraint::None);
+///
+/// let hinge = LocalCost::Custom(Arc::new(|x, y| ((x[0] - y[0]).abs() - 0.5).max(0.0)));
+/// let (distance, path) = dtw_with_cost(&a, &b, &hinge, DtwConstraint::None);
+/// ```
+pub fn dtw_with_cost(
+    a: &[f64],
+    b: &[f64],
+    cost: &LocalCost,
+    constraint: DtwConstraint,
+) -> (f64, Vec<(usize, usize)>) {
+    dtw_path(a.len(), b.len(), constraint, |i, j| {
+        cost.cost(std::slice::from_ref(&a[i]), std::slice::from_ref(&b[j]))
+    })
+}
+
+/// Checks that all channels of a multivariate series have the same length and returns it
+fn series_length(series: &[Vec<f64>]) -> usize {
+    let length = series.first().map_or(0, |channel| channel.len());
+    assert!(
+        series.iter().all(|channel| channel.len() == length),
+        "All channels of a series must have the same length"
+    );
+    length
+}
+
+/// Dependent multivariate DTW (DTW_D)
+///
+/// All channels share one warping path, and the local cost is computed between the points made up
+/// of the values of all channels at one time step. The series are given channel first, i.e. `a[c][t]`
+/// is the value of channel `c` at time `t`, and both must have the same number of channels.
+/// Normalized by the length of `a` like `dtw`.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw_dependent, DtwConstraint, LocalCost};
+/// let a = vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 0.0]];
+/// let b = vec![vec![1.0, 1.0, 2.0, 3.0], vec![0.0, 0.0, 1.0, 0.0]];
+/// let (distance, path) = dtw_dependent(&a, &b, &LocalCost::Euclidean, DtwConstraint::None);
+/// assert_eq!(distance, 0.0);
+/// ```
+pub fn dtw_dependent(
+    a: &[Vec<f64>],
+    b: &[Vec<f64>],
+    cost: &LocalCost,
+    constraint: DtwConstraint,
+) -> (f64, Vec<(usize, usize)>) {
+    assert_eq!(a.len(), b.len(), "Series must have the same number of channels");
+    let (n, m) = (series_length(a), series_length(b));
+
+    // Time major copies, so the points are contiguous
+    let transpose = |series: &[Vec<f64>], len: usize| -> Vec<Vec<f64>> {
+        (0..len)
+            .map(|t| series.iter().map(|channel| channel[t]).collect())
+            .collect()
+    };
+    let points_a = transpose(a, n);
+    let points_b = transpose(b, m);
+    dtw_path(n, m, constraint, |i, j| cost.cost(&points_a[i], &points_b[j]))
+}
+
+/// Independent multivariate DTW (DTW_I)
+///
+/// Every channel is warped on its own, and the result is the sum of the DTW distances of the
+/// channels. The series are given channel first like for `dtw_dependent`. Each channel is computed
+/// in linear memory.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::{dtw_independent, DtwConstraint, LocalCost};
+/// let a = vec![vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 0.0]];
+/// let b = vec![vec![1.0, 1.0, 2.0, 3.0], vec![0.0, 1.0, 1.0, 0.0]];
+/// let distance = dtw_independent(&a, &b, &LocalCost::Absolute, DtwConstraint::None);
+/// assert_eq!(distance, 0.0);
+/// ```
+pub fn dtw_independent(
+    a: &[Vec<f64>],
+    b: &[Vec<f64>],
+    cost: &LocalCost,
+    constraint: DtwConstraint,
+) -> f64 {
+    assert_eq!(a.len(), b.len(), "Series must have the same number of channels");
+    let (n, m) = (series_length(a), series_length(b));
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| {
+            dtw_bounded_distance(n, m, constraint, None, |i, j| {
+                cost.cost(std::slice::from_ref(&x[i]), std::slice::from_ref(&y[j]))
+            })
+        })
+        .sum()
+}
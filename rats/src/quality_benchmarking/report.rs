@@ -0,0 +1,491 @@
+//! Augmentation quality reports
+//!
+//! `generate_report` applies every augmenter of a pipeline on its own, and then the whole pipeline,
+//! to copies of an original dataset and summarizes how the data changed. The report can be written
+//! as machine-readable JSON and as a self-contained HTML page with inline SVG plots, so it can be
+//! reviewed from CI artifacts without Python.
+//! # Examples
+//! ```
+//! use rats_rs::Dataset;
+//! use rats_rs::augmenters::{AugmentationPipeline, Jittering, Scaling};
+//! use rats_rs::quality_benchmarking::report::{ReportConfig, generate_report};
+//!
+//! let original = Dataset {
+//!     features: (0..10).map(|i| (0..50).map(|t| (t as f64 * 0.1 * (i + 1) as f64).sin()).collect()).collect(),
+//!     labels: (0..10).map(|i| (i % 2).to_string()).collect(),
+//! };
+//! let pipeline = AugmentationPipeline::new() + Jittering::new(0.1) + Scaling::new(0.8, 1.2);
+//!
+//! let report = generate_report(&original, &pipeline, &ReportConfig::default());
+//! assert_eq!(report.augmenters.len(), 3);
+//!
+//! let json = report.to_json();
+//! let html = report.to_html();
+//! # assert!(json.contains("\"Jittering\""));
+//! # assert!(html.contains("<svg"));
+//! ```
+
+use super::distance::DistanceMetric;
+use super::distribution::psd_distance;
+use super::dtw::{DtwConstraint, dtw_distance};
+use super::knn::KnnClassifier;
+use crate::Dataset;
+use crate::augmenters::{AugmentationPipeline, Augmenter};
+use rayon::prelude::*;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Settings of `generate_report`
+///
+/// - `dtw_constraint`: constraint of the DTW distances between original and augmented series
+/// - `knn_metric`: metric of the 1-NN classifier used for label preservation. DTW with
+///   `dtw_constraint` is used instead when augmentation changed the length of the series.
+/// - `histogram_bins`: number of bins of the DTW distance histograms in the HTML report
+/// - `parallel`: whether augmentation and metrics are computed in parallel
+#[derive(Clone, Copy, Debug)]
+pub struct ReportConfig {
+    pub dtw_constraint: DtwConstraint,
+    pub knn_metric: DistanceMetric,
+    pub histogram_bins: usize,
+    pub parallel: bool,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        ReportConfig {
+            dtw_constraint: DtwConstraint::None,
+            knn_metric: DistanceMetric::Euclidean,
+            histogram_bins: 20,
+            parallel: true,
+        }
+    }
+}
+
+/// Summary statistics of a list of values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub std: f64,
+    pub min: f64,
+    pub median: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    /// Summarizes the values, all statistics are 0 for an empty list
+    pub fn new(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Summary {
+                mean: 0.0,
+                std: 0.0,
+                min: 0.0,
+                median: 0.0,
+                max: 0.0,
+            };
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
+        } else {
+            sorted[sorted.len() / 2]
+        };
+        Summary {
+            mean,
+            std,
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// How one augmenter (or the whole pipeline) changed the dataset
+///
+/// Augmented series are paired with the original series they were created from. The shifts are the
+/// differences of the statistic of the augmented and the original series.
+/// - `spectral_change`: `psd_distance` between original and augmented data, `None` if augmentation
+///   changed the length of the series
+/// - `label_preservation`: fraction of augmented series whose nearest original series has the same label
+/// - `dtw_distances`: DTW distance of every augmented series to its original
+/// - `example_original`, `example_augmented`: the first pair of series, plotted in the HTML report
+#[derive(Clone, Debug)]
+pub struct AugmenterReport {
+    pub name: String,
+    pub n_samples: usize,
+    pub mean_shift: Summary,
+    pub std_shift: Summary,
+    pub min_shift: Summary,
+    pub max_shift: Summary,
+    pub dtw: Summary,
+    pub spectral_change: Option<f64>,
+    pub label_preservation: f64,
+    pub dtw_distances: Vec<f64>,
+    pub example_original: Vec<f64>,
+    pub example_augmented: Vec<f64>,
+}
+
+/// Report of all augmenters of a pipeline, see `generate_report`
+#[derive(Clone, Debug)]
+pub struct QualityReport {
+    pub n_samples: usize,
+    pub series_length: usize,
+    pub augmenters: Vec<AugmenterReport>,
+    histogram_bins: usize,
+}
+
+/// Mean, standard deviation, minimum and maximum of a series
+fn series_statistics(x: &[f64]) -> [f64; 4] {
+    if x.is_empty() {
+        return [0.0; 4];
+    }
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    let std = (x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let min = x.iter().fold(f64::INFINITY, |a, &v| a.min(v));
+    let max = x.iter().fold(f64::NEG_INFINITY, |a, &v| a.max(v));
+    [mean, std, min, max]
+}
+
+fn copy_dataset(dataset: &Dataset) -> Dataset {
+    Dataset {
+        features: dataset.features.clone(),
+        labels: dataset.labels.clone(),
+    }
+}
+
+/// Compares the augmented dataset with the original one
+fn analyze(
+    name: String,
+    original: &Dataset,
+    augmented: &Dataset,
+    config: &ReportConfig,
+) -> AugmenterReport {
+    let n_original = original.features.len();
+    // Augmenters that add rows (like `Repeat`) append copies of the original rows
+    let pair = |j: usize| (&original.features[j % n_original], &augmented.features[j]);
+
+    let compare = |j: usize| {
+        let (x, y) = pair(j);
+        let before = series_statistics(x);
+        let after = series_statistics(y);
+        let shifts: Vec<f64> = after.iter().zip(before).map(|(a, b)| a - b).collect();
+        let dtw = dtw_distance(x, y, config.dtw_constraint, None);
+        (shifts, dtw)
+    };
+    let comparisons: Vec<(Vec<f64>, f64)> = if config.parallel {
+        (0..augmented.features.len())
+            .into_par_iter()
+            .map(compare)
+            .collect()
+    } else {
+        (0..augmented.features.len()).map(compare).collect()
+    };
+    let shift = |k: usize| Summary::new(&comparisons.iter().map(|c| c.0[k]).collect::<Vec<_>>());
+    let dtw_distances: Vec<f64> = comparisons.iter().map(|c| c.1).collect();
+
+    let same_length = original
+        .features
+        .iter()
+        .chain(augmented.features.iter())
+        .all(|x| x.len() == original.features[0].len());
+    let spectral_change = same_length.then(|| psd_distance(original, augmented, config.parallel));
+    let metric = if same_length {
+        config.knn_metric
+    } else {
+        DistanceMetric::Dtw(config.dtw_constraint)
+    };
+    let label_preservation =
+        KnnClassifier::new(1, metric).accuracy(original, augmented, config.parallel);
+
+    let (example_original, example_augmented) = if augmented.features.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        let (x, y) = pair(0);
+        (x.clone(), y.clone())
+    };
+
+    AugmenterReport {
+        name,
+        n_samples: augmented.features.len(),
+        mean_shift: shift(0),
+        std_shift: shift(1),
+        min_shift: shift(2),
+        max_shift: shift(3),
+        dtw: Summary::new(&dtw_distances),
+        spectral_change,
+        label_preservation,
+        dtw_distances,
+        example_original,
+        example_augmented,
+    }
+}
+
+/// Applies every augmenter of the pipeline on its own, and then the whole pipeline, to copies of the
+/// original dataset and summarizes the changes
+///
+/// The report contains one entry per augmenter in the order of the pipeline, followed by one entry
+/// for the whole pipeline.
+pub fn generate_report(
+    original: &Dataset,
+    pipeline: &AugmentationPipeline,
+    config: &ReportConfig,
+) -> QualityReport {
+    assert!(
+        !original.features.is_empty(),
+        "Original dataset must not be empty"
+    );
+
+    let run = |augmenter: &(dyn Augmenter + Sync)| {
+        let mut augmented = copy_dataset(original);
+        augmenter.augment_batch(&mut augmented, config.parallel, false);
+        analyze(augmenter.get_name(), original, &augmented, config)
+    };
+    let mut augmenters: Vec<AugmenterReport> = pipeline
+        .augmenters()
+        .iter()
+        .map(|augmenter| run(augmenter.as_ref()))
+        .collect();
+    augmenters.push(run(pipeline));
+
+    QualityReport {
+        n_samples: original.features.len(),
+        series_length: original.features[0].len(),
+        augmenters,
+        histogram_bins: config.histogram_bins.max(1),
+    }
+}
+
+pub(crate) fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub(crate) fn json_array(values: &[f64]) -> String {
+    let values: Vec<String> = values.iter().map(|&v| json_number(v)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn json_summary(summary: &Summary) -> String {
+    format!(
+        "{{\"mean\": {}, \"std\": {}, \"min\": {}, \"median\": {}, \"max\": {}}}",
+        json_number(summary.mean),
+        json_number(summary.std),
+        json_number(summary.min),
+        json_number(summary.median),
+        json_number(summary.max)
+    )
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const PLOT_WIDTH: f64 = 420.0;
+const PLOT_HEIGHT: f64 = 160.0;
+
+/// Line plot of the original and the augmented example series
+fn svg_example(original: &[f64], augmented: &[f64]) -> String {
+    let values = original.iter().chain(augmented.iter());
+    let min = values.clone().fold(f64::INFINITY, |a, &v| a.min(v));
+    let max = values.fold(f64::NEG_INFINITY, |a, &v| a.max(v));
+    let range = if max > min { max - min } else { 1.0 };
+    let length = original.len().max(augmented.len()).max(2) - 1;
+
+    let polyline = |series: &[f64], color: &str| {
+        let points: Vec<String> = series
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let x = i as f64 / length as f64 * PLOT_WIDTH;
+                let y = PLOT_HEIGHT - (v - min) / range * PLOT_HEIGHT;
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        format!(
+            "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>",
+            color,
+            points.join(" ")
+        )
+    };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\
+         <rect width=\"{w}\" height=\"{h}\" fill=\"#fafafa\"/>{}{}</svg>",
+        polyline(original, "#1f77b4"),
+        polyline(augmented, "#ff7f0e"),
+        w = PLOT_WIDTH,
+        h = PLOT_HEIGHT
+    )
+}
+
+/// Histogram of the DTW distances
+fn svg_histogram(values: &[f64], bins: usize) -> String {
+    let finite: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    let min = finite.iter().fold(f64::INFINITY, |a, &v| a.min(v));
+    let max = finite.iter().fold(f64::NEG_INFINITY, |a, &v| a.max(v));
+    let width = if max > min {
+        (max - min) / bins as f64
+    } else {
+        1.0
+    };
+    let mut counts = vec![0usize; bins];
+    for v in &finite {
+        let bin = (((v - min) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    let highest = counts.iter().copied().max().unwrap_or(0).max(1);
+    let bar_width = PLOT_WIDTH / bins as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\
+         <rect width=\"{w}\" height=\"{h}\" fill=\"#fafafa\"/>",
+        w = PLOT_WIDTH,
+        h = PLOT_HEIGHT
+    );
+    for (i, &count) in counts.iter().enumerate() {
+        let height = count as f64 / highest as f64 * (PLOT_HEIGHT - 10.0);
+        let _ = write!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#2ca02c\"><title>{:.4} to {:.4}: {}</title></rect>",
+            i as f64 * bar_width + 1.0,
+            PLOT_HEIGHT - height,
+            bar_width - 2.0,
+            height,
+            min + i as f64 * width,
+            min + (i + 1) as f64 * width,
+            count
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+impl QualityReport {
+    /// Serializes the report as JSON
+    pub fn to_json(&self) -> String {
+        let augmenters: Vec<String> = self
+            .augmenters
+            .iter()
+            .map(|a| {
+                format!(
+                    "    {{\n      \"name\": {},\n      \"n_samples\": {},\n      \"mean_shift\": {},\n      \
+                     \"std_shift\": {},\n      \"min_shift\": {},\n      \"max_shift\": {},\n      \
+                     \"dtw\": {},\n      \"spectral_change\": {},\n      \"label_preservation\": {},\n      \
+                     \"dtw_distances\": {},\n      \"example\": {{\"original\": {}, \"augmented\": {}}}\n    }}",
+                    json_string(&a.name),
+                    a.n_samples,
+                    json_summary(&a.mean_shift),
+                    json_summary(&a.std_shift),
+                    json_summary(&a.min_shift),
+                    json_summary(&a.max_shift),
+                    json_summary(&a.dtw),
+                    a.spectral_change.map_or("null".to_string(), json_number),
+                    json_number(a.label_preservation),
+                    json_array(&a.dtw_distances),
+                    json_array(&a.example_original),
+                    json_array(&a.example_augmented)
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"n_samples\": {},\n  \"series_length\": {},\n  \"augmenters\": [\n{}\n  ]\n}}\n",
+            self.n_samples,
+            self.series_length,
+            augmenters.join(",\n")
+        )
+    }
+
+    /// Renders the report as a self-contained HTML page with inline SVG plots
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Augmentation quality report</title>\n<style>\n\
+             body { font-family: sans-serif; margin: 2em; color: #222; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; }\n\
+             th:first-child, td:first-child { text-align: left; }\n\
+             .plots { display: flex; gap: 1em; flex-wrap: wrap; }\n\
+             </style>\n</head>\n<body>\n<h1>Augmentation quality report</h1>\n",
+        );
+        let _ = writeln!(
+            html,
+            "<p>{} series of length {}. Shifts are augmented minus original statistic, as mean &plusmn; std over all series.</p>",
+            self.n_samples, self.series_length
+        );
+        html.push_str(
+            "<table>\n<tr><th>Augmenter</th><th>Samples</th><th>Mean shift</th><th>Std shift</th>\
+             <th>Min shift</th><th>Max shift</th><th>DTW median</th><th>DTW mean</th>\
+             <th>Spectral change</th><th>Label preservation</th></tr>\n",
+        );
+        let pm = |s: &Summary| format!("{:.4} &plusmn; {:.4}", s.mean, s.std);
+        for a in &self.augmenters {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{:.4}</td><td>{:.4}</td><td>{}</td><td>{:.1}%</td></tr>",
+                html_escape(&a.name),
+                a.n_samples,
+                pm(&a.mean_shift),
+                pm(&a.std_shift),
+                pm(&a.min_shift),
+                pm(&a.max_shift),
+                a.dtw.median,
+                a.dtw.mean,
+                a.spectral_change
+                    .map_or("&ndash;".to_string(), |v| format!("{:.4}", v)),
+                a.label_preservation * 100.0
+            );
+        }
+        html.push_str("</table>\n");
+
+        for a in &self.augmenters {
+            let _ = writeln!(
+                html,
+                "<h2>{}</h2>\n<div class=\"plots\">\n<figure>{}<figcaption>First series: \
+                 <span style=\"color:#1f77b4\">original</span>, \
+                 <span style=\"color:#ff7f0e\">augmented</span></figcaption></figure>\n\
+                 <figure>{}<figcaption>DTW distance to the original series</figcaption></figure>\n</div>",
+                html_escape(&a.name),
+                svg_example(&a.example_original, &a.example_augmented),
+                svg_histogram(&a.dtw_distances, self.histogram_bins)
+            );
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Writes the JSON report to a file
+    pub fn write_json<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Writes the HTML report to a file
+    pub fn write_html<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_html())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::Float;
+
+/// Smoothed minimum `-gamma * log(sum(exp(-x / gamma)))`, computed stably
+fn softmin(values: [f64; 3], gamma: f64) -> f64 {
+    let min = values.iter().fold(f64::INFINITY, |acc, &v| acc.min(v));
+    if min == f64::INFINITY {
+        return f64::INFINITY;
+    }
+    let sum: f64 = values.iter().map(|&v| (-(v - min) / gamma).exp()).sum();
+    min - gamma * sum.ln()
+}
+
+/// Squared differences between all points of `a` and `b`, padded with a zero border at the end
+fn cost_matrix<T: Float>(a: &[T], b: &[T]) -> Vec<Vec<f64>> {
+    let (n, m) = (a.len(), b.len());
+    let mut cost = vec![vec![0.0; m + 2]; n + 2];
+    for i in 1..=n {
+        for j in 1..=m {
+            cost[i][j] = (a[i - 1].as_f64() - b[j - 1].as_f64()).powi(2);
+        }
+    }
+    cost
+}
+
+/// Forward pass, `r[i][j]` is the soft-DTW value of `a[..i]` and `b[..j]`
+fn accumulate(cost: &[Vec<f64>], n: usize, m: usize, gamma: f64) -> Vec<Vec<f64>> {
+    let mut r = vec![vec![f64::INFINITY; m + 2]; n + 2];
+    r[0][0] = 0.0;
+    for i in 1..=n {
+        for j in 1..=m {
+            r[i][j] = cost[i][j] + softmin([r[i - 1][j - 1], r[i - 1][j], r[i][j - 1]], gamma);
+        }
+    }
+    r
+}
+
+/// Soft-DTW of two sequences (Cuturi & Blondel, 2017)
+///
+/// Replaces the minimum in the DTW recursion with a soft minimum of smoothing parameter `gamma`, which
+/// makes the distance differentiable. Uses the squared difference as local cost and, as in the paper,
+/// is not normalized. Converges to the DTW distance with squared cost as `gamma` goes to 0. Soft-DTW can
+/// be negative, also for identical sequences. Computed in double precision for f32 sequences too.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::soft_dtw;
+/// let a = vec![1.0, 2.0, 3.0];
+/// let b = vec![1.0, 2.0, 2.0, 3.0];
+/// let distance = soft_dtw(&a, &b, 0.1);
+/// ```
+pub fn soft_dtw<T: Float>(a: &[T], b: &[T], gamma: f64) -> f64 {
+    assert!(gamma > 0.0, "gamma must be greater than 0");
+    let (n, m) = (a.len(), b.len());
+    let cost = cost_matrix(a, b);
+    accumulate(&cost, n, m, gamma)[n][m]
+}
+
+/// Soft-DTW of two sequences and its gradient with respect to `a`
+///
+/// The gradient is computed with the backward recursion of the expected alignment matrix, so it costs
+/// the same `O(n * m)` as the distance itself.
+/// # Examples
+/// ```
+/// use rats_rs::quality_benchmarking::soft_dtw_gradient;
+/// let a = vec![1.0, 2.0, 3.0];
+/// let b = vec![1.0, 2.0, 2.0, 3.0];
+/// let (distance, gradient) = soft_dtw_gradient(&a, &b, 0.1);
+/// assert_eq!(gradient.len(), a.len());
+/// ```
+pub fn soft_dtw_gradient<T: Float>(a: &[T], b: &[T], gamma: f64) -> (f64, Vec<f64>) {
+    assert!(gamma > 0.0, "gamma must be greater than 0");
+    let (n, m) = (a.len(), b.len());
+    let cost = cost_matrix(a, b);
+    let mut r = accumulate(&cost, n, m, gamma);
+    let distance = r[n][m];
+
+    // Border for the backward pass, only the path from the last cell contributes
+    for row in r.iter_mut().take(n + 1) {
+        row[m + 1] = f64::NEG_INFINITY;
+    }
+    r[n + 1].fill(f64::NEG_INFINITY);
+    r[n + 1][m + 1] = distance;
+
+    // e[i][j] is the expected alignment, i.e. the derivative of the distance by cost[i][j]
+    let mut e = vec![vec![0.0; m + 2]; n + 2];
+    e[n + 1][m + 1] = 1.0;
+    for i in (1..=n).rev() {
+        for j in (1..=m).rev() {
+            let down = ((r[i + 1][j] - r[i][j] - cost[i + 1][j]) / gamma).exp();
+            let right = ((r[i][j + 1] - r[i][j] - cost[i][j + 1]) / gamma).exp();
+            let diag = ((r[i + 1][j + 1] - r[i][j] - cost[i + 1][j + 1]) / gamma).exp();
+            e[i][j] = e[i + 1][j] * down + e[i][j + 1] * right + e[i + 1][j + 1] * diag;
+        }
+    }
+
+    let gradient = (1..=n)
+        .map(|i| {
+            (1..=m)
+                .map(|j| e[i][j] * 2.0 * (a[i - 1].as_f64() - b[j - 1].as_f64()))
+                .sum()
+        })
+        .collect();
+    (distance, gradient)
+}
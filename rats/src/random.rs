@@ -0,0 +1,140 @@
+//! Random numbers of the augmenters, which can be seeded for reproducible augmentations
+//!
+//! By default the augmenters draw from the thread-local generator of `rand`. After `set_seed`, they
+//! draw from generators derived from the seed instead. The seed belongs to the thread that set it,
+//! seeds of other threads are not affected. Every series of a batch is augmented with a generator
+//! of its own, derived from the seed, the number of batches the calling thread augmented since
+//! `set_seed` and the index of the series, so the results are the same with and without rayon.
+//! Draws for the whole batch, like the choice of `SomeOf`, come from the generator of the calling
+//! thread, which `set_seed` seeds. The results are reproducible as long as the thread starts the
+//! same augmentations in the same order. `with_seed` seeds only the augmentations of a closure.
+//!
+//! # Example
+//!
+//! ```
+//! use rats_rs::Dataset;
+//! use rats_rs::augmenters::{Augmenter, Jittering};
+//!
+//! let set = Dataset {
+//!     features: vec![vec![0.0; 10]; 4],
+//!     labels: vec![String::from("1"); 4],
+//! };
+//!
+//! let augment = |parallel| {
+//!     rats_rs::random::set_seed(Some(42));
+//!     let mut copy = set.clone();
+//!     Jittering::new(0.1).augment_batch(&mut copy, parallel, false);
+//!     copy.features
+//! };
+//!
+//! assert_eq!(augment(false), augment(true));
+//! # rats_rs::random::set_seed(None);
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static GENERATOR: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    /// Seed of the thread and the batches it started since the seed was set
+    static SEED: Cell<Option<(u64, u64)>> = const { Cell::new(None) };
+}
+
+/// Seeds all following augmentations started by the calling thread, or makes them random again
+/// when `seed` is `None`
+pub fn set_seed(seed: Option<u64>) {
+    SEED.with(|s| s.set(seed.map(|seed| (seed, 0))));
+    GENERATOR.with(|g| *g.borrow_mut() = seed.map(StdRng::seed_from_u64));
+}
+
+/// The seed the calling thread set with `set_seed`
+pub fn seed() -> Option<u64> {
+    SEED.with(Cell::get).map(|(seed, _)| seed)
+}
+
+/// Generator the augmenters draw from
+///
+/// Draws from the seeded generator of the current thread if there is one and from the
+/// thread-local generator of `rand` otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AugmenterRng;
+
+/// The generator of the current thread, see `AugmenterRng`
+pub fn rng() -> AugmenterRng {
+    AugmenterRng
+}
+
+impl AugmenterRng {
+    fn with<R>(f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+        GENERATOR.with(|g| match g.borrow_mut().as_mut() {
+            Some(generator) => f(generator),
+            None => f(&mut rand::rng()),
+        })
+    }
+}
+
+impl RngCore for AugmenterRng {
+    fn next_u32(&mut self) -> u32 {
+        Self::with(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Self::with(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Self::with(|rng| rng.fill_bytes(dest))
+    }
+}
+
+/// Runs `f` with the augmentations it starts on the calling thread seeded by `seed`
+///
+/// Like `set_seed` for the duration of `f`, the seed and the state of the generator of the thread
+/// are restored afterwards. Used to seed work by a seed of its own, like the batches of a stream.
+pub fn with_seed<R>(seed: u64, f: impl FnOnce() -> R) -> R {
+    let previous = SEED.with(|s| s.replace(Some((seed, 0))));
+    let generator = GENERATOR.with(|g| g.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = f();
+    GENERATOR.with(|g| g.replace(generator));
+    SEED.with(|s| s.set(previous));
+    result
+}
+
+/// Combines two numbers into a seed, e.g. a seed and the index of a batch
+///
+/// The SplitMix64 finalizer, which spreads both over all bits.
+pub fn mix(a: u64, b: u64) -> u64 {
+    let mut z = a ^ b
+        .wrapping_add(0x9e37_79b9_7f4a_7c15)
+        .wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Seed of the series of one batch, `None` unless the thread is seeded
+#[derive(Clone, Copy)]
+pub(crate) struct BatchSeed(Option<u64>);
+
+impl BatchSeed {
+    /// Starts a new batch
+    pub(crate) fn next() -> Self {
+        BatchSeed(SEED.with(|s| {
+            let (seed, batch) = s.get()?;
+            s.set(Some((seed, batch + 1)));
+            Some(mix(seed, batch))
+        }))
+    }
+
+    /// Runs `f` with the generator of series `row` of this batch
+    ///
+    /// The thread is seeded by the row for the duration of `f`, so batches started for the series,
+    /// e.g. by nested pipelines on rayon threads, are seeded as well.
+    pub(crate) fn row<R>(self, row: usize, f: impl FnOnce() -> R) -> R {
+        match self.0 {
+            Some(seed) => with_seed(mix(seed, row as u64), f),
+            None => f(),
+        }
+    }
+}
@@ -1,36 +1,39 @@
-use crate::Dataset;
+use crate::{Dataset, DenseDataset, Float};
 use rayon::prelude::*;
-use rustdct::{DctPlanner};
+use rustdct::DctPlanner;
+
+/// Normalizes the output of the DCT-III so that it inverts the DCT-II
+fn normalize<T: Float>(buffer: &mut [T]) {
+    let norm = T::cast(2.0 / (buffer.len() as f64));
+    for v in buffer {
+        *v *= norm;
+    }
+}
 
 /// Discrete Cosine Transform (DCT-II) for time series data.
 /// Converts each real-valued time series in the dataset into DCT coefficients (real, frequency representation)
-pub fn dataset_dct(dataset: &Dataset, parallel: bool) -> Dataset {
-    let dct_features: Vec<Vec<f64>> = if parallel {
-        dataset
-            .features
-            .par_iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = DctPlanner::new();
-                let dct = planner.plan_dct2(len);
-                let mut buffer = sample.clone();
-                dct.process_dct2(&mut buffer);
-                buffer
-            })
-            .collect()
+///
+/// The DCT is planned once for the length of the first series and again only for series of other
+/// lengths.
+pub fn dataset_dct<T: Float>(dataset: &Dataset<T>, parallel: bool) -> Dataset<T> {
+    let shared = dataset.features.first().map(|s| {
+        let len = s.len();
+        (len, DctPlanner::new().plan_dct2(len))
+    });
+    let transform = |sample: &Vec<T>| {
+        let len = sample.len();
+        let dct = match &shared {
+            Some((first, plan)) if *first == len => plan.clone(),
+            _ => DctPlanner::new().plan_dct2(len),
+        };
+        let mut buffer = sample.clone();
+        dct.process_dct2(&mut buffer);
+        buffer
+    };
+    let dct_features: Vec<Vec<T>> = if parallel {
+        dataset.features.par_iter().map(transform).collect()
     } else {
-        dataset
-            .features
-            .iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = DctPlanner::new();
-                let dct = planner.plan_dct2(len);
-                let mut buffer = sample.clone();
-                dct.process_dct2(&mut buffer);
-                buffer
-            })
-            .collect()
+        dataset.features.iter().map(transform).collect()
     };
 
     Dataset {
@@ -41,46 +44,53 @@ pub fn dataset_dct(dataset: &Dataset, parallel: bool) -> Dataset {
 
 /// Inverse Discrete Cosine Transform (DCT-III) for time series data.
 /// Reconstructs each time series from its DCT coefficients, recovering the original signal
-pub fn dataset_idct(dataset: &Dataset, parallel: bool) -> Dataset {
-    let time_features: Vec<Vec<f64>> = if parallel {
-        dataset
-            .features
-            .par_iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = DctPlanner::new();
-                let idct = planner.plan_dct3(len);
-                let mut buffer = sample.clone();
-                idct.process_dct3(&mut buffer);
-                // normalize
-                let norm = 2.0 / (len as f64);
-                for v in &mut buffer {
-                    *v *= norm;
-                }
-                buffer
-            })
-            .collect()
+pub fn dataset_idct<T: Float>(dataset: &Dataset<T>, parallel: bool) -> Dataset<T> {
+    let shared = dataset.features.first().map(|s| {
+        let len = s.len();
+        (len, DctPlanner::new().plan_dct3(len))
+    });
+    let transform = |sample: &Vec<T>| {
+        let len = sample.len();
+        let idct = match &shared {
+            Some((first, plan)) if *first == len => plan.clone(),
+            _ => DctPlanner::new().plan_dct3(len),
+        };
+        let mut buffer = sample.clone();
+        idct.process_dct3(&mut buffer);
+        normalize(&mut buffer);
+        buffer
+    };
+    let time_features: Vec<Vec<T>> = if parallel {
+        dataset.features.par_iter().map(transform).collect()
     } else {
-        dataset
-            .features
-            .iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = DctPlanner::new();
-                let idct = planner.plan_dct3(len);
-                let mut buffer = sample.clone();
-                idct.process_dct3(&mut buffer);
-                let norm = 2.0 / (len as f64);
-                for v in &mut buffer {
-                    *v *= norm;
-                }
-                buffer
-            })
-            .collect()
+        dataset.features.iter().map(transform).collect()
     };
 
     Dataset {
         features: time_features,
         labels: dataset.labels.clone(),
     }
-}
\ No newline at end of file
+}
+
+/// Like `dataset_dct` for contiguously stored series, transforming every channel
+///
+/// The DCT is planned once for all series.
+pub fn dense_dct<T: Float>(dataset: &DenseDataset<T>, parallel: bool) -> DenseDataset<T> {
+    let len = dataset.series_length();
+    let dct = DctPlanner::new().plan_dct2(len);
+    dataset.map_series(len, parallel, |sample, buffer| {
+        buffer.copy_from_slice(sample);
+        dct.process_dct2(buffer);
+    })
+}
+
+/// Like `dataset_idct` for contiguously stored series, transforming every channel
+pub fn dense_idct<T: Float>(dataset: &DenseDataset<T>, parallel: bool) -> DenseDataset<T> {
+    let len = dataset.series_length();
+    let idct = DctPlanner::new().plan_dct3(len);
+    dataset.map_series(len, parallel, |sample, buffer| {
+        buffer.copy_from_slice(sample);
+        idct.process_dct3(buffer);
+        normalize(buffer);
+    })
+}
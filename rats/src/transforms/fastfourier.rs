@@ -1,49 +1,63 @@
-use crate::Dataset;
-use rustfft::{FftPlanner, num_complex::Complex};
+use crate::{Dataset, DenseDataset, Float};
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
 
 use rayon::prelude::*;
 
+/// Transforms `sample` with `fft` and writes the interleaved real and imaginary parts to `spectrum`
+fn fft_into<T: Float>(fft: &dyn Fft<T>, sample: &[T], spectrum: &mut [T]) {
+    let mut buffer: Vec<Complex<T>> = sample
+        .iter()
+        .map(|&x| Complex {
+            re: x,
+            im: T::zero(),
+        })
+        .collect();
+    fft.process(&mut buffer);
+    for (c, out) in buffer.iter().zip(spectrum.chunks_exact_mut(2)) {
+        out[0] = c.re;
+        out[1] = c.im;
+    }
+}
+
+/// Transforms the interleaved `spectrum` back with `ifft` and writes the real parts to `sample`
+fn ifft_into<T: Float>(ifft: &dyn Fft<T>, spectrum: &[T], sample: &mut [T]) {
+    let len = spectrum.len() / 2;
+    let mut buffer: Vec<Complex<T>> = (0..len)
+        .map(|i| Complex {
+            re: spectrum[2 * i],
+            im: spectrum[2 * i + 1],
+        })
+        .collect();
+    ifft.process(&mut buffer);
+    for (c, out) in buffer.iter().zip(sample.iter_mut()) {
+        *out = c.re / T::cast(len as f64);
+    }
+}
+
 /// Converts each real-valued time series in the dataset into its frequency domain representation,
 /// storing the result as interleaved real and imaginary parts: [re0, im0, re1, im1, ...]
-pub fn dataset_fft(dataset: &Dataset, parallel: bool) -> Dataset {
-    let freq_features: Vec<Vec<f64>> = if parallel {
-        dataset
-            .features
-            .par_iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = FftPlanner::new();
-                let fft = planner.plan_fft_forward(len);
-                let mut buffer: Vec<Complex<f64>> =
-                    sample.iter().map(|&x| Complex { re: x, im: 0.0 }).collect();
-                fft.process(&mut buffer);
-                let mut spectrum = Vec::with_capacity(2 * len);
-                for c in buffer {
-                    spectrum.push(c.re);
-                    spectrum.push(c.im);
-                }
-                spectrum
-            })
-            .collect()
+///
+/// The FFT is planned once for the length of the first series and again only for series of other
+/// lengths.
+pub fn dataset_fft<T: Float>(dataset: &Dataset<T>, parallel: bool) -> Dataset<T> {
+    let shared = dataset.features.first().map(|s| {
+        let len = s.len();
+        (len, FftPlanner::new().plan_fft_forward(len))
+    });
+    let transform = |sample: &Vec<T>| {
+        let len = sample.len();
+        let fft = match &shared {
+            Some((first, plan)) if *first == len => plan.clone(),
+            _ => FftPlanner::new().plan_fft_forward(len),
+        };
+        let mut spectrum = vec![T::zero(); 2 * len];
+        fft_into(fft.as_ref(), sample, &mut spectrum);
+        spectrum
+    };
+    let freq_features: Vec<Vec<T>> = if parallel {
+        dataset.features.par_iter().map(transform).collect()
     } else {
-        dataset
-            .features
-            .iter()
-            .map(|sample| {
-                let len = sample.len();
-                let mut planner = FftPlanner::new();
-                let fft = planner.plan_fft_forward(len);
-                let mut buffer: Vec<Complex<f64>> =
-                    sample.iter().map(|&x| Complex { re: x, im: 0.0 }).collect();
-                fft.process(&mut buffer);
-                let mut spectrum = Vec::with_capacity(2 * len);
-                for c in buffer {
-                    spectrum.push(c.re);
-                    spectrum.push(c.im);
-                }
-                spectrum
-            })
-            .collect()
+        dataset.features.iter().map(transform).collect()
     };
 
     Dataset {
@@ -54,43 +68,25 @@ pub fn dataset_fft(dataset: &Dataset, parallel: bool) -> Dataset {
 
 
 /// Reconstructs each time series from its frequency domain representation (interleaved real/imag parts),
-pub fn dataset_ifft(dataset: &Dataset, parallel: bool) -> Dataset {
-    let time_features: Vec<Vec<f64>> = if parallel {
-        dataset
-            .features
-            .par_iter()
-            .map(|sample| {
-                let len = sample.len() / 2;
-                let mut planner = FftPlanner::new();
-                let ifft = planner.plan_fft_inverse(len);
-                let mut buffer: Vec<Complex<f64>> = (0..len)
-                    .map(|i| Complex {
-                        re: sample[2 * i],
-                        im: sample[2 * i + 1],
-                    })
-                    .collect();
-                ifft.process(&mut buffer);
-                buffer.iter().map(|c| c.re / len as f64).collect()
-            })
-            .collect()
+pub fn dataset_ifft<T: Float>(dataset: &Dataset<T>, parallel: bool) -> Dataset<T> {
+    let shared = dataset.features.first().map(|s| {
+        let len = s.len() / 2;
+        (len, FftPlanner::new().plan_fft_inverse(len))
+    });
+    let transform = |sample: &Vec<T>| {
+        let len = sample.len() / 2;
+        let ifft = match &shared {
+            Some((first, plan)) if *first == len => plan.clone(),
+            _ => FftPlanner::new().plan_fft_inverse(len),
+        };
+        let mut series = vec![T::zero(); len];
+        ifft_into(ifft.as_ref(), sample, &mut series);
+        series
+    };
+    let time_features: Vec<Vec<T>> = if parallel {
+        dataset.features.par_iter().map(transform).collect()
     } else {
-        dataset
-            .features
-            .iter()
-            .map(|sample| {
-                let len = sample.len() / 2;
-                let mut planner = FftPlanner::new();
-                let ifft = planner.plan_fft_inverse(len);
-                let mut buffer: Vec<Complex<f64>> = (0..len)
-                    .map(|i| Complex {
-                        re: sample[2 * i],
-                        im: sample[2 * i + 1],
-                    })
-                    .collect();
-                ifft.process(&mut buffer);
-                buffer.iter().map(|c| c.re / len as f64).collect()
-            })
-            .collect()
+        dataset.features.iter().map(transform).collect()
     };
 
     Dataset {
@@ -98,3 +94,23 @@ pub fn dataset_ifft(dataset: &Dataset, parallel: bool) -> Dataset {
         labels: dataset.labels.clone(),
     }
 }
+
+/// Like `dataset_fft` for contiguously stored series, transforming every channel
+///
+/// The FFT is planned once for all series.
+pub fn dense_fft<T: Float>(dataset: &DenseDataset<T>, parallel: bool) -> DenseDataset<T> {
+    let len = dataset.series_length();
+    let fft = FftPlanner::new().plan_fft_forward(len);
+    dataset.map_series(2 * len, parallel, |sample, spectrum| {
+        fft_into(fft.as_ref(), sample, spectrum)
+    })
+}
+
+/// Like `dataset_ifft` for contiguously stored series, transforming every channel
+pub fn dense_ifft<T: Float>(dataset: &DenseDataset<T>, parallel: bool) -> DenseDataset<T> {
+    let len = dataset.series_length() / 2;
+    let ifft = FftPlanner::new().plan_fft_inverse(len);
+    dataset.map_series(len, parallel, |spectrum, sample| {
+        ifft_into(ifft.as_ref(), spectrum, sample)
+    })
+}
@@ -0,0 +1,402 @@
+use crate::{Dataset, DenseDataset, Float};
+use crate::transforms::stft::WindowFunction;
+use rayon::prelude::*;
+use rustfft::num_complex::Complex;
+use std::f64::consts::PI;
+
+/// Enum to specify the frequency band a filter lets through
+///
+/// All frequencies are normalized to the Nyquist frequency, i.e. they lie in `(0, 1)` where `1`
+/// corresponds to half the sampling rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterBand {
+    LowPass(f64),
+    HighPass(f64),
+    /// Lower and upper edge of the pass band
+    BandPass(f64, f64),
+    /// Lower and upper edge of the stop band
+    BandStop(f64, f64),
+}
+
+impl FilterBand {
+    fn validate(&self) {
+        let valid = |f: f64| f > 0.0 && f < 1.0;
+        match *self {
+            FilterBand::LowPass(f) | FilterBand::HighPass(f) => {
+                assert!(valid(f), "Cutoff frequency must be in (0, 1), got {}", f)
+            }
+            FilterBand::BandPass(low, high) | FilterBand::BandStop(low, high) => assert!(
+                valid(low) && valid(high) && low < high,
+                "Band edges must satisfy 0 < low < high < 1, got ({}, {})",
+                low,
+                high
+            ),
+        }
+    }
+}
+
+/// Enum to specify how a filter is designed
+///
+/// - `Butterworth`: IIR filter with a maximally flat pass band. The order is doubled for band-pass
+///   and band-stop filters.
+/// - `WindowedSinc`: linear-phase FIR filter with `num_taps` coefficients, obtained by windowing
+///   the ideal impulse response. High-pass and band-stop filters need an odd number of taps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterDesign {
+    Butterworth { order: usize },
+    WindowedSinc { num_taps: usize, window: WindowFunction },
+}
+
+/// A digital filter
+///
+/// - `Fir`: finite impulse response filter given by its taps
+/// - `Iir`: infinite impulse response filter given as a cascade of second-order sections
+///   `[b0, b1, b2, a0, a1, a2]`, which is numerically more robust than a single transfer function
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Fir(Vec<f64>),
+    Iir(Vec<[f64; 6]>),
+}
+
+impl Filter {
+    /// Designs a filter for the given band
+    pub fn design(design: &FilterDesign, band: &FilterBand) -> Self {
+        match *design {
+            FilterDesign::Butterworth { order } => Filter::butterworth(order, band),
+            FilterDesign::WindowedSinc { num_taps, window } => {
+                Filter::windowed_sinc(num_taps, band, window)
+            }
+        }
+    }
+
+    /// Designs a Butterworth filter of the given order
+    ///
+    /// The analog prototype is mapped to the requested band and digitized with the bilinear
+    /// transform, with the band edges pre-warped so that they are exact in the digital filter.
+    pub fn butterworth(order: usize, band: &FilterBand) -> Self {
+        assert!(order > 0, "Filter order must be greater than 0");
+        band.validate();
+
+        // Analog low-pass prototype with cutoff 1 rad/s
+        let poles: Vec<Complex<f64>> = (0..order)
+            .map(|k| {
+                let theta = PI * (2 * k + order + 1) as f64 / (2 * order) as f64;
+                Complex::from_polar(1.0, theta)
+            })
+            .collect();
+
+        // Pre-warped analog frequencies for the bilinear transform with fs = 2
+        let fs = 2.0;
+        let warp = |f: f64| 2.0 * fs * (PI * f / fs).tan();
+
+        let (zeros, poles, gain) = match *band {
+            FilterBand::LowPass(f) => lowpass_to_lowpass(&poles, warp(f)),
+            FilterBand::HighPass(f) => lowpass_to_highpass(&poles, warp(f)),
+            FilterBand::BandPass(low, high) => {
+                lowpass_to_bandpass(&poles, warp(low), warp(high))
+            }
+            FilterBand::BandStop(low, high) => {
+                lowpass_to_bandstop(&poles, warp(low), warp(high))
+            }
+        };
+
+        let (zeros, poles, gain) = bilinear(&zeros, &poles, gain, fs);
+        Filter::Iir(to_sections(&zeros, &poles, gain))
+    }
+
+    /// Designs a linear-phase FIR filter with the window method
+    pub fn windowed_sinc(num_taps: usize, band: &FilterBand, window: WindowFunction) -> Self {
+        assert!(num_taps > 0, "Number of taps must be greater than 0");
+        band.validate();
+
+        // Bands that pass frequencies around the Nyquist frequency need a type I filter
+        let passes_nyquist = matches!(band, FilterBand::HighPass(_) | FilterBand::BandStop(..));
+        assert!(
+            !passes_nyquist || num_taps % 2 == 1,
+            "High-pass and band-stop FIR filters need an odd number of taps"
+        );
+
+        let pass_bands = match *band {
+            FilterBand::LowPass(f) => vec![(0.0, f)],
+            FilterBand::HighPass(f) => vec![(f, 1.0)],
+            FilterBand::BandPass(low, high) => vec![(low, high)],
+            FilterBand::BandStop(low, high) => vec![(0.0, low), (high, 1.0)],
+        };
+
+        let sinc = |x: f64| if x == 0.0 { 1.0 } else { (PI * x).sin() / (PI * x) };
+        let mid = (num_taps - 1) as f64 / 2.0;
+        let window = window.symmetric_coefficients(num_taps);
+
+        let mut taps: Vec<f64> = (0..num_taps)
+            .map(|n| {
+                let m = n as f64 - mid;
+                let ideal: f64 = pass_bands
+                    .iter()
+                    .map(|&(left, right)| right * sinc(right * m) - left * sinc(left * m))
+                    .sum();
+                ideal * window[n]
+            })
+            .collect();
+
+        // Normalize to unit gain in the middle of the first pass band
+        let (left, right) = pass_bands[0];
+        let frequency = if left == 0.0 {
+            0.0
+        } else if right == 1.0 {
+            1.0
+        } else {
+            (left + right) / 2.0
+        };
+        let scale: f64 = taps
+            .iter()
+            .enumerate()
+            .map(|(n, h)| h * (PI * (n as f64 - mid) * frequency).cos())
+            .sum();
+        taps.iter_mut().for_each(|h| *h /= scale);
+
+        Filter::Fir(taps)
+    }
+
+    /// Magnitude of the frequency response at the normalized frequency `frequency` (in `[0, 1]`)
+    pub fn gain(&self, frequency: f64) -> f64 {
+        let z_inv = Complex::from_polar(1.0, -PI * frequency);
+        let polynomial = |coefficients: &[f64]| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(Complex::new(0.0, 0.0), |acc, &c| acc * z_inv + c)
+        };
+        match self {
+            Filter::Fir(taps) => polynomial(taps).norm(),
+            Filter::Iir(sections) => sections
+                .iter()
+                .map(|s| (polynomial(&s[..3]) / polynomial(&s[3..])).norm())
+                .product(),
+        }
+    }
+
+    /// Applies the filter causally, starting from rest
+    pub fn filter<T: Float>(&self, x: &[T]) -> Vec<T> {
+        self.apply(x, false)
+    }
+
+    /// Applies the filter forward and backward, which results in zero phase distortion and
+    /// squares the magnitude response
+    ///
+    /// To reduce transients, the series is extended at both ends by point reflection and the
+    /// filter starts in the steady state of its first input value.
+    pub fn filtfilt<T: Float>(&self, x: &[T]) -> Vec<T> {
+        if x.len() < 2 {
+            return x.to_vec();
+        }
+
+        let order = match self {
+            Filter::Fir(taps) => taps.len(),
+            Filter::Iir(sections) => 2 * sections.len() + 1,
+        };
+        let pad = (3 * order).min(x.len() - 1);
+
+        let two = T::cast(2.0);
+        let first = x[0];
+        let last = x[x.len() - 1];
+        let mut extended = Vec::with_capacity(x.len() + 2 * pad);
+        extended.extend((1..=pad).rev().map(|i| two * first - x[i]));
+        extended.extend_from_slice(x);
+        extended.extend((1..=pad).map(|i| two * last - x[x.len() - 1 - i]));
+
+        let mut y = self.apply(&extended, true);
+        y.reverse();
+        let mut y = self.apply(&y, true);
+        y.reverse();
+
+        y[pad..pad + x.len()].to_vec()
+    }
+
+    /// Filters `x`; if `steady_state` is set, the filter starts as if `x[0]` had been its input forever
+    fn apply<T: Float>(&self, x: &[T], steady_state: bool) -> Vec<T> {
+        if x.is_empty() {
+            return Vec::new();
+        }
+        match self {
+            Filter::Fir(taps) => {
+                let initial = if steady_state { x[0] } else { T::zero() };
+                (0..x.len())
+                    .map(|n| {
+                        taps.iter()
+                            .enumerate()
+                            .map(|(k, &h)| T::cast(h) * if k <= n { x[n - k] } else { initial })
+                            .sum()
+                    })
+                    .collect()
+            }
+            Filter::Iir(sections) => {
+                let mut y = x.to_vec();
+                let mut level = if steady_state { x[0] } else { T::zero() };
+                for section in sections {
+                    let [b0, b1, b2, a0, a1, a2] = section.map(T::cast);
+                    let (b0, b1, b2, a1, a2) = (b0 / a0, b1 / a0, b2 / a0, a1 / a0, a2 / a0);
+                    let one = T::one();
+
+                    // Steady state of the transposed direct form II for a constant input
+                    let (c0, c1) = (b1 - a1 * b0, b2 - a2 * b0);
+                    let mut z0 = level * (c0 + c1) / (one + a1 + a2);
+                    let mut z1 = level * c1 - a2 * z0;
+
+                    for v in y.iter_mut() {
+                        let input = *v;
+                        let output = b0 * input + z0;
+                        z0 = b1 * input - a1 * output + z1;
+                        z1 = b2 * input - a2 * output;
+                        *v = output;
+                    }
+                    level *= (b0 + b1 + b2) / (one + a1 + a2);
+                }
+                y
+            }
+        }
+    }
+}
+
+/// Applies the filter forward and backward to every time series in the dataset
+pub fn dataset_filtfilt<T: Float>(dataset: &Dataset<T>, filter: &Filter, parallel: bool) -> Dataset<T> {
+    let features: Vec<Vec<T>> = if parallel {
+        dataset
+            .features
+            .par_iter()
+            .map(|sample| filter.filtfilt(sample))
+            .collect()
+    } else {
+        dataset
+            .features
+            .iter()
+            .map(|sample| filter.filtfilt(sample))
+            .collect()
+    };
+
+    Dataset {
+        features,
+        labels: dataset.labels.clone(),
+    }
+}
+
+/// Like `dataset_filtfilt` for contiguously stored series, filtering every channel
+pub fn dense_filtfilt<T: Float>(
+    dataset: &DenseDataset<T>,
+    filter: &Filter,
+    parallel: bool,
+) -> DenseDataset<T> {
+    dataset.map_series(dataset.series_length(), parallel, |sample, out| {
+        out.copy_from_slice(&filter.filtfilt(sample))
+    })
+}
+
+type Zpk = (Vec<Complex<f64>>, Vec<Complex<f64>>, f64);
+
+fn product(values: &[Complex<f64>]) -> Complex<f64> {
+    values.iter().fold(Complex::new(1.0, 0.0), |acc, v| acc * v)
+}
+
+fn negated(values: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    values.iter().map(|v| -v).collect()
+}
+
+fn lowpass_to_lowpass(poles: &[Complex<f64>], cutoff: f64) -> Zpk {
+    let gain = cutoff.powi(poles.len() as i32);
+    let poles = poles.iter().map(|p| p * cutoff).collect();
+    (Vec::new(), poles, gain)
+}
+
+fn lowpass_to_highpass(poles: &[Complex<f64>], cutoff: f64) -> Zpk {
+    let gain = (Complex::new(1.0, 0.0) / product(&negated(poles))).re;
+    let zeros = vec![Complex::new(0.0, 0.0); poles.len()];
+    let poles = poles.iter().map(|p| cutoff / p).collect();
+    (zeros, poles, gain)
+}
+
+fn lowpass_to_bandpass(poles: &[Complex<f64>], low: f64, high: f64) -> Zpk {
+    let bandwidth = high - low;
+    let center = (low * high).sqrt();
+    let gain = bandwidth.powi(poles.len() as i32);
+    let zeros = vec![Complex::new(0.0, 0.0); poles.len()];
+    let poles = poles
+        .iter()
+        .flat_map(|p| {
+            let p = p * bandwidth / 2.0;
+            let root = (p * p - center * center).sqrt();
+            [p + root, p - root]
+        })
+        .collect();
+    (zeros, poles, gain)
+}
+
+fn lowpass_to_bandstop(poles: &[Complex<f64>], low: f64, high: f64) -> Zpk {
+    let bandwidth = high - low;
+    let center = (low * high).sqrt();
+    let gain = (Complex::new(1.0, 0.0) / product(&negated(poles))).re;
+    let poles: Vec<Complex<f64>> = poles
+        .iter()
+        .flat_map(|p| {
+            let p = bandwidth / 2.0 / p;
+            let root = (p * p - center * center).sqrt();
+            [p + root, p - root]
+        })
+        .collect();
+    let zeros = (0..poles.len())
+        .map(|i| Complex::new(0.0, if i % 2 == 0 { center } else { -center }))
+        .collect();
+    (zeros, poles, gain)
+}
+
+fn bilinear(zeros: &[Complex<f64>], poles: &[Complex<f64>], gain: f64, fs: f64) -> Zpk {
+    let fs2 = 2.0 * fs;
+    let map = |s: &Complex<f64>| (fs2 + s) / (fs2 - s);
+
+    let mut digital_zeros: Vec<Complex<f64>> = zeros.iter().map(map).collect();
+    let digital_poles: Vec<Complex<f64>> = poles.iter().map(map).collect();
+    // Zeros at infinity are mapped to the Nyquist frequency
+    digital_zeros.resize(digital_poles.len(), Complex::new(-1.0, 0.0));
+
+    let shifted = |values: &[Complex<f64>]| -> Vec<Complex<f64>> {
+        values.iter().map(|v| fs2 - v).collect()
+    };
+    let gain = gain * (product(&shifted(zeros)) / product(&shifted(poles))).re;
+
+    (digital_zeros, digital_poles, gain)
+}
+
+/// Groups roots into real polynomials of degree at most two: conjugate pairs first, then the real
+/// roots two at a time
+fn quadratic_factors(roots: &[Complex<f64>]) -> Vec<[f64; 3]> {
+    let tolerance = 1e-10;
+    let mut factors = Vec::new();
+    let mut real = Vec::new();
+    for root in roots {
+        if root.im.abs() <= tolerance {
+            real.push(root.re);
+        } else if root.im > 0.0 {
+            factors.push([1.0, -2.0 * root.re, root.norm_sqr()]);
+        }
+    }
+    for pair in real.chunks(2) {
+        match *pair {
+            [a, b] => factors.push([1.0, -(a + b), a * b]),
+            [a] => factors.push([1.0, -a, 0.0]),
+            _ => unreachable!(),
+        }
+    }
+    factors
+}
+
+fn to_sections(zeros: &[Complex<f64>], poles: &[Complex<f64>], gain: f64) -> Vec<[f64; 6]> {
+    let numerators = quadratic_factors(zeros);
+    let denominators = quadratic_factors(poles);
+    numerators
+        .iter()
+        .zip(denominators.iter())
+        .enumerate()
+        .map(|(i, (b, a))| {
+            let scale = if i == 0 { gain } else { 1.0 };
+            [b[0] * scale, b[1] * scale, b[2] * scale, a[0], a[1], a[2]]
+        })
+        .collect()
+}
@@ -1,5 +1,8 @@
 //! Module containing various frequency domain transforms for time series data.
 //! This module provides implementations of different frequency domain transforms such as Fast Fourier Transform (FFT) and Discrete Cosine Transform (DCT).
+//! The short-time Fourier transform (STFT) in `stft` provides a time-frequency representation in form of a `Spectrogram`,
+//! and the discrete wavelet transform (DWT) in `wavelet` a multi-resolution representation in form of `WaveletCoefficients`.
+//! The `filter` module designs FIR and IIR low-, high-, band-pass and band-stop filters and applies them with zero phase.
 //! These transforms can be used for various purposes, including feature extraction, noise reduction, and data
 //! compression in time series analysis.
 //! # Examples
@@ -22,4 +25,7 @@
 #![allow(dead_code)]
 pub mod fastfourier;
 pub mod dct;
+pub mod stft;
+pub mod wavelet;
+pub mod filter;
 pub mod accuracy;
@@ -0,0 +1,331 @@
+use crate::{Dataset, Float};
+use rayon::prelude::*;
+use rustfft::{FftPlanner, num_complex::Complex};
+use std::f64::consts::PI;
+
+/// Enum to specify a window function, applied to every frame of the STFT or used to design FIR filters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowFunction {
+    /// Computes the periodic window of the given size
+    ///
+    /// Periodic windows (as opposed to symmetric ones) satisfy the constant overlap-add
+    /// property for the usual hop lengths, which is what the STFT needs.
+    pub fn coefficients(&self, size: usize) -> Vec<f64> {
+        self.evaluate(size, size as f64)
+    }
+
+    /// Computes the symmetric window of the given size
+    ///
+    /// Symmetric windows are used for designing FIR filters.
+    pub fn symmetric_coefficients(&self, size: usize) -> Vec<f64> {
+        if size == 1 {
+            return vec![1.0];
+        }
+        self.evaluate(size, (size - 1) as f64)
+    }
+
+    fn evaluate(&self, size: usize, n: f64) -> Vec<f64> {
+        (0..size)
+            .map(|i| {
+                let phase = 2.0 * PI * i as f64 / n;
+                match self {
+                    WindowFunction::Rectangular => 1.0,
+                    WindowFunction::Hann => 0.5 - 0.5 * phase.cos(),
+                    WindowFunction::Hamming => 0.54 - 0.46 * phase.cos(),
+                    WindowFunction::Blackman => {
+                        0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parameters of a short-time Fourier transform
+///
+/// - `n_fft`: length of each frame and of the FFT computed on it
+/// - `hop_length`: number of samples between the starts of two consecutive frames
+/// - `window`: window function multiplied with every frame
+/// - `center`: if true, the series is reflect-padded by `n_fft / 2` at the start and the rest of
+///   `n_fft` at the end so that frame `t` is centered around sample `t * hop_length`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StftParams {
+    pub n_fft: usize,
+    pub hop_length: usize,
+    pub window: WindowFunction,
+    pub center: bool,
+}
+
+impl StftParams {
+    pub fn new(n_fft: usize, hop_length: usize, window: WindowFunction, center: bool) -> Self {
+        assert!(n_fft > 0, "n_fft must be greater than 0");
+        assert!(
+            hop_length > 0 && hop_length <= n_fft,
+            "hop_length must be in 1..=n_fft"
+        );
+        StftParams {
+            n_fft,
+            hop_length,
+            window,
+            center,
+        }
+    }
+}
+
+impl Default for StftParams {
+    /// Hann window with `n_fft = 64` and 75% overlap
+    fn default() -> Self {
+        StftParams::new(64, 16, WindowFunction::Hann, true)
+    }
+}
+
+/// Time-frequency representation of one time series
+///
+/// `frames[t][f]` holds the complex coefficient of frequency bin `f` in frame `t`. Only the
+/// non-negative frequencies are stored, so every frame has `n_fft / 2 + 1` bins.
+///
+/// The spectrogram remembers the parameters and the length of the series it was computed from,
+/// so it can be inverted with `istft` after it has been modified. The coefficients have the
+/// precision of the series.
+#[derive(Clone, Debug)]
+pub struct Spectrogram<T = f64> {
+    pub frames: Vec<Vec<Complex<T>>>,
+    pub params: StftParams,
+    /// Length of the original time series
+    pub length: usize,
+}
+
+impl<T: Float> Spectrogram<T> {
+    /// Number of frames (time steps)
+    pub fn n_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Number of frequency bins per frame
+    pub fn n_bins(&self) -> usize {
+        self.params.n_fft / 2 + 1
+    }
+
+    /// Magnitude of every coefficient, indexed as `[frame][bin]`
+    pub fn magnitude(&self) -> Vec<Vec<T>> {
+        self.frames
+            .iter()
+            .map(|frame| frame.iter().map(|c| c.norm()).collect())
+            .collect()
+    }
+
+    /// Power (squared magnitude) of every coefficient, indexed as `[frame][bin]`
+    pub fn power(&self) -> Vec<Vec<T>> {
+        self.frames
+            .iter()
+            .map(|frame| frame.iter().map(|c| c.norm_sqr()).collect())
+            .collect()
+    }
+}
+
+/// Maps an index of the padded series back into `0..len` by mirroring at the borders
+/// (without repeating the edge value)
+fn reflect_index(i: isize, len: usize) -> usize {
+    if len == 1 {
+        return 0;
+    }
+    let period = 2 * (len as isize - 1);
+    let i = i.rem_euclid(period);
+    if i < len as isize {
+        i as usize
+    } else {
+        (period - i) as usize
+    }
+}
+
+/// Builds the series the frames are cut from: reflect-padded when centered, otherwise zero-padded
+/// at the end so the last samples are covered by a full frame
+fn pad_series<T: Float>(x: &[T], params: &StftParams) -> Vec<T> {
+    if params.center {
+        // An odd frame needs one more sample after its center than before it
+        let before = (params.n_fft / 2) as isize;
+        let after = (params.n_fft - params.n_fft / 2) as isize;
+        (-before..x.len() as isize + after)
+            .map(|i| x[reflect_index(i, x.len())])
+            .collect()
+    } else {
+        let n_frames = num_frames(x.len(), params);
+        let mut padded = x.to_vec();
+        padded.resize((n_frames - 1) * params.hop_length + params.n_fft, T::zero());
+        padded
+    }
+}
+
+fn num_frames(len: usize, params: &StftParams) -> usize {
+    if params.center {
+        1 + len / params.hop_length
+    } else if len <= params.n_fft {
+        1
+    } else {
+        1 + (len - params.n_fft).div_ceil(params.hop_length)
+    }
+}
+
+/// Short-time Fourier transform of one time series
+///
+/// Splits the series into overlapping frames of `n_fft` samples every `hop_length` samples,
+/// multiplies each frame with the window and computes its FFT in the precision of the series.
+///
+/// # Examples
+/// ```
+/// use rats_rs::transforms::stft::*;
+///
+/// let series: Vec<f64> = (0..256).map(|i| (i as f64 * 0.1).sin()).collect();
+/// let params = StftParams::new(32, 8, WindowFunction::Hann, true);
+///
+/// let spectrogram = stft(&series, &params);
+/// assert_eq!(spectrogram.n_bins(), 17);
+///
+/// let reconstructed = istft(&spectrogram);
+/// assert_eq!(reconstructed.len(), series.len());
+/// ```
+pub fn stft<T: Float>(x: &[T], params: &StftParams) -> Spectrogram<T> {
+    let n_fft = params.n_fft;
+    let n_bins = n_fft / 2 + 1;
+
+    if x.is_empty() {
+        return Spectrogram {
+            frames: Vec::new(),
+            params: *params,
+            length: 0,
+        };
+    }
+
+    let window: Vec<T> = params.window.coefficients(n_fft).into_iter().map(T::cast).collect();
+    let padded = pad_series(x, params);
+    let n_frames = num_frames(x.len(), params);
+
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(n_fft);
+
+    let frames = (0..n_frames)
+        .map(|t| {
+            let start = t * params.hop_length;
+            let mut buffer: Vec<Complex<T>> = padded[start..start + n_fft]
+                .iter()
+                .zip(window.iter())
+                .map(|(&v, &w)| Complex {
+                    re: v * w,
+                    im: T::zero(),
+                })
+                .collect();
+            fft.process(&mut buffer);
+            buffer.truncate(n_bins);
+            buffer
+        })
+        .collect();
+
+    Spectrogram {
+        frames,
+        params: *params,
+        length: x.len(),
+    }
+}
+
+/// Inverse short-time Fourier transform
+///
+/// Reconstructs a time series of the original length by inverting the FFT of each frame and
+/// combining the frames with weighted overlap-add. An unmodified spectrogram is reconstructed
+/// exactly (up to floating point error) as long as every sample is covered by a non-zero window
+/// value, which always holds when `center` is set.
+pub fn istft<T: Float>(spectrogram: &Spectrogram<T>) -> Vec<T> {
+    let params = &spectrogram.params;
+    let n_fft = params.n_fft;
+    let n_bins = n_fft / 2 + 1;
+
+    if spectrogram.frames.is_empty() {
+        return vec![T::zero(); spectrogram.length];
+    }
+
+    let window: Vec<T> = params.window.coefficients(n_fft).into_iter().map(T::cast).collect();
+    let n_frames = spectrogram.frames.len();
+    let padded_len = (n_frames - 1) * params.hop_length + n_fft;
+
+    let mut planner = FftPlanner::new();
+    let ifft = planner.plan_fft_inverse(n_fft);
+
+    let mut signal = vec![T::zero(); padded_len];
+    let mut window_sum = vec![T::zero(); padded_len];
+
+    for (t, frame) in spectrogram.frames.iter().enumerate() {
+        // Restore the negative frequencies from the Hermitian symmetry of a real signal
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); n_fft];
+        for (f, c) in frame.iter().take(n_bins).enumerate() {
+            buffer[f] = *c;
+            if f > 0 && f < n_fft - f {
+                buffer[n_fft - f] = c.conj();
+            }
+        }
+        ifft.process(&mut buffer);
+
+        let start = t * params.hop_length;
+        for (k, c) in buffer.iter().enumerate() {
+            signal[start + k] += c.re / T::cast(n_fft as f64) * window[k];
+            window_sum[start + k] += window[k] * window[k];
+        }
+    }
+
+    signal
+        .iter_mut()
+        .zip(window_sum.iter())
+        .filter(|&(_, &w)| w.as_f64() > 1e-10)
+        .for_each(|(v, &w)| *v /= w);
+
+    let offset = if params.center { n_fft / 2 } else { 0 };
+    signal
+        .into_iter()
+        .skip(offset)
+        .take(spectrogram.length)
+        .collect()
+}
+
+/// Computes the STFT of every time series in the dataset
+pub fn dataset_stft<T: Float>(
+    dataset: &Dataset<T>,
+    params: &StftParams,
+    parallel: bool,
+) -> Vec<Spectrogram<T>> {
+    if parallel {
+        dataset
+            .features
+            .par_iter()
+            .map(|sample| stft(sample, params))
+            .collect()
+    } else {
+        dataset
+            .features
+            .iter()
+            .map(|sample| stft(sample, params))
+            .collect()
+    }
+}
+
+/// Reconstructs a dataset from the spectrograms of its time series and the corresponding labels
+pub fn dataset_istft<T: Float>(
+    spectrograms: &[Spectrogram<T>],
+    labels: &[String],
+    parallel: bool,
+) -> Dataset<T> {
+    let features: Vec<Vec<T>> = if parallel {
+        spectrograms.par_iter().map(istft).collect()
+    } else {
+        spectrograms.iter().map(istft).collect()
+    };
+
+    Dataset {
+        features,
+        labels: labels.to_vec(),
+    }
+}
@@ -0,0 +1,453 @@
+use crate::{Dataset, Float};
+use rayon::prelude::*;
+use std::str::FromStr;
+
+/// Enum to specify the orthogonal wavelet used by the discrete wavelet transform
+///
+/// - `Haar`: the Haar wavelet, identical to `Daubechies(1)`
+/// - `Daubechies(n)`: Daubechies wavelet with `n` vanishing moments, `n` in `1..=8`
+/// - `Symlet(n)`: least asymmetric Daubechies wavelet with `n` vanishing moments, `n` in `2..=8`
+///
+/// Wavelets can also be parsed from their usual short names, e.g. `"haar"`, `"db4"` or `"sym5"`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wavelet {
+    Haar,
+    Daubechies(usize),
+    Symlet(usize),
+}
+
+impl Wavelet {
+    /// Whether the filters of the wavelet are available, i.e. its order is in the supported range
+    pub fn is_supported(&self) -> bool {
+        match *self {
+            Wavelet::Haar => true,
+            Wavelet::Daubechies(n) => (1..=8).contains(&n),
+            Wavelet::Symlet(n) => (2..=8).contains(&n),
+        }
+    }
+
+    /// Low-pass reconstruction filter of the wavelet
+    ///
+    /// The high-pass filter and the decomposition filters are derived from it by the quadrature
+    /// mirror relation. Panics if the wavelet is not supported, see `is_supported`.
+    pub fn filter(&self) -> &'static [f64] {
+        match self {
+            Wavelet::Haar | Wavelet::Daubechies(1) => &HAAR,
+            Wavelet::Daubechies(2) | Wavelet::Symlet(2) => &DB2,
+            Wavelet::Daubechies(3) | Wavelet::Symlet(3) => &DB3,
+            Wavelet::Daubechies(4) => &DB4,
+            Wavelet::Daubechies(5) => &DB5,
+            Wavelet::Daubechies(6) => &DB6,
+            Wavelet::Daubechies(7) => &DB7,
+            Wavelet::Daubechies(8) => &DB8,
+            Wavelet::Symlet(4) => &SYM4,
+            Wavelet::Symlet(5) => &SYM5,
+            Wavelet::Symlet(6) => &SYM6,
+            Wavelet::Symlet(7) => &SYM7,
+            Wavelet::Symlet(8) => &SYM8,
+            _ => panic!("Unsupported wavelet: {:?}", self),
+        }
+    }
+
+    /// Length of the wavelet filters
+    pub fn filter_length(&self) -> usize {
+        self.filter().len()
+    }
+
+    /// Low-pass and high-pass reconstruction filters in the precision `T`
+    fn filters<T: Float>(&self) -> (Vec<T>, Vec<T>) {
+        let low = self.filter();
+        let len = low.len();
+        let high = (0..len)
+            .map(|n| {
+                let sign = if n % 2 == 0 { 1.0 } else { -1.0 };
+                T::cast(sign * low[len - 1 - n])
+            })
+            .collect();
+        (low.iter().map(|&v| T::cast(v)).collect(), high)
+    }
+}
+
+impl FromStr for Wavelet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        let parse_order = |order: &str| {
+            order
+                .parse::<usize>()
+                .map_err(|_| format!("Unknown wavelet: {}", s))
+        };
+        let wavelet = if name == "haar" {
+            Wavelet::Haar
+        } else if let Some(order) = name.strip_prefix("db") {
+            Wavelet::Daubechies(parse_order(order)?)
+        } else if let Some(order) = name.strip_prefix("sym") {
+            Wavelet::Symlet(parse_order(order)?)
+        } else {
+            return Err(format!("Unknown wavelet: {}", s));
+        };
+
+        match wavelet {
+            Wavelet::Daubechies(n) if !(1..=8).contains(&n) => {
+                Err(format!("Daubechies wavelets are supported for orders 1 to 8, got {}", n))
+            }
+            Wavelet::Symlet(n) if !(2..=8).contains(&n) => {
+                Err(format!("Symlets are supported for orders 2 to 8, got {}", n))
+            }
+            _ => Ok(wavelet),
+        }
+    }
+}
+
+/// Multi-level wavelet decomposition of one time series
+///
+/// `details[0]` holds the detail coefficients of level 1 (the finest scale), `details[i]` those of
+/// level `i + 1`. `approximation` holds the approximation coefficients of the coarsest level.
+///
+/// The decomposition remembers the length of the signal at every level, so it can be
+/// reconstructed with `waverec` after the coefficients have been modified. The coefficients have
+/// the precision of the series.
+#[derive(Clone, Debug)]
+pub struct WaveletCoefficients<T = f64> {
+    pub approximation: Vec<T>,
+    pub details: Vec<Vec<T>>,
+    pub wavelet: Wavelet,
+    /// Length of the signal that was decomposed at each level, starting with the original series
+    lengths: Vec<usize>,
+}
+
+impl<T> WaveletCoefficients<T> {
+    /// Number of decomposition levels
+    pub fn level(&self) -> usize {
+        self.details.len()
+    }
+
+    /// Length of the original time series
+    pub fn length(&self) -> usize {
+        self.lengths.first().copied().unwrap_or(self.approximation.len())
+    }
+}
+
+/// Maximum useful decomposition level for a series of length `len`
+///
+/// Beyond this level, the filter is longer than the signal it is applied to.
+pub fn max_level(len: usize, wavelet: &Wavelet) -> usize {
+    let filter_len = wavelet.filter_length();
+    if len < filter_len - 1 || len < 2 {
+        return 0;
+    }
+    (len as f64 / (filter_len - 1) as f64).log2().floor() as usize
+}
+
+/// Single-level discrete wavelet transform
+///
+/// Uses periodic extension of the series, so a series of length `n` results in `ceil(n / 2)`
+/// approximation and detail coefficients each. Series of odd length are extended by repeating
+/// their last value.
+///
+/// Returns the approximation and detail coefficients.
+pub fn dwt<T: Float>(x: &[T], wavelet: &Wavelet) -> (Vec<T>, Vec<T>) {
+    let (low, high) = wavelet.filters::<T>();
+
+    let mut signal = x.to_vec();
+    if signal.len() % 2 == 1 {
+        signal.push(signal[signal.len() - 1]);
+    }
+    let len = signal.len();
+
+    let mut approximation = vec![T::zero(); len / 2];
+    let mut detail = vec![T::zero(); len / 2];
+    for k in 0..len / 2 {
+        for (n, (&l, &h)) in low.iter().zip(high.iter()).enumerate() {
+            let v = signal[(2 * k + n) % len];
+            approximation[k] += l * v;
+            detail[k] += h * v;
+        }
+    }
+    (approximation, detail)
+}
+
+/// Single-level inverse discrete wavelet transform
+///
+/// Reconstructs a signal of length `2 * approximation.len()` from its approximation and detail
+/// coefficients.
+pub fn idwt<T: Float>(approximation: &[T], detail: &[T], wavelet: &Wavelet) -> Vec<T> {
+    assert_eq!(
+        approximation.len(),
+        detail.len(),
+        "Approximation and detail coefficients must have the same length"
+    );
+    let (low, high) = wavelet.filters::<T>();
+
+    let len = 2 * approximation.len();
+    let mut signal = vec![T::zero(); len];
+    for (k, (&a, &d)) in approximation.iter().zip(detail.iter()).enumerate() {
+        for (n, (&l, &h)) in low.iter().zip(high.iter()).enumerate() {
+            signal[(2 * k + n) % len] += a * l + d * h;
+        }
+    }
+    signal
+}
+
+/// Multi-level discrete wavelet decomposition of one time series
+///
+/// `level` is clamped to `max_level` for the length of the series.
+///
+/// # Examples
+/// ```
+/// use rats_rs::transforms::wavelet::*;
+///
+/// let series: Vec<f64> = (0..100).map(|i| (i as f64 * 0.1).sin()).collect();
+///
+/// let coefficients = wavedec(&series, &Wavelet::Daubechies(4), 3);
+/// assert_eq!(coefficients.level(), 3);
+///
+/// let reconstructed = waverec(&coefficients);
+/// assert_eq!(reconstructed.len(), series.len());
+/// ```
+pub fn wavedec<T: Float>(x: &[T], wavelet: &Wavelet, level: usize) -> WaveletCoefficients<T> {
+    let level = level.min(max_level(x.len(), wavelet));
+
+    let mut approximation = x.to_vec();
+    let mut details = Vec::with_capacity(level);
+    let mut lengths = Vec::with_capacity(level);
+    for _ in 0..level {
+        lengths.push(approximation.len());
+        let (a, d) = dwt(&approximation, wavelet);
+        approximation = a;
+        details.push(d);
+    }
+
+    WaveletCoefficients {
+        approximation,
+        details,
+        wavelet: *wavelet,
+        lengths,
+    }
+}
+
+/// Reconstructs a time series from its multi-level wavelet decomposition
+pub fn waverec<T: Float>(coefficients: &WaveletCoefficients<T>) -> Vec<T> {
+    let mut signal = coefficients.approximation.clone();
+    for (detail, &len) in coefficients
+        .details
+        .iter()
+        .zip(coefficients.lengths.iter())
+        .rev()
+    {
+        signal = idwt(&signal, detail, &coefficients.wavelet);
+        signal.truncate(len);
+    }
+    signal
+}
+
+/// Computes the multi-level wavelet decomposition of every time series in the dataset
+pub fn dataset_wavedec<T: Float>(
+    dataset: &Dataset<T>,
+    wavelet: &Wavelet,
+    level: usize,
+    parallel: bool,
+) -> Vec<WaveletCoefficients<T>> {
+    if parallel {
+        dataset
+            .features
+            .par_iter()
+            .map(|sample| wavedec(sample, wavelet, level))
+            .collect()
+    } else {
+        dataset
+            .features
+            .iter()
+            .map(|sample| wavedec(sample, wavelet, level))
+            .collect()
+    }
+}
+
+/// Reconstructs a dataset from the wavelet decompositions of its time series and the corresponding labels
+pub fn dataset_waverec<T: Float>(
+    coefficients: &[WaveletCoefficients<T>],
+    labels: &[String],
+    parallel: bool,
+) -> Dataset<T> {
+    let features: Vec<Vec<T>> = if parallel {
+        coefficients.par_iter().map(waverec).collect()
+    } else {
+        coefficients.iter().map(waverec).collect()
+    };
+
+    Dataset {
+        features,
+        labels: labels.to_vec(),
+    }
+}
+
+// Low-pass reconstruction filters, computed by spectral factorization of the Daubechies polynomial
+
+const HAAR: [f64; 2] = [std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2];
+
+const DB2: [f64; 4] = [
+    0.48296291314453427,
+    0.836516303737808,
+    0.2241438680420133,
+    -0.12940952255126045,
+];
+
+const DB3: [f64; 6] = [
+    0.3326705529500827,
+    0.8068915093110927,
+    0.45987750211849154,
+    -0.13501102001025464,
+    -0.08544127388202664,
+    0.03522629188570957,
+];
+
+const DB4: [f64; 8] = [
+    0.23037781330889656,
+    0.7148465705529159,
+    0.630880767929859,
+    -0.02798376941685991,
+    -0.18703481171909314,
+    0.030841381835560764,
+    0.032883011666885203,
+    -0.010597401785069037,
+];
+
+const DB5: [f64; 10] = [
+    0.16010239797419298,
+    0.6038292697971899,
+    0.7243085284377733,
+    0.13842814590132022,
+    -0.24229488706638208,
+    -0.03224486958463836,
+    0.07757149384004577,
+    -0.006241490212798298,
+    -0.01258075199908201,
+    0.003335725285473777,
+];
+
+const DB6: [f64; 12] = [
+    0.11154074335010952,
+    0.4946238903984533,
+    0.7511339080210956,
+    0.3152503517091976,
+    -0.2262646939654401,
+    -0.12976686756726194,
+    0.09750160558732315,
+    0.027522865530305723,
+    -0.031582039317486064,
+    0.000553842201161505,
+    0.004777257510945514,
+    -0.0010773010853084813,
+];
+
+const DB7: [f64; 14] = [
+    0.0778520540850091,
+    0.396539319481917,
+    0.7291320908462348,
+    0.4697822874051931,
+    -0.14390600392856429,
+    -0.2240361849938748,
+    0.07130921926683015,
+    0.08061260915108297,
+    -0.038029936935014316,
+    -0.016574541630666878,
+    0.012550998556099821,
+    0.0004295779729213697,
+    -0.0018016407040474878,
+    0.0003537137999745192,
+];
+
+const DB8: [f64; 16] = [
+    0.05441584224310399,
+    0.3128715909142999,
+    0.6756307362972898,
+    0.5853546836542063,
+    -0.015829105256348466,
+    -0.28401554296154746,
+    0.00047248457391377215,
+    0.12874742662047806,
+    -0.017369301001807308,
+    -0.0440882539307948,
+    0.013981027917398274,
+    0.008746094047405771,
+    -0.004870352993451569,
+    -0.00039174037337694716,
+    0.0006754494064505686,
+    -0.00011747678412476937,
+];
+
+const SYM4: [f64; 8] = [
+    0.03222310060405146,
+    -0.012603967262031314,
+    -0.0992195435766335,
+    0.29785779560530606,
+    0.8037387518051319,
+    0.49761866763277496,
+    -0.029635527646002538,
+    -0.0757657147895022,
+];
+
+const SYM5: [f64; 10] = [
+    0.02733306834499877,
+    0.029519490925706274,
+    -0.039134249302313795,
+    0.19939753397685567,
+    0.7234076904040406,
+    0.6339789634567923,
+    0.016602105764510607,
+    -0.17532808990805618,
+    -0.021101834024689042,
+    0.01953888273524983,
+];
+
+const SYM6: [f64; 12] = [
+    -0.007800708325032388,
+    0.0017677118642539997,
+    0.04472490177078145,
+    -0.02106029251237103,
+    -0.07263752278637638,
+    0.3379294217281657,
+    0.7876411410286512,
+    0.49105594192797386,
+    -0.04831174258569812,
+    -0.1179901111485201,
+    0.0034907120842221757,
+    0.015404109327044837,
+];
+
+const SYM7: [f64; 14] = [
+    0.0022918339540537653,
+    -0.0032832978474668143,
+    -0.01812660513133842,
+    0.020464207577545968,
+    0.04474234946835253,
+    -0.10101092086842044,
+    -0.056804476889665896,
+    0.48361091568226755,
+    0.7819215932917275,
+    0.3602184609062599,
+    -0.06413128980738586,
+    -0.06490800354718838,
+    0.017213376300804488,
+    0.012015419283549177,
+];
+
+const SYM8: [f64; 16] = [
+    -0.0033824159510050006,
+    -0.0005421323318000123,
+    0.031695087811525975,
+    0.007607487324976628,
+    -0.14329423835127258,
+    -0.06127335906781084,
+    0.48135965125905306,
+    0.777185751699628,
+    0.3644418948361788,
+    -0.05194583810788182,
+    -0.027219029917103506,
+    0.04913717967373033,
+    0.0038087520138944692,
+    -0.014952258337062183,
+    -0.0003029205147241346,
+    0.001889950332767688,
+];
@@ -1,14 +1,18 @@
 use rats_rs::Dataset;
 use rats_rs::augmenters::{
-    AddNoise, AmplitudePhasePerturbation, Augmenter, Crop, FrequencyMask, Jittering, NoiseType,
-    Permutate, Pool, PoolingMethod, Quantize, RandomTimeWarpAugmenter, Repeat, Resize, Reverse,
-    Rotation, Scaling,
+    AddNoise, AmplitudePhasePerturbation, AugmentationPipeline, Augmenter, Convolve, ConvolveBoundary, ConvolveWindow,
+    Crop, Drift, Drop, FilterType, FrequencyMask, Jittering, Magnitude, NoiseType,
+    Permutate, Pool, PoolingMethod, Quantize, RandAugment, RandomFilter, RandomTimeWarpAugmenter, Repeat, Resize, Reverse,
+    Rotation, Scaling, SpecAugment, TrivialAugment, WaveletDrop, WaveletPerturbation,
 };
 use rats_rs::quality_benchmarking::dtw;
 
 use rats_rs::transforms::fastfourier::{dataset_fft, dataset_ifft};
 use rats_rs::transforms::dct::{dataset_dct, dataset_idct};
 use rats_rs::transforms::accuracy::compare_datasets_within_tolerance;
+use rats_rs::transforms::filter::FilterDesign;
+use rats_rs::transforms::stft::{StftParams, WindowFunction};
+use rats_rs::transforms::wavelet::Wavelet;
 
 fn make_test_dataset() -> Dataset {
     Dataset {
@@ -141,6 +145,84 @@ fn app_augmenter_time() {
     assert_ne!(orig, data.features[0]);
 }
 
+#[test]
+fn convolve_constant_boundaries() {
+    let series: Vec<f64> = vec![2.0; 20];
+
+    for boundary in [
+        ConvolveBoundary::Reflect,
+        ConvolveBoundary::Replicate,
+        ConvolveBoundary::Wrap,
+    ] {
+        for window in [
+            ConvolveWindow::Flat,
+            ConvolveWindow::Gaussian,
+            ConvolveWindow::Triangular,
+            ConvolveWindow::Hann,
+            ConvolveWindow::Hamming,
+            ConvolveWindow::Blackman,
+            ConvolveWindow::Exponential(2.0),
+        ] {
+            // Also covers kernels longer than the series
+            let mut aug = Convolve::with_size_range(window, (3, 40));
+            aug.set_boundary(boundary);
+            let res = aug.augment_one(&series);
+
+            assert_eq!(res.len(), series.len());
+            assert!(res.iter().all(|v| (v - 2.0).abs() < 1e-9), "{:?}", boundary);
+        }
+    }
+}
+
+#[test]
+fn convolve_zero_boundary() {
+    let series: Vec<f64> = vec![3.0; 10];
+
+    let aug = Convolve::new(ConvolveWindow::Flat, 3);
+    let res = aug.augment_one(&series);
+
+    assert!((res[0] - 2.0).abs() < 1e-12);
+    assert!((res[5] - 3.0).abs() < 1e-12);
+    assert!((res[9] - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn convolve_custom_kernel() {
+    let series = vec![1.0, 2.0, 3.0, 4.0];
+
+    let mut aug = Convolve::new(ConvolveWindow::Custom(vec![1.0, 0.0, 0.0]), 1);
+    aug.set_boundary(ConvolveBoundary::Wrap);
+    let res = aug.augment_one(&series);
+
+    assert_eq!(res, vec![2.0, 3.0, 4.0, 1.0]);
+}
+
+#[test]
+fn convolve_fft_matches_direct() {
+    let series: Vec<f64> = (0..300).map(|i| (i as f64 * 0.37).sin() * i as f64).collect();
+    let kernel: Vec<f64> = (0..101).map(|i| ((i * 7) % 11) as f64).collect();
+
+    // A kernel of this size is applied with FFT convolution
+    let mut aug = Convolve::new(ConvolveWindow::Custom(kernel.clone()), 1);
+    aug.set_boundary(ConvolveBoundary::Reflect);
+    let res = aug.augment_one(&series);
+
+    let half = kernel.len() / 2;
+    let reflect = |i: isize| {
+        let n = series.len() as isize;
+        let i = if i < 0 { -i } else if i >= n { 2 * (n - 1) - i } else { i };
+        series[i as usize]
+    };
+    for (i, v) in res.iter().enumerate() {
+        let expected: f64 = kernel
+            .iter()
+            .enumerate()
+            .map(|(k, w)| w * reflect(i as isize - k as isize + (kernel.len() - 1 - half) as isize))
+            .sum();
+        assert!((v - expected).abs() < 1e-6 * expected.abs().max(1.0));
+    }
+}
+
 #[test]
 fn crop_larger() {
     let series = vec![1.0; 100];
@@ -189,6 +271,18 @@ fn drop_none() {
     assert_eq!(series, vec![1.0; 100]);
 }
 
+#[test]
+fn drop_fraction() {
+    let series = vec![1.0; 100_000];
+
+    let drop = rats_rs::augmenters::Drop::new(0.3, Some(-1.0));
+    let series = drop.augment_one(&series);
+
+    let dropped = series.iter().filter(|&&v| v == -1.0).count();
+    assert!((dropped as f64 / 100_000.0 - 0.3).abs() < 0.01);
+    assert!(series.iter().all(|&v| v == -1.0 || v == 1.0));
+}
+
 #[test]
 fn test_frequency_mask_dataset() {
     let mut data = Dataset {
@@ -225,6 +319,28 @@ fn jittering() {
     assert_ne!(series, vec![1.0; 100]);
 }
 
+#[test]
+fn jittering_distribution() {
+    let n = 100_001;
+    let series = vec![1.0; n];
+
+    let augmenter = Jittering::new(0.5);
+    let noise: Vec<f64> = augmenter
+        .augment_one(&series)
+        .iter()
+        .map(|v| (v - 1.0) / 0.5)
+        .collect();
+
+    let mean = noise.iter().sum::<f64>() / n as f64;
+    let var = noise.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+    let fraction = |bound: f64| noise.iter().filter(|v| v.abs() < bound).count() as f64 / n as f64;
+    assert!(mean.abs() < 0.02, "mean {}", mean);
+    assert!((var - 1.0).abs() < 0.02, "variance {}", var);
+    assert!((fraction(1.0) - 0.6827).abs() < 0.01);
+    assert!((fraction(2.0) - 0.9545).abs() < 0.005);
+    assert!((fraction(3.0) - 0.9973).abs() < 0.001);
+}
+
 #[test]
 fn permutate() {
     let series = vec![1.0, 2.0, 3.0, 4.0];
@@ -401,6 +517,267 @@ fn scaling() {
         .for_each(|&val| assert!(val >= 2.0 && val <= 4.0));
 }
 
+#[test]
+fn spec_augment_no_masks() {
+    let series: Vec<f64> = (0..128).map(|i| (i as f64 * 0.2).sin()).collect();
+
+    let params = StftParams::new(32, 8, WindowFunction::Hann, true);
+    let aug = SpecAugment::new(0, 0, 0, 0, None, params);
+    let res = aug.augment_one(&series);
+
+    assert_eq!(res.len(), series.len());
+    for (a, b) in series.iter().zip(res.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn spec_augment_batch() {
+    let series: Vec<f64> = (0..128).map(|i| (i as f64 * 0.2).sin()).collect();
+
+    let params = StftParams::new(32, 8, WindowFunction::Hann, true);
+    let aug = SpecAugment::new(2, 8, 2, 8, None, params);
+    let mut set = Dataset {
+        features: vec![series.clone(); 20],
+        labels: vec![String::from("1"); 20],
+    };
+    aug.augment_batch(&mut set, true, false);
+
+    assert!(set.features.iter().all(|row| row.len() == 128));
+    assert!(set.features.iter().any(|row| {
+        row.iter().zip(series.iter()).any(|(a, b)| (a - b).abs() > 1e-6)
+    }));
+}
+
+#[test]
+fn spec_augment_masks_and_warp() {
+    let series: Vec<f64> = (0..256)
+        .map(|i| (i as f64 * 0.2).sin() + (i as f64 * 1.3).sin())
+        .collect();
+
+    let params = StftParams::new(32, 8, WindowFunction::Hann, true);
+    let aug = SpecAugment::new(2, 4, 2, 5, Some(4), params);
+    let res = aug.augment_one(&series);
+
+    assert_eq!(res.len(), series.len());
+    assert!(res.iter().all(|v| v.is_finite()));
+}
+
+#[test]
+fn wavelet_drop_finest_level() {
+    let series = [1.0f64, 3.0].repeat(8);
+
+    // With the Haar wavelet, the finest details hold the differences of neighbouring pairs
+    let aug = WaveletDrop::new(Wavelet::Haar, vec![1], 1.0);
+    let res = aug.augment_one(&series);
+
+    for v in res {
+        assert!((v - 2.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn wavelet_drop_none() {
+    let series: Vec<f64> = (0..40).map(|i| (i as f64 * 0.4).sin()).collect();
+
+    let aug = WaveletDrop::new(Wavelet::Symlet(4), vec![1, 2], 0.0);
+    let res = aug.augment_one(&series);
+
+    for (a, b) in series.iter().zip(res.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn wavelet_perturbation() {
+    let series: Vec<f64> = (0..64).map(|i| (i as f64 * 0.4).sin()).collect();
+
+    let aug = WaveletPerturbation::new(Wavelet::Daubechies(4), vec![1, 2], 0.5);
+    let res = aug.augment_one(&series);
+    assert_eq!(res.len(), series.len());
+    assert_ne!(res, series);
+
+    let aug = WaveletPerturbation::new(Wavelet::Daubechies(4), vec![1, 2], 0.0);
+    let res = aug.augment_one(&series);
+    for (a, b) in series.iter().zip(res.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Unsupported wavelet")]
+fn wavelet_perturbation_unsupported_wavelet() {
+    WaveletPerturbation::new(Wavelet::Daubechies(9), vec![1], 0.5);
+}
+
+#[test]
+#[should_panic(expected = "Percentage must be in [0, 1]")]
+fn wavelet_drop_percentage_out_of_range() {
+    WaveletDrop::new(Wavelet::Haar, vec![1], 50.0);
+}
+
+#[test]
+#[should_panic(expected = "odd number of taps")]
+fn random_filter_even_taps_highpass() {
+    RandomFilter::new(
+        FilterType::HighPass,
+        (0.1, 0.3),
+        FilterDesign::WindowedSinc {
+            num_taps: 4,
+            window: WindowFunction::Hamming,
+        },
+    );
+}
+
+#[test]
+fn random_filter_lowpass() {
+    // Alternating series only contains the Nyquist frequency
+    let series = [1.0f64, -1.0].repeat(50);
+
+    let aug = RandomFilter::new(
+        FilterType::LowPass,
+        (0.1, 0.3),
+        FilterDesign::Butterworth { order: 4 },
+    );
+    let res = aug.augment_one(&series);
+
+    assert_eq!(res.len(), series.len());
+    // filtfilt pads the series by odd reflection about its first and last value, which gives the
+    // padding a mean of 2 here. The step to the zero mean of the series leaves an edge transient
+    // that decays slowly at low cutoffs, so only the middle is checked
+    assert!(res.iter().skip(30).take(40).all(|v| v.abs() < 0.05));
+}
+
+#[test]
+fn random_filter_bandstop_fir() {
+    let series: Vec<f64> = (0..100).map(|i| (i as f64 * 0.05).sin()).collect();
+    let mut set = Dataset {
+        features: vec![series.clone(); 5],
+        labels: vec![String::from("1"); 5],
+    };
+
+    let aug = RandomFilter::new(
+        FilterType::BandStop,
+        (0.5, 0.9),
+        FilterDesign::WindowedSinc {
+            num_taps: 21,
+            window: WindowFunction::Hamming,
+        },
+    );
+    aug.augment_batch(&mut set, true, false);
+
+    // The low frequency content is preserved
+    for row in set.features {
+        for (a, b) in row.iter().zip(series.iter()).skip(10).take(80) {
+            assert!((a - b).abs() < 0.05);
+        }
+    }
+}
+
+#[test]
+fn magnitude_zero_is_identity() {
+    let series: Vec<f64> = (0..64).map(|i| (i as f64 * 0.3).sin()).collect();
+    let augmenters: Vec<Box<dyn Augmenter>> = vec![
+        Box::new(Jittering::from_magnitude(0.0)),
+        Box::new(Scaling::from_magnitude(0.0)),
+        Box::new(Drift::from_magnitude(0.0)),
+        Box::new(Drop::from_magnitude(0.0)),
+        Box::new(Convolve::from_magnitude(0.0)),
+        Box::new(Pool::from_magnitude(0.0)),
+        Box::new(RandomTimeWarpAugmenter::from_magnitude(0.0)),
+        Box::new(WaveletDrop::from_magnitude(0.0)),
+        Box::new(WaveletPerturbation::from_magnitude(0.0)),
+    ];
+    for aug in augmenters {
+        let res = aug.augment_one(&series);
+        for (a, b) in series.iter().zip(res.iter()) {
+            assert!((a - b).abs() < 1e-9, "{} changed the series", aug.get_name());
+        }
+    }
+}
+
+#[test]
+fn magnitude_increases_strength() {
+    let series: Vec<f64> = (0..200).map(|i| (i as f64 * 0.3).sin()).collect();
+    let change = |aug: &dyn Augmenter| -> f64 {
+        let res = aug.augment_one(&series);
+        res.iter().zip(series.iter()).map(|(a, b)| (a - b).abs()).sum::<f64>()
+    };
+
+    assert!(change(&Jittering::from_magnitude(0.1)) < change(&Jittering::from_magnitude(1.0)));
+    assert!(change(&Convolve::from_magnitude(0.1)) < change(&Convolve::from_magnitude(1.0)));
+    assert!(change(&RandomFilter::from_magnitude(0.0)) < change(&RandomFilter::from_magnitude(1.0)));
+}
+
+#[test]
+#[should_panic(expected = "standard deviation")]
+fn jittering_negative_deviation() {
+    Augmenter::<f64>::augment_one(&Jittering::new(-1.0), &[0.0; 8]);
+}
+
+#[test]
+#[should_panic]
+fn magnitude_out_of_range() {
+    Jittering::from_magnitude(1.5);
+}
+
+#[test]
+fn rand_augment_default_ops() {
+    let series: Vec<f64> = (0..64).map(|i| (i as f64 * 0.3).sin()).collect();
+    let mut set = Dataset {
+        features: vec![series.clone(); 20],
+        labels: vec![String::from("1"); 20],
+    };
+
+    let aug = RandAugment::new(3, 0.7);
+    assert_eq!(aug.num_ops(), 11);
+    aug.augment_batch(&mut set, true, false);
+
+    for row in set.features {
+        assert_eq!(row.len(), series.len());
+        assert!(row.iter().all(|v| v.is_finite()));
+    }
+}
+
+#[test]
+fn rand_augment_custom_ops() {
+    let series = vec![1.0; 10];
+
+    let mut aug = RandAugment::new(4, 0.0);
+    aug.clear_ops();
+    aug.add_op::<Scaling>();
+    assert_eq!(aug.augment_one(&series), series);
+
+    let mut aug = RandAugment::new(1, 1.0);
+    aug.clear_ops();
+    aug.add_op::<Scaling>();
+    let res = aug.augment_one(&series);
+    assert!(res.iter().all(|&v| (0.5..=1.5).contains(&v)));
+}
+
+#[test]
+fn trivial_augment() {
+    let series: Vec<f64> = (0..64).map(|i| (i as f64 * 0.3).sin()).collect();
+    let mut set = Dataset {
+        features: vec![series.clone(); 20],
+        labels: vec![String::from("1"); 20],
+    };
+
+    let aug = TrivialAugment::new();
+    aug.augment_batch(&mut set, false, false);
+
+    for row in set.features {
+        assert_eq!(row.len(), series.len());
+    }
+
+    let mut aug = TrivialAugment::new();
+    aug.clear_ops();
+    aug.add_op::<Drop>();
+    let res = aug.augment_one(&vec![1.0; 100]);
+    // Drops at most 30 % of the points in expectation
+    assert!(res.iter().filter(|&&v| v == 0.0).count() < 60);
+}
+
 #[test]
 fn random_time_warp_full_series() {
     let mut data = Dataset {
@@ -467,4 +844,28 @@ fn time_warp_with_dtw_full_window(){
     aug.augment_batch(&mut data, true, true);
     let (distance, _) = dtw(&original[0], &data.features[0]);
     assert_ne!(distance , 0.0);
-}   
\ No newline at end of file
+}   
+#[test]
+fn single_precision() {
+    let mut set: Dataset<f32> = Dataset {
+        features: vec![(0..64).map(|i| (i as f32 * 0.2).sin()).collect(); 8],
+        labels: vec![String::from("1"); 8],
+    };
+    let expected: Vec<Vec<f32>> = set
+        .features
+        .iter()
+        .map(|x| x.iter().rev().map(|v| v * 2.0).collect())
+        .collect();
+
+    let pipeline = AugmentationPipeline::new() + Reverse::new() + Scaling::new(2.0, 2.0);
+    pipeline.augment_batch(&mut set, true, true);
+    assert_eq!(set.features, expected);
+
+    let pipeline = AugmentationPipeline::new()
+        + Jittering::new(0.1)
+        + Convolve::new(ConvolveWindow::Gaussian, 5)
+        + FrequencyMask::new(4, true);
+    pipeline.augment_batch(&mut set, true, false);
+    assert!(set.features.iter().flatten().all(|v| v.is_finite()));
+    assert!(dtw(&set.features[0], &expected[0]).0 < 1.0);
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn rats(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rats"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Empty directory for the files of one test
+fn workdir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rats-cli-{}-{}", std::process::id(), test));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path(dir: &Path, name: &str) -> String {
+    dir.join(name).to_str().unwrap().to_string()
+}
+
+/// Three series of length 4 in two classes, as the CLI writes them
+const CSV: &str = "\
+t_0,t_1,t_2,t_3,label
+0,0.5,-1.25,3,a
+1.5,2,2.5,-3,b
+0.125,0,0,7,a
+";
+
+#[test]
+fn formats_roundtrip() {
+    let dir = workdir("roundtrip");
+    let input = path(&dir, "data.csv");
+    fs::write(&input, CSV).unwrap();
+
+    // csv -> tsv -> txt -> csv, reversing twice on every step leaves the series unchanged
+    let files = [input, path(&dir, "data.tsv"), path(&dir, "data.txt"), path(&dir, "back.csv")];
+    for step in files.windows(2) {
+        let output = rats(&["augment", &step[0], "-p", "Reverse + Reverse", "-o", &step[1]]);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    assert_eq!(
+        fs::read_to_string(&files[1]).unwrap(),
+        "a\t0\t0.5\t-1.25\t3\nb\t1.5\t2\t2.5\t-3\na\t0.125\t0\t0\t7\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&files[2]).unwrap(),
+        "a 0 0.5 -1.25 3\nb 1.5 2 2.5 -3\na 0.125 0 0 7\n"
+    );
+    assert_eq!(fs::read_to_string(&files[3]).unwrap(), CSV);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn transform_roundtrip() {
+    let dir = workdir("transform");
+    let input = path(&dir, "data.csv");
+    let spectrum = path(&dir, "spectrum.tsv");
+    let back = path(&dir, "back.csv");
+    fs::write(&input, CSV).unwrap();
+
+    assert!(rats(&["transform", "fft", &input, "-o", &spectrum]).status.success());
+    assert!(rats(&["transform", "fft", &spectrum, "--inverse", "-o", &back]).status.success());
+
+    let values = |text: String| -> Vec<f64> {
+        text.lines()
+            .skip(1)
+            .flat_map(|line| line.split(',').filter_map(|v| v.parse().ok()).collect::<Vec<_>>())
+            .collect()
+    };
+    let original = values(fs::read_to_string(&input).unwrap());
+    let restored = values(fs::read_to_string(&back).unwrap());
+    assert_eq!(original.len(), restored.len());
+    assert!(original.iter().zip(&restored).all(|(a, b)| (a - b).abs() < 1e-9));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn same_seed_same_output() {
+    let dir = workdir("seed");
+    let input = path(&dir, "data.csv");
+    fs::write(&input, CSV).unwrap();
+    let spec = "Jittering(deviation=0.1) + Scaling(min_factor=0.5, max_factor=2, p=0.5)";
+
+    let run = |seed: &str, name: &str| {
+        let output = path(&dir, name);
+        let status = rats(&["augment", &input, "-p", spec, "-o", &output, "-n", "3", "-s", seed]);
+        assert!(status.status.success());
+        fs::read_to_string(output).unwrap()
+    };
+    let first = run("7", "first.csv");
+    assert_eq!(first, run("7", "second.csv"));
+    assert_ne!(first, run("8", "other.csv"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn usage_errors_exit_with_2() {
+    let dir = workdir("usage");
+    let input = path(&dir, "data.csv");
+    let output = path(&dir, "out.csv");
+    fs::write(&input, CSV).unwrap();
+
+    for args in [
+        vec!["augment", &input, "-p", "Foo(size=2)", "-o", &output],
+        vec!["augment", &input, "-p", "Jittering(deviation=-1)", "-o", &output],
+        vec!["augment", &input, "-p", "Repeat(n=0)", "-o", &output],
+        vec![
+            "augment",
+            &input,
+            "-p",
+            "RandomFilter(filter_type=highpass, cutoff_range=[0.1, 0.3], num_taps=4)",
+            "-o",
+            &output,
+        ],
+        vec!["transform", "wavelet", &input, "-o", &output],
+        vec!["augment", &input, "-p", "Reverse", "-o", &output, "--bogus"],
+        vec!["frobnicate"],
+    ] {
+        let result = rats(&args);
+        assert_eq!(result.status.code(), Some(2), "{:?}", args);
+        assert!(!result.stderr.is_empty());
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn panic_reported_once() {
+    let dir = workdir("panic");
+    let input = path(&dir, "data.csv");
+    let output = path(&dir, "out.csv");
+    // Empty series, a spike cannot be placed in any of them
+    fs::write(&input, format!("label\n{}", "a\n".repeat(64))).unwrap();
+
+    // Every series panics, on several threads
+    let spec = "AddNoise(noise_type=spike, bounds=[1, 2])";
+    let result = rats(&["augment", &input, "-p", spec, "-o", &output]);
+    assert_eq!(result.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert_eq!(stderr.lines().count(), 1, "{}", stderr);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unreadable_file_exits_with_1() {
+    let dir = workdir("unreadable");
+    let missing = path(&dir, "missing.csv");
+    let invalid = path(&dir, "invalid.tsv");
+    fs::write(&invalid, "a\t1\tx\n").unwrap();
+
+    for input in [&missing, &invalid] {
+        let result = rats(&["inspect", input]);
+        assert_eq!(result.status.code(), Some(1), "{}", input);
+        assert!(String::from_utf8_lossy(&result.stderr).contains(input.as_str()));
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,20 @@
+//! Signals shared by the integration tests
+
+#![allow(dead_code)]
+
+use rats_rs::Dataset;
+
+/// Sum of two sine waves of different frequencies, shifted by `phase`
+pub fn make_signal(len: usize, phase: f64) -> Vec<f64> {
+    (0..len)
+        .map(|i| (i as f64 * 0.3 + phase).sin() + 0.5 * (i as f64 * 0.05).cos())
+        .collect()
+}
+
+/// Dataset of `n` signals of length `len` with different phases in two classes
+pub fn make_dataset(n: usize, len: usize) -> Dataset {
+    Dataset {
+        features: (0..n).map(|i| make_signal(len, i as f64)).collect(),
+        labels: (0..n).map(|i| (i % 2).to_string()).collect(),
+    }
+}
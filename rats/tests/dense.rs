@@ -0,0 +1,190 @@
+mod common;
+
+use common::make_dataset;
+use ndarray::{Array2, Array3};
+use rats_rs::augmenters::{
+    AugmentationPipeline, Augmenter, AugmenterBase, Crop, FrequencyMask, LabelConditional, Reverse,
+    Scaling,
+};
+use rats_rs::transforms::accuracy::compare_datasets_within_tolerance;
+use rats_rs::transforms::dct::{dataset_dct, dense_dct, dense_idct};
+use rats_rs::transforms::fastfourier::{dataset_fft, dense_fft, dense_ifft};
+use rats_rs::transforms::filter::{Filter, FilterBand, dataset_filtfilt, dense_filtfilt};
+use rats_rs::{Dataset, DenseDataset};
+
+#[test]
+fn dataset_roundtrip() {
+    let set = make_dataset(5, 32);
+    let dense = DenseDataset::from_dataset(&set);
+
+    assert_eq!(dense.shape(), (5, 1, 32));
+    assert_eq!(dense.series(3, 0), set.features[3].as_slice());
+    assert_eq!(dense.sample(4), set.features[4].as_slice());
+
+    let back = dense.to_dataset();
+    assert_eq!(back.features, set.features);
+    assert_eq!(back.labels, set.labels);
+}
+
+#[test]
+#[should_panic(expected = "same length")]
+fn dataset_unequal_lengths() {
+    let set = Dataset {
+        features: vec![vec![1.0; 3], vec![1.0; 4]],
+        labels: vec![String::from("a"); 2],
+    };
+    DenseDataset::from_dataset(&set);
+}
+
+#[test]
+fn ndarray_interop() {
+    let array = Array3::from_shape_fn((2, 3, 4), |(i, c, t)| (i * 100 + c * 10 + t) as f64);
+    let ptr = array.as_ptr();
+    let mut dense = DenseDataset::from_array3(array, vec![String::from("a"); 2]);
+
+    assert_eq!(dense.as_slice().as_ptr(), ptr);
+    assert_eq!(dense.shape(), (2, 3, 4));
+    assert_eq!(dense.series(1, 2), &[120.0, 121.0, 122.0, 123.0]);
+    assert_eq!(dense.view()[[1, 0, 3]], 103.0);
+    assert_eq!(dense.series_view().row(5).to_vec(), dense.series(1, 2));
+
+    dense.view_mut()[[0, 1, 1]] = -1.0;
+    assert_eq!(dense.series(0, 1)[1], -1.0);
+
+    let array = dense.into_array3();
+    assert_eq!(array.as_ptr(), ptr);
+
+    // Non-standard layouts are copied in row-major order
+    let transposed = Array2::from_shape_fn((3, 2), |(i, t)| (i * 10 + t) as f64).reversed_axes();
+    let dense = DenseDataset::from_array2(transposed, vec![String::from("a"); 2]);
+    assert_eq!(dense.as_slice(), &[0.0, 10.0, 20.0, 1.0, 11.0, 21.0]);
+}
+
+#[test]
+fn augment_dense_per_channel() {
+    let data: Vec<f64> = (0..12).map(|i| i as f64).collect();
+    let mut dense = DenseDataset::with_channels(data, 2, 2, 3, vec![String::from("a"); 2]);
+
+    Reverse::new().augment_dense(&mut dense, true);
+
+    assert_eq!(
+        dense.as_slice(),
+        &[2.0, 1.0, 0.0, 5.0, 4.0, 3.0, 8.0, 7.0, 6.0, 11.0, 10.0, 9.0]
+    );
+}
+
+#[test]
+fn augment_dense_probability() {
+    let mut dense = DenseDataset::new(vec![1.0; 1000], 100, vec![String::from("a"); 100]);
+    let mut scaling = Scaling::new(2.0, 2.0);
+    scaling.set_probability(0.5);
+
+    scaling.augment_dense(&mut dense, false);
+
+    // All channels of a sample are augmented together
+    let augmented = (0..100).filter(|&i| dense.sample(i)[0] == 2.0).count();
+    assert!(augmented > 20 && augmented < 80);
+    for i in 0..100 {
+        let first = dense.sample(i)[0];
+        assert!(dense.sample(i).iter().all(|&v| v == first));
+    }
+}
+
+#[test]
+fn augment_dense_labels() {
+    let set = make_dataset(6, 16);
+    let mut dense = DenseDataset::from_dataset(&set);
+
+    let pipeline =
+        AugmentationPipeline::new() + LabelConditional::for_labels(Scaling::new(2.0, 2.0), &["1"]);
+    pipeline.augment_dense(&mut dense, true);
+
+    for i in 0..6 {
+        let factor = if i % 2 == 1 { 2.0 } else { 1.0 };
+        let expected: Vec<f64> = set.features[i].iter().map(|v| v * factor).collect();
+        assert_eq!(dense.series(i, 0), expected.as_slice());
+    }
+}
+
+#[test]
+fn augment_dense_class_pipelines() {
+    let set = make_dataset(6, 16);
+    let mut dense = DenseDataset::from_dataset(&set);
+
+    let mut pipeline = AugmentationPipeline::new() + Scaling::new(3.0, 3.0);
+    pipeline.set_class_pipeline("1", AugmentationPipeline::new() + Reverse::new());
+    pipeline.augment_dense(&mut dense, true);
+
+    for i in 0..6 {
+        let expected: Vec<f64> = if i % 2 == 1 {
+            set.features[i].iter().rev().copied().collect()
+        } else {
+            set.features[i].iter().map(|v| v * 3.0).collect()
+        };
+        assert_eq!(dense.series(i, 0), expected.as_slice());
+    }
+}
+
+#[test]
+fn augment_dense_batch_level() {
+    let set = make_dataset(4, 64);
+    let mut dense = DenseDataset::from_dataset(&set);
+
+    FrequencyMask::new(8, true).augment_dense(&mut dense, true);
+
+    assert_eq!(dense.shape(), (4, 1, 64));
+    assert_ne!(dense, DenseDataset::from_dataset(&set));
+}
+
+#[test]
+#[should_panic(expected = "changes the length")]
+fn augment_dense_length_change() {
+    let mut dense = DenseDataset::new(vec![1.0; 20], 2, vec![String::from("a"); 2]);
+    Crop::new(5).augment_dense(&mut dense, false);
+}
+
+#[test]
+fn dense_transforms_match_dataset() {
+    let set = make_dataset(8, 100);
+    let dense = DenseDataset::from_dataset(&set);
+
+    let fft = dense_fft(&dense, true);
+    assert_eq!(fft.shape(), (8, 1, 200));
+    assert!(compare_datasets_within_tolerance(&fft.to_dataset(), &dataset_fft(&set, true), 1e-9).1);
+    assert!(compare_datasets_within_tolerance(&dense_ifft(&fft, false).to_dataset(), &set, 1e-9).1);
+
+    let dct = dense_dct(&dense, false);
+    assert!(
+        compare_datasets_within_tolerance(&dct.to_dataset(), &dataset_dct(&set, false), 1e-9).1
+    );
+    assert!(compare_datasets_within_tolerance(&dense_idct(&dct, true).to_dataset(), &set, 1e-9).1);
+
+    let filter = Filter::butterworth(4, &FilterBand::LowPass(0.2));
+    assert!(
+        compare_datasets_within_tolerance(
+            &dense_filtfilt(&dense, &filter, true).to_dataset(),
+            &dataset_filtfilt(&set, &filter, true),
+            1e-12
+        )
+        .1
+    );
+}
+
+#[test]
+fn dense_single_precision() {
+    let array = Array2::from_shape_fn((4, 32), |(i, t)| (t as f32 * 0.3 + i as f32).sin());
+    let mut dense = DenseDataset::from_array2(array, vec![String::from("a"); 4]);
+    let original = dense.clone();
+
+    (AugmentationPipeline::new() + Reverse::new() + Reverse::new()).augment_dense(&mut dense, true);
+    assert_eq!(dense, original);
+
+    let restored = dense_ifft(&dense_fft(&dense, true), true);
+    assert!(
+        restored
+            .as_slice()
+            .iter()
+            .zip(original.as_slice())
+            .all(|(a, b)| (a - b).abs() < 1e-5)
+    );
+}
@@ -1,5 +1,18 @@
 use rats_rs::Dataset;
-use rats_rs::augmenters::{AugmentationPipeline, Augmenter, Crop, Drop, Repeat, Scaling};
+use rats_rs::augmenters::{
+    AugmentationPipeline, Augmenter, AugmenterBase, Convolve, ConvolveWindow, Crop, Drop,
+    FrequencyMask, Jittering, LabelConditional, OneOf, Permutate, RandomOrder, Repeat, Reverse,
+    Scaling, SomeOf, StreamConfig,
+};
+use rats_rs::provenance::Value;
+use std::thread;
+
+fn constant_dataset(n: usize) -> Dataset {
+    Dataset {
+        features: vec![vec![1.0; 10]; n],
+        labels: vec![String::from("1"); n],
+    }
+}
 
 #[test]
 fn combine_two_augmenters() {
@@ -65,4 +78,401 @@ fn per_sample_pipelining() {
         let expected = vec![(i as f64 + 1.0) * 2.0; 5];
         assert_eq!(&row[..], &expected[..]);
     }
-}
\ No newline at end of file
+}
+#[test]
+fn one_of_batch_and_per_sample() {
+    for per_sample in [false, true] {
+        let mut set = constant_dataset(100);
+        let one_of = OneOf::new() + Scaling::new(2.0, 2.0) + Scaling::new(3.0, 3.0);
+        let pipeline = AugmentationPipeline::new() + one_of + Crop::new(5);
+        pipeline.augment_batch(&mut set, true, per_sample);
+
+        let doubled = set.features.iter().filter(|row| row[0] == 2.0).count();
+        let tripled = set.features.iter().filter(|row| row[0] == 3.0).count();
+        assert_eq!(doubled + tripled, 100);
+        assert!(doubled > 0 && tripled > 0);
+        assert!(set.features.iter().all(|row| row == &vec![row[0]; 5]));
+    }
+}
+
+#[test]
+fn one_of_weights() {
+    let mut set = constant_dataset(200);
+    let mut one_of = OneOf::new();
+    one_of.add(Scaling::new(2.0, 2.0), 1.0);
+    one_of.add(Scaling::new(3.0, 3.0), 1e-9);
+    one_of.augment_batch(&mut set, false, false);
+
+    assert!(set.features.iter().all(|row| row[0] == 2.0));
+}
+
+#[test]
+fn one_of_batch_level_child() {
+    let mut set = constant_dataset(10);
+    let one_of = OneOf::new() + FrequencyMask::new(2, true);
+    assert!(!one_of.supports_per_sample());
+    one_of.augment_batch(&mut set, true, false);
+
+    assert!(set.features.iter().all(|row| row.len() == 10));
+}
+
+#[test]
+#[should_panic]
+fn one_of_changing_row_count() {
+    let mut set = constant_dataset(10);
+    let one_of = OneOf::new() + Repeat::new(2);
+    one_of.augment_batch(&mut set, false, false);
+}
+
+#[test]
+fn some_of_batch_and_per_sample() {
+    for per_sample in [false, true] {
+        let mut set = constant_dataset(100);
+        let some_of = SomeOf::new(2)
+            + Scaling::new(2.0, 2.0)
+            + Scaling::new(3.0, 3.0)
+            + Scaling::new(5.0, 5.0);
+        let pipeline = AugmentationPipeline::new() + some_of;
+        pipeline.augment_batch(&mut set, false, per_sample);
+
+        for row in &set.features {
+            assert!([6.0, 10.0, 15.0].contains(&row[0]));
+        }
+        assert!(set.features.iter().any(|row| row[0] != set.features[0][0]));
+    }
+}
+
+#[test]
+fn random_order_batch_and_per_sample() {
+    for per_sample in [false, true] {
+        let mut set = constant_dataset(100);
+        // Setting to 3 then doubling gives 6, doubling then setting to 3 gives 3
+        let random_order = RandomOrder::new() + Drop::new(1.0, Some(3.0)) + Scaling::new(2.0, 2.0);
+        let pipeline = AugmentationPipeline::new() + random_order;
+        pipeline.augment_batch(&mut set, true, per_sample);
+
+        let set_first = set.features.iter().filter(|row| row[0] == 6.0).count();
+        let scale_first = set.features.iter().filter(|row| row[0] == 3.0).count();
+        assert_eq!(set_first + scale_first, 100);
+        assert!(set_first > 0 && scale_first > 0);
+    }
+}
+
+fn labeled_dataset() -> Dataset {
+    Dataset {
+        features: vec![vec![0.0, 1.0, 2.0], vec![2.0, 1.0, 0.0], vec![1.0, 1.0, 1.0]],
+        labels: vec!["rising".into(), "falling".into(), "flat".into()],
+    }
+}
+
+#[test]
+fn label_conditional_batch_and_per_sample() {
+    for per_sample in [false, true] {
+        let mut set = labeled_dataset();
+        let pipeline = AugmentationPipeline::new()
+            + LabelConditional::for_labels(Scaling::new(2.0, 2.0), &["rising", "flat"]);
+        pipeline.augment_batch(&mut set, true, per_sample);
+
+        assert_eq!(set.features[0], vec![0.0, 2.0, 4.0]);
+        assert_eq!(set.features[1], vec![2.0, 1.0, 0.0]);
+        assert_eq!(set.features[2], vec![2.0, 2.0, 2.0]);
+    }
+}
+
+#[test]
+fn label_conditional_predicate() {
+    let mut set = labeled_dataset();
+    let augmenter = LabelConditional::new(Reverse::new(), |label| label == "flat");
+    assert!(augmenter.matches("flat"));
+    assert!(!augmenter.matches("rising"));
+    augmenter.augment_batch(&mut set, false, false);
+    assert_eq!(set.features, labeled_dataset().features);
+
+    let mut set = labeled_dataset();
+    let augmenter = LabelConditional::except_labels(Reverse::new(), &["rising", "falling"]);
+    augmenter.augment_batch(&mut set, false, false);
+    assert_eq!(set.features, labeled_dataset().features);
+}
+
+#[test]
+fn label_conditional_inside_one_of() {
+    let mut set = labeled_dataset();
+    let one_of = OneOf::new() + LabelConditional::for_labels(Scaling::new(3.0, 3.0), &["flat"]);
+    let pipeline = AugmentationPipeline::new() + one_of;
+    pipeline.augment_batch(&mut set, false, true);
+
+    assert_eq!(set.features[0], vec![0.0, 1.0, 2.0]);
+    assert_eq!(set.features[2], vec![3.0, 3.0, 3.0]);
+}
+
+#[test]
+fn class_pipelines() {
+    for per_sample in [false, true] {
+        let mut set = labeled_dataset();
+        let mut pipeline = AugmentationPipeline::new() + Reverse::new();
+        pipeline.set_class_pipeline("rising", AugmentationPipeline::new() + Scaling::new(2.0, 2.0));
+        pipeline.set_class_pipeline("falling", AugmentationPipeline::new());
+        pipeline.augment_batch(&mut set, false, per_sample);
+
+        assert_eq!(set.labels, labeled_dataset().labels);
+        assert_eq!(set.features[0], vec![0.0, 2.0, 4.0]);
+        assert_eq!(set.features[1], vec![2.0, 1.0, 0.0]);
+        assert_eq!(set.features[2], vec![1.0, 1.0, 1.0]);
+    }
+}
+
+#[test]
+fn class_pipeline_oversampling() {
+    let mut set = labeled_dataset();
+    let mut pipeline = AugmentationPipeline::new();
+    pipeline.add_for_labels(Scaling::new(2.0, 2.0), &["falling"]);
+    pipeline.set_class_pipeline("rising", AugmentationPipeline::new() + Repeat::new(3));
+    pipeline.augment_batch(&mut set, true, false);
+
+    assert_eq!(set.labels, vec!["falling", "flat", "rising", "rising", "rising"]);
+    assert_eq!(set.features[0], vec![4.0, 2.0, 0.0]);
+    assert_eq!(set.features[1], vec![1.0, 1.0, 1.0]);
+    assert!(set.features[2..].iter().all(|row| row == &vec![0.0, 1.0, 2.0]));
+}
+
+#[test]
+fn augment_to_new_keeps_source() {
+    let set = labeled_dataset();
+    let augmented = Scaling::new(2.0, 2.0).augment_to_new(&set, true, false);
+
+    assert_eq!(set.features, labeled_dataset().features);
+    assert_eq!(augmented.source, vec![0, 1, 2]);
+    assert_eq!(augmented.dataset.labels, set.labels);
+    assert_eq!(augmented.dataset.features[0], vec![0.0, 2.0, 4.0]);
+}
+
+#[test]
+fn augment_copies_with_original() {
+    let set = labeled_dataset();
+    let pipeline = AugmentationPipeline::new() + Scaling::new(2.0, 2.0) + Crop::new(2);
+    let augmented = pipeline.augment_copies(&set, 2, true, false, true);
+
+    assert_eq!(augmented.dataset.features.len(), 9);
+    assert_eq!(augmented.dataset.features.capacity(), 9);
+    assert_eq!(augmented.source, vec![0, 1, 2, 0, 1, 2, 0, 1, 2]);
+    for (i, row) in augmented.dataset.features.iter().enumerate() {
+        let source = augmented.source[i];
+        assert_eq!(augmented.dataset.labels[i], set.labels[source]);
+        if i < 6 {
+            assert_eq!(row.len(), 2);
+        } else {
+            assert_eq!(row, &set.features[source]);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn augment_copies_changing_row_count() {
+    let set = labeled_dataset();
+    Repeat::new(2).augment_copies(&set, 2, false, false, false);
+}
+
+fn indexed_dataset(n: usize) -> Dataset {
+    Dataset {
+        features: (0..n).map(|i| vec![i as f64; 4]).collect(),
+        labels: (0..n).map(|i| i.to_string()).collect(),
+    }
+}
+
+#[test]
+fn stream_covers_every_epoch() {
+    let set = indexed_dataset(10);
+    let pipeline = AugmentationPipeline::new() + Scaling::new(2.0, 2.0);
+    let config = StreamConfig {
+        batch_size: 3,
+        seed: Some(7),
+        epochs: Some(3),
+        prefetch: 2,
+        ..Default::default()
+    };
+
+    let batches: Vec<_> = thread::scope(|scope| pipeline.stream(scope, &set, config).collect());
+    assert_eq!(batches.len(), 12);
+    for epoch in 0..3 {
+        let mut seen: Vec<usize> = batches
+            .iter()
+            .filter(|batch| batch.epoch == epoch)
+            .flat_map(|batch| batch.source.clone())
+            .collect();
+        seen.sort();
+        assert_eq!(seen, (0..10).collect::<Vec<_>>());
+    }
+    for batch in &batches {
+        for (i, &source) in batch.source.iter().enumerate() {
+            assert_eq!(batch.dataset.features[i], vec![2.0 * source as f64; 4]);
+            assert_eq!(batch.dataset.labels[i], source.to_string());
+        }
+    }
+    // The streamed dataset is left untouched
+    assert_eq!(set.features, indexed_dataset(10).features);
+}
+
+#[test]
+fn stream_seeded_order() {
+    let set = indexed_dataset(20);
+    let pipeline = AugmentationPipeline::new();
+    let config = StreamConfig {
+        batch_size: 8,
+        seed: Some(1),
+        drop_last: true,
+        ..Default::default()
+    };
+
+    let order = |config: StreamConfig| -> Vec<Vec<usize>> {
+        thread::scope(|scope| {
+            pipeline
+                .stream(scope, &set, config)
+                .take(6)
+                .map(|batch| batch.source)
+                .collect()
+        })
+    };
+    let first = order(config.clone());
+    assert_eq!(first, order(config.clone()));
+    assert!(first.iter().all(|rows| rows.len() == 8));
+    // Every epoch has its own order, which is not the first one of another seed
+    assert_ne!(first[0], first[2]);
+    let other_seed = order(StreamConfig {
+        seed: Some(2),
+        ..config.clone()
+    });
+    assert_ne!(other_seed[0], first[2]);
+
+    let unshuffled = order(StreamConfig {
+        shuffle: false,
+        ..config
+    });
+    assert_eq!(unshuffled[0], (0..8).collect::<Vec<_>>());
+    assert_eq!(unshuffled[2], (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_seeded_augmentations() {
+    let set = indexed_dataset(20);
+    let pipeline = AugmentationPipeline::new() + Jittering::new(0.1) + Scaling::new(0.5, 2.0);
+    let config = StreamConfig {
+        batch_size: 6,
+        seed: Some(3),
+        prefetch: 3,
+        ..Default::default()
+    };
+
+    // The stream is infinite, the background thread stops when the iterator is dropped
+    let features = |config: StreamConfig| -> Vec<Vec<Vec<f64>>> {
+        thread::scope(|scope| {
+            pipeline
+                .stream(scope, &set, config)
+                .take(10)
+                .map(|batch| batch.dataset.features)
+                .collect()
+        })
+    };
+    let first = features(config.clone());
+    assert_eq!(first, features(config.clone()));
+    assert_ne!(first[0], first[4]);
+    assert_ne!(
+        first,
+        features(StreamConfig {
+            seed: Some(4),
+            ..config
+        })
+    );
+}
+
+fn ramp_dataset(n: usize) -> Dataset {
+    Dataset {
+        features: (0..n)
+            .map(|i| (0..50).map(|t| (100 * i + t) as f64).collect())
+            .collect(),
+        labels: vec![String::from("1"); n],
+    }
+}
+
+#[test]
+fn provenance_records_parameters() {
+    for per_sample in [false, true] {
+        let mut set = ramp_dataset(6);
+        let mut scaling = Scaling::new(0.5, 2.0);
+        scaling.set_probability(0.0);
+        let pipeline = AugmentationPipeline::new() + Crop::new(10) + scaling;
+        let provenance = pipeline.augment_batch_with_provenance(&mut set, true, per_sample);
+
+        assert_eq!(provenance.rows.len(), 6);
+        for (i, row) in provenance.rows.iter().enumerate() {
+            assert_eq!(row.source, i);
+            assert_eq!(row.steps.len(), 2);
+            let crop = &row.steps[0];
+            assert_eq!(crop.augmenter, "Crop");
+            assert!(crop.applied);
+            let start = set.features[i][0] as i64 - 100 * i as i64;
+            assert_eq!(crop.params, vec![("start".to_string(), Value::Integer(start))]);
+            // Skipped because of the probability, without drawing parameters
+            assert!(!row.steps[1].applied);
+            assert!(row.steps[1].params.is_empty());
+        }
+    }
+
+    // The order of the segments and the dropped values reproduce the augmented series
+    let mut set = ramp_dataset(4);
+    let pipeline = AugmentationPipeline::new() + Permutate::new(25, 5) + Drop::new(0.3, Some(-1.0));
+    let provenance = pipeline.augment_batch_with_provenance(&mut set, true, false);
+    for (i, row) in provenance.rows.iter().enumerate() {
+        let Value::List(order) = &row.steps[0].params[0].1 else { panic!() };
+        let Value::List(indices) = &row.steps[1].params[0].1 else { panic!() };
+        assert_eq!(order.len(), 10);
+        let mut expected: Vec<f64> = order
+            .iter()
+            .flat_map(|&s| (0..5).map(move |t| (100 * i + 5 * s as usize + t) as f64))
+            .collect();
+        for &t in indices {
+            expected[t as usize] = -1.0;
+        }
+        assert_eq!(set.features[i], expected);
+    }
+
+    // A custom kernel has its own size, so no size is drawn
+    let mut set = ramp_dataset(2);
+    let custom = Convolve::new(ConvolveWindow::Custom(vec![0.5, 0.5]), 5);
+    let pipeline = AugmentationPipeline::new() + custom;
+    let provenance = pipeline.augment_batch_with_provenance(&mut set, false, true);
+    for row in &provenance.rows {
+        assert!(row.steps[0].applied);
+        assert!(row.steps[0].params.is_empty());
+    }
+}
+
+#[test]
+fn provenance_nested_steps() {
+    let mut set = ramp_dataset(4);
+    let pipeline = AugmentationPipeline::new() + (OneOf::new() + Scaling::new(2.0, 2.0));
+    let provenance = pipeline.augment_batch_with_provenance(&mut set, false, true);
+
+    for row in &provenance.rows {
+        let names: Vec<&str> = row.steps.iter().map(|s| s.augmenter.as_str()).collect();
+        assert_eq!(names, vec!["OneOf", "Scaling"]);
+        assert_eq!(row.applied("Scaling").next().unwrap().params[0].1, Value::Number(2.0));
+    }
+    let json = provenance.to_json();
+    assert!(json.contains("\"source\": 3"));
+    assert!(json.contains("\"factor\": 2"));
+}
+
+#[test]
+fn provenance_follows_class_pipelines() {
+    let mut set = labeled_dataset();
+    let mut pipeline = AugmentationPipeline::new();
+    pipeline.add_for_labels(Scaling::new(2.0, 2.0), &["falling"]);
+    pipeline.set_class_pipeline("rising", AugmentationPipeline::new() + Repeat::new(3));
+    let provenance = pipeline.augment_batch_with_provenance(&mut set, true, false);
+
+    let sources: Vec<usize> = provenance.rows.iter().map(|row| row.source).collect();
+    assert_eq!(sources, vec![1, 2, 0, 0, 0]);
+    assert_eq!(provenance.rows[0].applied("Scaling").count(), 1);
+    assert!(provenance.rows[1..].iter().all(|row| row.applied("Scaling").count() == 0));
+}
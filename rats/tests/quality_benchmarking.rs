@@ -0,0 +1,568 @@
+mod common;
+
+use common::{make_dataset, make_signal};
+use rand::RngCore;
+use rats_rs::{Dataset, random};
+use rats_rs::augmenters::{AugmentationPipeline, Crop, Jittering, Repeat, Scaling};
+use rats_rs::quality_benchmarking::knn::{KnnClassifier, evaluate_augmentation};
+use rats_rs::quality_benchmarking::report::{ReportConfig, Summary, generate_report};
+use rats_rs::quality_benchmarking::{
+    DistanceMetric, DtwConstraint, LocalCost, acf_distance, distance_matrix, diversity, dtw,
+    dtw_constrained, dtw_dependent, dtw_distance, dtw_independent, dtw_with_cost, ks_test,
+    lb_keogh, lb_kim, mmd_rbf, pairwise_distance_matrix, per_timestep_ks, psd_distance, soft_dtw,
+    soft_dtw_gradient, spectral_ks,
+};
+use std::sync::Arc;
+
+fn is_valid_path(path: &[(usize, usize)], n: usize, m: usize) -> bool {
+    path.first() == Some(&(0, 0))
+        && path.last() == Some(&(n - 1, m - 1))
+        && path.windows(2).all(|w| {
+            let (di, dj) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+            di <= 1 && dj <= 1 && di + dj > 0
+        })
+}
+
+#[test]
+fn dtw_identical_series() {
+    let a = make_signal(50, 0.0);
+    let (distance, path) = dtw(&a, &a);
+    assert_eq!(distance, 0.0);
+    assert_eq!(path, (0..50).map(|i| (i, i)).collect::<Vec<_>>());
+}
+
+#[test]
+fn dtw_distance_matches_full_dtw() {
+    let a = make_signal(60, 0.0);
+    for (len, phase) in [(60, 0.5), (45, 1.0), (80, 2.0)] {
+        let b = make_signal(len, phase);
+        for constraint in [
+            DtwConstraint::None,
+            DtwConstraint::SakoeChiba(3),
+            DtwConstraint::Itakura(2.0),
+        ] {
+            let (full, path) = dtw_constrained(&a, &b, constraint);
+            let fast = dtw_distance(&a, &b, constraint, None);
+            assert!(
+                (full - fast).abs() < 1e-9,
+                "{:?}: {} vs {}",
+                constraint,
+                full,
+                fast
+            );
+            assert!(is_valid_path(&path, a.len(), b.len()));
+        }
+    }
+}
+
+#[test]
+fn dtw_constraints_bound_unconstrained() {
+    let a = make_signal(60, 0.0);
+    let b = make_signal(70, 1.5);
+    let unconstrained = dtw(&a, &b).0;
+
+    // Narrower bands can only increase the distance
+    let mut previous = f64::INFINITY;
+    for radius in [0, 2, 5, 10] {
+        let distance = dtw_distance(&a, &b, DtwConstraint::SakoeChiba(radius), None);
+        assert!(distance.is_finite());
+        assert!(distance <= previous + 1e-12);
+        assert!(distance >= unconstrained - 1e-12);
+        previous = distance;
+    }
+    let wide = dtw_distance(&a, &b, DtwConstraint::SakoeChiba(100), None);
+    assert!((wide - unconstrained).abs() < 1e-12);
+
+    let itakura = dtw_distance(&a, &b, DtwConstraint::Itakura(1.5), None);
+    assert!(itakura.is_finite());
+    assert!(itakura >= unconstrained - 1e-12);
+}
+
+#[test]
+fn dtw_sakoe_chiba_zero_radius_is_pointwise() {
+    let a = make_signal(40, 0.0);
+    let b = make_signal(40, 0.8);
+    let expected: f64 = a.iter().zip(&b).map(|(x, y)| (x - y).abs()).sum::<f64>() / 40.0;
+    let (distance, path) = dtw_constrained(&a, &b, DtwConstraint::SakoeChiba(0));
+    assert!((distance - expected).abs() < 1e-12);
+    assert_eq!(path, (0..40).map(|i| (i, i)).collect::<Vec<_>>());
+}
+
+#[test]
+fn dtw_itakura_very_different_lengths() {
+    let a = make_signal(10, 0.0);
+    let b = make_signal(55, 0.0);
+    let (distance, path) = dtw_constrained(&a, &b, DtwConstraint::Itakura(2.0));
+    assert!(distance.is_finite());
+    assert!(is_valid_path(&path, a.len(), b.len()));
+}
+
+#[test]
+fn dtw_distance_early_abandoning() {
+    let a = make_signal(50, 0.0);
+    let b = make_signal(50, 2.0);
+    let exact = dtw_distance(&a, &b, DtwConstraint::None, None);
+    assert_eq!(
+        dtw_distance(&a, &b, DtwConstraint::None, Some(exact * 2.0)),
+        exact
+    );
+    assert_eq!(
+        dtw_distance(&a, &b, DtwConstraint::None, Some(exact * 0.5)),
+        f64::INFINITY
+    );
+}
+
+#[test]
+fn lower_bounds_below_dtw() {
+    let a = make_signal(50, 0.0);
+    for phase in [0.3, 1.0, 2.5] {
+        let b = make_signal(50, phase);
+        let unconstrained = dtw(&a, &b).0;
+        assert!(lb_kim(&a, &b) <= unconstrained + 1e-12);
+        for radius in [0, 3, 10] {
+            let constrained = dtw_distance(&a, &b, DtwConstraint::SakoeChiba(radius), None);
+            assert!(lb_keogh(&a, &b, radius) <= constrained + 1e-12);
+        }
+    }
+    assert_eq!(lb_keogh(&a, &a, 2), 0.0);
+
+    // Single precision series give the same bounds up to rounding
+    let b = make_signal(50, 1.0);
+    let (x, y): (Vec<f32>, Vec<f32>) = a.iter().zip(&b).map(|(&u, &v)| (u as f32, v as f32)).unzip();
+    assert!((lb_kim(&x, &y) - lb_kim(&a, &b)).abs() < 1e-5);
+    assert!((lb_keogh(&x, &y, 3) - lb_keogh(&a, &b, 3)).abs() < 1e-5);
+}
+
+#[test]
+fn distance_metrics() {
+    let a = vec![1.0, 2.0, 3.0, 4.0];
+    let b = vec![2.0, 4.0, 6.0, 8.0];
+    let c = vec![4.0, 3.0, 2.0, 1.0];
+    assert!((DistanceMetric::Euclidean.distance(&a, &b) - 30f64.sqrt()).abs() < 1e-12);
+    assert!(DistanceMetric::Correlation.distance(&a, &b).abs() < 1e-12);
+    assert!((DistanceMetric::Correlation.distance(&a, &c) - 2.0).abs() < 1e-12);
+    assert_eq!(DistanceMetric::Correlation.distance(&a, &[1.0; 4]), 1.0);
+    assert_eq!(
+        DistanceMetric::Dtw(DtwConstraint::None).distance(&a, &b),
+        dtw(&a, &b).0
+    );
+}
+
+#[test]
+fn distance_matrix_shape_and_values() {
+    let a = make_dataset(5, 30);
+    let b = make_dataset(3, 30);
+    for metric in [
+        DistanceMetric::Euclidean,
+        DistanceMetric::Dtw(DtwConstraint::SakoeChiba(2)),
+        DistanceMetric::Correlation,
+    ] {
+        let parallel = distance_matrix(&a, &b, metric, true);
+        let sequential = distance_matrix(&a, &b, metric, false);
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel.len(), 5);
+        for (i, row) in parallel.iter().enumerate() {
+            assert_eq!(row.len(), 3);
+            for (j, &d) in row.iter().enumerate() {
+                assert_eq!(d, metric.distance(&a.features[i], &b.features[j]));
+            }
+        }
+    }
+}
+
+#[test]
+fn pairwise_distance_matrix_symmetric() {
+    let dataset = make_dataset(6, 25);
+    let metric = DistanceMetric::Euclidean;
+    let matrix = pairwise_distance_matrix(&dataset, metric, true);
+    assert_eq!(matrix, pairwise_distance_matrix(&dataset, metric, false));
+    assert_eq!(matrix, distance_matrix(&dataset, &dataset, metric, false));
+    for (i, row) in matrix.iter().enumerate() {
+        assert_eq!(row[i], 0.0);
+    }
+}
+
+#[test]
+fn soft_dtw_approaches_hard_dtw() {
+    // Hard DTW with squared cost of these sequences is 0, reached by repeating the 2
+    let a = vec![1.0, 2.0, 3.0];
+    let b = vec![1.0, 2.0, 2.0, 3.0];
+    assert!(soft_dtw(&a, &b, 1e-4).abs() < 1e-2);
+
+    // Larger smoothing lowers the value
+    let c = make_signal(30, 0.0);
+    let d = make_signal(30, 1.0);
+    assert!(soft_dtw(&c, &d, 1.0) < soft_dtw(&c, &d, 0.1));
+    assert!(soft_dtw(&c, &d, 0.1) < soft_dtw(&c, &d, 0.01));
+
+    let (x, y): (Vec<f32>, Vec<f32>) = c.iter().zip(&d).map(|(&u, &v)| (u as f32, v as f32)).unzip();
+    assert!((soft_dtw(&x, &y, 0.1) - soft_dtw(&c, &d, 0.1)).abs() < 1e-4);
+}
+
+#[test]
+fn soft_dtw_gradient_matches_finite_differences() {
+    let a = make_signal(12, 0.0);
+    let b = make_signal(15, 0.9);
+    let gamma = 0.5;
+    let (distance, gradient) = soft_dtw_gradient(&a, &b, gamma);
+    assert!((distance - soft_dtw(&a, &b, gamma)).abs() < 1e-12);
+
+    let eps = 1e-6;
+    for (i, g) in gradient.iter().enumerate() {
+        let mut plus = a.clone();
+        plus[i] += eps;
+        let mut minus = a.clone();
+        minus[i] -= eps;
+        let numeric = (soft_dtw(&plus, &b, gamma) - soft_dtw(&minus, &b, gamma)) / (2.0 * eps);
+        assert!((g - numeric).abs() < 1e-5, "{}: {} vs {}", i, g, numeric);
+    }
+}
+
+#[test]
+fn dtw_with_cost_variants() {
+    let a = make_signal(30, 0.0);
+    let b = make_signal(35, 0.6);
+    let (absolute, path) = dtw_with_cost(&a, &b, &LocalCost::Absolute, DtwConstraint::None);
+    assert_eq!((absolute, path), dtw(&a, &b));
+
+    let euclidean = dtw_with_cost(&a, &b, &LocalCost::Euclidean, DtwConstraint::None).0;
+    assert!((euclidean - absolute).abs() < 1e-12);
+
+    let custom = LocalCost::Custom(Arc::new(|x, y| 2.0 * (x[0] - y[0]).abs()));
+    let doubled = dtw_with_cost(&a, &b, &custom, DtwConstraint::None).0;
+    assert!((doubled - 2.0 * absolute).abs() < 1e-12);
+}
+
+#[test]
+fn multivariate_dtw_single_channel_matches_univariate() {
+    let a = make_signal(30, 0.0);
+    let b = make_signal(25, 1.2);
+    let constraint = DtwConstraint::SakoeChiba(4);
+    let expected = dtw_constrained(&a, &b, constraint);
+    let channels_a = vec![a.clone()];
+    let channels_b = vec![b.clone()];
+    assert_eq!(
+        dtw_dependent(&channels_a, &channels_b, &LocalCost::Absolute, constraint),
+        expected
+    );
+    let independent = dtw_independent(&channels_a, &channels_b, &LocalCost::Absolute, constraint);
+    assert!((independent - expected.0).abs() < 1e-12);
+}
+
+#[test]
+fn multivariate_dtw_dependent_vs_independent() {
+    // The second channel is shifted in time, so only independent warping aligns both channels
+    let a = vec![make_signal(40, 0.0), make_signal(40, 0.0)];
+    let mut shifted = make_signal(40, 0.0);
+    shifted.rotate_right(3);
+    let b = vec![make_signal(40, 0.0), shifted];
+
+    let dependent = dtw_dependent(&a, &b, &LocalCost::Absolute, DtwConstraint::None).0;
+    let independent = dtw_independent(&a, &b, &LocalCost::Absolute, DtwConstraint::None);
+    let per_channel: f64 = a.iter().zip(&b).map(|(x, y)| dtw(x, y).0).sum();
+    assert!((independent - per_channel).abs() < 1e-12);
+    assert!(independent <= dependent + 1e-12);
+    assert!(independent < dependent);
+}
+
+fn scaled(dataset: &Dataset, factor: f64, offset: f64) -> Dataset {
+    Dataset {
+        features: dataset
+            .features
+            .iter()
+            .map(|x| x.iter().map(|v| v * factor + offset).collect())
+            .collect(),
+        labels: dataset.labels.clone(),
+    }
+}
+
+#[test]
+fn ks_test_statistic() {
+    let x: Vec<f64> = (0..50).map(|i| i as f64).collect();
+    let same = ks_test(&x, &x);
+    assert_eq!(same.statistic, 0.0);
+    assert_eq!(same.p_value, 1.0);
+
+    let shifted: Vec<f64> = x.iter().map(|v| v + 25.0).collect();
+    let result = ks_test(&x, &shifted);
+    assert!((result.statistic - 0.5).abs() < 1e-12);
+    assert!(result.p_value < 1e-3);
+
+    // Interleaved samples of the same distribution
+    let odd: Vec<f64> = (0..50).map(|i| (2 * i + 1) as f64).collect();
+    let even: Vec<f64> = (0..50).map(|i| (2 * i) as f64).collect();
+    assert!(ks_test(&odd, &even).p_value > 0.9);
+}
+
+#[test]
+fn distribution_metrics_identical_datasets() {
+    let dataset = make_dataset(20, 32);
+    assert_eq!(mmd_rbf(&dataset, &dataset, None, true), 0.0);
+    assert!(
+        per_timestep_ks(&dataset, &dataset, true)
+            .iter()
+            .all(|r| r.statistic == 0.0)
+    );
+    let spectral = spectral_ks(&dataset, &dataset, false);
+    assert_eq!(spectral.len(), 17);
+    assert!(spectral.iter().all(|r| r.statistic == 0.0));
+    assert_eq!(acf_distance(&dataset, &dataset, 5, true), 0.0);
+    assert!(psd_distance(&dataset, &dataset, true).abs() < 1e-12);
+}
+
+#[test]
+fn distribution_metrics_detect_changes() {
+    let dataset = make_dataset(20, 32);
+    let slightly = scaled(&dataset, 1.0, 0.1);
+    let strongly = scaled(&dataset, 1.0, 3.0);
+
+    let mmd_slight = mmd_rbf(&dataset, &slightly, Some(1.0), false);
+    let mmd_strong = mmd_rbf(&dataset, &strongly, Some(1.0), true);
+    assert!(mmd_slight > 0.0 && mmd_slight < mmd_strong);
+
+    let ks = per_timestep_ks(&dataset, &strongly, true);
+    assert_eq!(ks.len(), 32);
+    assert!(ks.iter().all(|r| r.statistic == 1.0 && r.p_value < 1e-6));
+
+    // Scaling and offsets keep the autocorrelation, adding an alternating component does not
+    assert!(acf_distance(&dataset, &scaled(&dataset, 2.0, 1.0), 5, false) < 1e-12);
+    let noisy = Dataset {
+        features: dataset
+            .features
+            .iter()
+            .map(|x| {
+                x.iter()
+                    .enumerate()
+                    .map(|(i, v)| v + if i % 2 == 0 { 1.0 } else { -1.0 })
+                    .collect()
+            })
+            .collect(),
+        labels: dataset.labels.clone(),
+    };
+    assert!(acf_distance(&dataset, &noisy, 5, true) > 0.1);
+    assert!(psd_distance(&dataset, &noisy, false) > 0.1);
+    assert!(psd_distance(&dataset, &scaled(&dataset, 2.0, 0.0), false) < 1e-12);
+}
+
+#[test]
+fn distribution_metrics_empty_dataset() {
+    let dataset = make_dataset(5, 16);
+    let empty = Dataset {
+        features: Vec::new(),
+        labels: Vec::new(),
+    };
+    assert_eq!(acf_distance(&dataset, &empty, 5, false), 0.0);
+    assert_eq!(acf_distance(&empty, &dataset, 5, true), 0.0);
+    assert_eq!(acf_distance(&empty, &empty, 5, false), 0.0);
+    assert_eq!(psd_distance(&dataset, &empty, false), 0.0);
+}
+
+#[test]
+fn diversity_score() {
+    let dataset = make_dataset(10, 30);
+    let metric = DistanceMetric::Euclidean;
+    let score = diversity(&dataset, metric, true);
+    let matrix = pairwise_distance_matrix(&dataset, metric, false);
+    let mean = matrix.iter().flatten().sum::<f64>() / 90.0;
+    assert!((score - mean).abs() < 1e-12);
+
+    let collapsed = Dataset {
+        features: vec![dataset.features[0].clone(); 10],
+        labels: dataset.labels.clone(),
+    };
+    assert_eq!(diversity(&collapsed, metric, false), 0.0);
+}
+
+fn make_classes(n: usize, len: usize, offset: f64) -> Dataset {
+    // Class "slow" and "fast" oscillations with varying phase
+    let features = (0..n)
+        .map(|i| {
+            let frequency = if i % 2 == 0 { 0.1 } else { 0.4 };
+            let phase = i as f64 * 0.37 + offset;
+            (0..len)
+                .map(|t| (t as f64 * frequency + phase).sin())
+                .collect()
+        })
+        .collect();
+    let labels = (0..n)
+        .map(|i| if i % 2 == 0 { "slow" } else { "fast" }.to_string())
+        .collect();
+    Dataset { features, labels }
+}
+
+#[test]
+fn knn_neighbors_match_exhaustive_search() {
+    let train = make_classes(20, 40, 0.0);
+    let test = make_classes(6, 40, 0.5);
+    for metric in [
+        DistanceMetric::Euclidean,
+        DistanceMetric::Dtw(DtwConstraint::None),
+        DistanceMetric::Dtw(DtwConstraint::SakoeChiba(3)),
+    ] {
+        let classifier = KnnClassifier::new(3, metric);
+        for x in &test.features {
+            let mut expected: Vec<(usize, f64)> = train
+                .features
+                .iter()
+                .enumerate()
+                .map(|(i, y)| (i, metric.distance(x, y)))
+                .collect();
+            expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+            expected.truncate(3);
+            let neighbors = classifier.neighbors(&train, x);
+            assert_eq!(neighbors.len(), 3);
+            for (found, wanted) in neighbors.iter().zip(&expected) {
+                assert!((found.1 - wanted.1).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn knn_classifies_and_votes() {
+    let train = make_classes(20, 40, 0.0);
+    let test = make_classes(10, 40, 0.2);
+    let classifier = KnnClassifier::new(1, DistanceMetric::Dtw(DtwConstraint::SakoeChiba(4)));
+    assert_eq!(classifier.accuracy(&train, &test, true), 1.0);
+    let predictions = classifier.predict_batch(&train, &test, false);
+    assert!(predictions.iter().zip(&test.labels).all(|(p, l)| p.as_ref() == Some(l)));
+
+    // Two of three neighbors vote for "b", although the nearest one is "a"
+    let train = Dataset {
+        features: vec![vec![0.0], vec![0.2], vec![0.3], vec![5.0]],
+        labels: ["a", "b", "b", "a"].iter().map(|l| l.to_string()).collect(),
+    };
+    let classifier = KnnClassifier::new(3, DistanceMetric::Euclidean);
+    assert_eq!(classifier.predict(&train, &[0.0]).as_deref(), Some("b"));
+    // With a tie, the label of the nearest neighbor wins
+    let classifier = KnnClassifier::new(2, DistanceMetric::Euclidean);
+    assert_eq!(classifier.predict(&train, &[0.0]).as_deref(), Some("a"));
+}
+
+#[test]
+fn knn_without_finite_neighbor() {
+    let train = make_classes(5, 20, 0.0);
+    let mut test = make_classes(4, 30, 0.0);
+    // DTW cannot align an empty series, the band is widened for the other lengths
+    test.features[0].clear();
+    let classifier = KnnClassifier::new(1, DistanceMetric::Dtw(DtwConstraint::SakoeChiba(2)));
+    assert_eq!(classifier.predict(&train, &test.features[0]), None);
+    let predictions = classifier.predict_batch(&train, &test, true);
+    assert!(predictions[0].is_none() && predictions[1..].iter().all(Option::is_some));
+    assert!(classifier.accuracy(&train, &test, false) <= 0.75);
+
+    let classifier = KnnClassifier::new(1, DistanceMetric::Euclidean);
+    assert_eq!(classifier.predict(&train, &[f64::NAN; 20]), None);
+}
+
+#[test]
+fn knn_evaluate_augmentation() {
+    let train = make_classes(10, 40, 0.0);
+    let test = make_classes(10, 40, 0.2);
+    let classifier = KnnClassifier::new(1, DistanceMetric::Euclidean);
+
+    let pipeline = AugmentationPipeline::new() + Jittering::new(0.0);
+    let evaluation = evaluate_augmentation(&train, &test, &pipeline, &classifier, &[1, 2, 3], false, true);
+    assert_eq!(
+        evaluation.augmented_accuracies,
+        vec![evaluation.baseline_accuracy; 3]
+    );
+    assert_eq!(evaluation.improvement(), 0.0);
+    assert_eq!(evaluation.std_augmented_accuracy(), 0.0);
+
+    let pipeline = AugmentationPipeline::new() + Jittering::new(0.1);
+    let seeds = [1, 2, 3, 4];
+    let evaluation = evaluate_augmentation(&train, &test, &pipeline, &classifier, &seeds, true, false);
+    assert_eq!(evaluation.augmented_accuracies.len(), 4);
+    // The same seeds give the same accuracies, sequentially and in parallel
+    let again = evaluate_augmentation(&train, &test, &pipeline, &classifier, &seeds, true, true);
+    assert_eq!(again.augmented_accuracies, evaluation.augmented_accuracies);
+    assert!(
+        evaluation
+            .augmented_accuracies
+            .iter()
+            .all(|&a| (0.0..=1.0).contains(&a))
+    );
+
+    // The seeded generator of the caller continues where it was
+    random::set_seed(Some(9));
+    let draws = [random::rng().next_u64(), random::rng().next_u64()];
+    random::set_seed(Some(9));
+    assert_eq!(random::rng().next_u64(), draws[0]);
+    evaluate_augmentation(&train, &test, &pipeline, &classifier, &seeds, true, true);
+    assert_eq!(random::rng().next_u64(), draws[1]);
+    assert_eq!(random::seed(), Some(9));
+    random::set_seed(None);
+}
+
+#[test]
+fn report_summary() {
+    let summary = Summary::new(&[3.0, 1.0, 4.0, 2.0]);
+    assert_eq!(summary.mean, 2.5);
+    assert_eq!(summary.median, 2.5);
+    assert_eq!(summary.min, 1.0);
+    assert_eq!(summary.max, 4.0);
+    assert!((summary.std - 1.25f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn report_per_augmenter() {
+    let original = make_classes(12, 40, 0.0);
+    let pipeline = AugmentationPipeline::new()
+        + Jittering::new(0.0)
+        + Scaling::new(2.0, 2.0)
+        + Repeat::new(2)
+        + Crop::new(30);
+    let report = generate_report(&original, &pipeline, &ReportConfig::default());
+
+    let names: Vec<&str> = report.augmenters.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names.len(), 5);
+    assert_eq!(names[4], pipeline.name);
+
+    // Jittering without noise changes nothing
+    let identity = &report.augmenters[0];
+    assert_eq!(identity.dtw.max, 0.0);
+    assert_eq!(identity.mean_shift.max, 0.0);
+    assert_eq!(identity.label_preservation, 1.0);
+    assert!(identity.spectral_change.unwrap().abs() < 1e-12);
+
+    // Doubling the values increases the standard deviation of every series
+    let scaling = &report.augmenters[1];
+    assert!(scaling.dtw.min > 0.0);
+    assert!(scaling.std_shift.min > 0.0);
+
+    assert_eq!(report.augmenters[2].n_samples, 24);
+    // Cropping changes the length, so there is no spectral comparison
+    assert!(report.augmenters[3].spectral_change.is_none());
+    assert_eq!(report.augmenters[3].example_augmented.len(), 30);
+    assert_eq!(report.augmenters[4].n_samples, 24);
+}
+
+#[test]
+fn report_json_and_html() {
+    let original = make_classes(6, 30, 0.0);
+    let pipeline = AugmentationPipeline::new() + Jittering::new(0.1) + Crop::new(20);
+    let report = generate_report(&original, &pipeline, &ReportConfig::default());
+
+    let json = report.to_json();
+    assert!(json.starts_with('{') && json.trim_end().ends_with('}'));
+    assert!(json.contains("\"name\": \"Jittering\""));
+    assert!(json.contains("\"spectral_change\": null"));
+    assert_eq!(json.matches('{').count(), json.matches('}').count());
+    assert_eq!(json.matches('[').count(), json.matches(']').count());
+
+    let html = report.to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert_eq!(html.matches("<svg").count(), 2 * report.augmenters.len());
+    assert_eq!(html.matches("<svg").count(), html.matches("</svg>").count());
+
+    let dir = std::env::temp_dir().join("rats_report_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    report.write_json(dir.join("report.json")).unwrap();
+    report.write_html(dir.join("report.html")).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("report.json")).unwrap(),
+        json
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
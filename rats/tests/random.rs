@@ -0,0 +1,79 @@
+use rats_rs::Dataset;
+use rats_rs::augmenters::{
+    AddNoise, AugmentationPipeline, Augmenter, Crop, Jittering, NoiseType, RandomTimeWarpAugmenter,
+    Scaling, SomeOf,
+};
+use rats_rs::random::{seed, set_seed, with_seed};
+use std::thread;
+
+fn make_pipeline() -> AugmentationPipeline {
+    AugmentationPipeline::new()
+        + Crop::new(40)
+        + Jittering::new(0.1)
+        + (SomeOf::new(1) + Scaling::new(0.5, 2.0) + RandomTimeWarpAugmenter::new(5, (0.5, 2.0)))
+        + AddNoise::new(NoiseType::Spike, Some((-1.0, 1.0)), None, None)
+}
+
+fn augment_twice(parallel: bool, per_sample: bool) -> Vec<Vec<f64>> {
+    let mut set = Dataset {
+        features: (0..50)
+            .map(|i| (0..64).map(|t| (t as f64 * 0.1 * i as f64).sin()).collect())
+            .collect(),
+        labels: vec![String::from("1"); 50],
+    };
+    let pipeline = make_pipeline();
+    pipeline.augment_batch(&mut set, parallel, per_sample);
+    pipeline.augment_batch(&mut set, parallel, per_sample);
+    set.features
+}
+
+fn augment(seed: Option<u64>, parallel: bool, per_sample: bool) -> Vec<Vec<f64>> {
+    set_seed(seed);
+    augment_twice(parallel, per_sample)
+}
+
+#[test]
+fn seeded_augmentation_is_reproducible() {
+    for per_sample in [false, true] {
+        let sequential = augment(Some(42), false, per_sample);
+        assert_eq!(seed(), Some(42));
+        assert_eq!(augment(Some(42), false, per_sample), sequential);
+        assert_eq!(augment(Some(42), true, per_sample), sequential);
+        assert_ne!(augment(Some(43), false, per_sample), sequential);
+        assert_ne!(augment(None, false, per_sample), sequential);
+    }
+    assert_eq!(seed(), None);
+}
+
+#[test]
+fn seed_belongs_to_the_thread() {
+    set_seed(Some(7));
+    let expected = augment_twice(true, false);
+
+    set_seed(Some(7));
+    let other = thread::spawn(|| {
+        assert_eq!(seed(), None);
+        set_seed(Some(8));
+        augment_twice(true, false)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(seed(), Some(7));
+    assert_eq!(augment_twice(true, false), expected);
+    assert_ne!(other, expected);
+}
+
+#[test]
+fn with_seed_restores_the_generator() {
+    set_seed(Some(1));
+    let first = augment_twice(false, false);
+    let second = augment_twice(false, false);
+
+    set_seed(Some(1));
+    assert_eq!(augment_twice(false, false), first);
+    let scoped = with_seed(2, || augment_twice(false, false));
+    // The scope neither advances nor rewinds the seeded generator of the thread
+    assert_eq!(augment_twice(false, false), second);
+    assert_eq!(seed(), Some(1));
+    assert_eq!(with_seed(2, || augment_twice(true, false)), scoped);
+}
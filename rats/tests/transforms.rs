@@ -0,0 +1,359 @@
+mod common;
+
+use common::make_signal;
+use rats_rs::Dataset;
+use rats_rs::transforms::accuracy::compare_datasets_within_tolerance;
+use rats_rs::transforms::dct::{dataset_dct, dataset_idct};
+use rats_rs::transforms::fastfourier::{dataset_fft, dataset_ifft};
+use rats_rs::transforms::filter::{Filter, FilterBand, dataset_filtfilt};
+use rats_rs::transforms::stft::{
+    StftParams, WindowFunction, dataset_istft, dataset_stft, istft, stft,
+};
+use rats_rs::transforms::wavelet::{
+    Wavelet, dataset_wavedec, dataset_waverec, dwt, idwt, max_level, wavedec, waverec,
+};
+
+#[test]
+fn stft_shape() {
+    let series = make_signal(100, 0.0);
+    let params = StftParams::new(16, 4, WindowFunction::Hann, true);
+
+    let spectrogram = stft(&series, &params);
+
+    assert_eq!(spectrogram.n_bins(), 9);
+    assert_eq!(spectrogram.n_frames(), 1 + 100 / 4);
+    assert!(spectrogram.frames.iter().all(|f| f.len() == 9));
+}
+
+#[test]
+fn stft_istft_roundtrip_windows() {
+    let series = make_signal(203, 0.0);
+    for window in [
+        WindowFunction::Rectangular,
+        WindowFunction::Hann,
+        WindowFunction::Hamming,
+        WindowFunction::Blackman,
+    ] {
+        for hop in [1, 4, 8] {
+            let params = StftParams::new(32, hop, window, true);
+            let reconstructed = istft(&stft(&series, &params));
+
+            assert_eq!(reconstructed.len(), series.len());
+            for (a, b) in series.iter().zip(reconstructed.iter()) {
+                assert!((a - b).abs() < 1e-9, "{:?} hop {}: {} != {}", window, hop, a, b);
+            }
+        }
+    }
+}
+
+#[test]
+fn stft_istft_roundtrip_not_centered() {
+    let series = make_signal(100, 0.0);
+    let params = StftParams::new(16, 4, WindowFunction::Hamming, false);
+
+    let reconstructed = istft(&stft(&series, &params));
+
+    assert_eq!(reconstructed.len(), series.len());
+    for (a, b) in series.iter().zip(reconstructed.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn stft_istft_roundtrip_odd_n_fft() {
+    for (n_fft, hop, len) in [(5, 2, 4), (5, 3, 4), (7, 3, 50), (31, 8, 203)] {
+        let series = make_signal(len, 0.0);
+        let params = StftParams::new(n_fft, hop, WindowFunction::Hann, true);
+
+        let spectrogram = stft(&series, &params);
+        assert_eq!(spectrogram.n_frames(), 1 + len / hop);
+        assert_eq!(spectrogram.n_bins(), n_fft / 2 + 1);
+
+        let reconstructed = istft(&spectrogram);
+        assert_eq!(reconstructed.len(), series.len());
+        for (a, b) in series.iter().zip(reconstructed.iter()) {
+            assert!((a - b).abs() < 1e-9, "n_fft {} hop {}: {} != {}", n_fft, hop, a, b);
+        }
+    }
+}
+
+#[test]
+fn stft_short_series() {
+    let series: Vec<f64> = vec![1.0, 2.0, 3.0];
+    let params = StftParams::new(16, 4, WindowFunction::Hann, true);
+
+    let reconstructed = istft(&stft(&series, &params));
+
+    for (a, b) in series.iter().zip(reconstructed.iter()) {
+        assert!((a - b).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn stft_peak_frequency() {
+    // 8 periods in 64 samples -> bin 8 of a 64 point FFT
+    let series: Vec<f64> = (0..512)
+        .map(|i| (2.0 * std::f64::consts::PI * 8.0 * i as f64 / 64.0).sin())
+        .collect();
+    let params = StftParams::new(64, 16, WindowFunction::Hann, true);
+
+    let magnitude = stft(&series, &params).magnitude();
+    let frame = &magnitude[magnitude.len() / 2];
+    let peak = frame
+        .iter()
+        .enumerate()
+        .fold((0, 0.0), |(i, a), (j, &b)| if b > a { (j, b) } else { (i, a) })
+        .0;
+
+    assert_eq!(peak, 8);
+}
+
+#[test]
+fn dataset_stft_istft_roundtrip() {
+    let orig = Dataset {
+        features: vec![make_signal(64, 0.0), make_signal(64, 0.0)],
+        labels: vec!["A".into(), "B".into()],
+    };
+    let params = StftParams::default();
+
+    let spectrograms = dataset_stft(&orig, &params, true);
+    let recon = dataset_istft(&spectrograms, &orig.labels, false);
+
+    let (max_diff, all_within) = compare_datasets_within_tolerance(&orig, &recon, 1e-6);
+    assert!(all_within, "STFT to ISTFT failed, max diff = {}", max_diff);
+    assert_eq!(recon.labels, orig.labels);
+}
+
+#[test]
+fn haar_dwt() {
+    let series = vec![1.0, 3.0, 5.0, 5.0];
+
+    let (approximation, detail) = dwt(&series, &Wavelet::Haar);
+
+    let s = std::f64::consts::SQRT_2;
+    assert!((approximation[0] - 4.0 / s).abs() < 1e-12);
+    assert!((approximation[1] - 10.0 / s).abs() < 1e-12);
+    assert!((detail[0] + 2.0 / s).abs() < 1e-12);
+    assert!(detail[1].abs() < 1e-12);
+
+    let reconstructed = idwt(&approximation, &detail, &Wavelet::Haar);
+    for (a, b) in series.iter().zip(reconstructed.iter()) {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn wavelet_filters_orthonormal() {
+    let mut wavelets = vec![Wavelet::Haar];
+    wavelets.extend((1..=8).map(Wavelet::Daubechies));
+    wavelets.extend((2..=8).map(Wavelet::Symlet));
+
+    for wavelet in wavelets {
+        let h = wavelet.filter();
+        assert!((h.iter().sum::<f64>() - std::f64::consts::SQRT_2).abs() < 1e-12);
+        for shift in (0..h.len()).step_by(2) {
+            let dot: f64 = h.iter().zip(h[shift..].iter()).map(|(a, b)| a * b).sum();
+            let expected = if shift == 0 { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-12, "{:?} shift {}", wavelet, shift);
+        }
+    }
+}
+
+#[test]
+fn wavedec_waverec_roundtrip() {
+    for len in [64, 101, 250] {
+        let series = make_signal(len, 0.0);
+        for wavelet in [
+            Wavelet::Haar,
+            Wavelet::Daubechies(2),
+            Wavelet::Daubechies(8),
+            Wavelet::Symlet(4),
+            Wavelet::Symlet(7),
+        ] {
+            let coefficients = wavedec(&series, &wavelet, 10);
+            assert_eq!(coefficients.level(), max_level(len, &wavelet));
+
+            let reconstructed = waverec(&coefficients);
+            assert_eq!(reconstructed.len(), len);
+            for (a, b) in series.iter().zip(reconstructed.iter()) {
+                assert!((a - b).abs() < 1e-9, "{:?} len {}: {} != {}", wavelet, len, a, b);
+            }
+        }
+    }
+}
+
+#[test]
+fn wavelet_from_str() {
+    assert_eq!("haar".parse::<Wavelet>(), Ok(Wavelet::Haar));
+    assert_eq!("db4".parse::<Wavelet>(), Ok(Wavelet::Daubechies(4)));
+    assert_eq!("Sym5".parse::<Wavelet>(), Ok(Wavelet::Symlet(5)));
+    assert!("db12".parse::<Wavelet>().is_err());
+    assert!("coif1".parse::<Wavelet>().is_err());
+}
+
+#[test]
+fn dataset_wavedec_waverec_roundtrip() {
+    let orig = Dataset {
+        features: vec![make_signal(50, 0.0), make_signal(50, 0.0)],
+        labels: vec!["A".into(), "B".into()],
+    };
+
+    let coefficients = dataset_wavedec(&orig, &Wavelet::Daubechies(3), 2, true);
+    let recon = dataset_waverec(&coefficients, &orig.labels, false);
+
+    let (max_diff, all_within) = compare_datasets_within_tolerance(&orig, &recon, 1e-6);
+    assert!(all_within, "DWT to IDWT failed, max diff = {}", max_diff);
+}
+
+#[test]
+fn butterworth_coefficients() {
+    // Reference values from scipy.signal.butter(2, 0.5, output="sos")
+    let filter = Filter::butterworth(2, &FilterBand::LowPass(0.5));
+
+    let Filter::Iir(sections) = filter else {
+        panic!("Butterworth filters are IIR filters")
+    };
+    assert_eq!(sections.len(), 1);
+    let expected = [0.29289322, 0.58578644, 0.29289322, 1.0, 0.0, 0.17157288];
+    for (a, b) in sections[0].iter().zip(expected.iter()) {
+        assert!((a - b).abs() < 1e-7, "{:?}", sections[0]);
+    }
+}
+
+#[test]
+fn butterworth_responses() {
+    let half_power = std::f64::consts::FRAC_1_SQRT_2;
+    for order in 1..=6 {
+        let lowpass = Filter::butterworth(order, &FilterBand::LowPass(0.3));
+        assert!((lowpass.gain(0.0) - 1.0).abs() < 1e-9);
+        assert!((lowpass.gain(0.3) - half_power).abs() < 1e-9);
+        assert!(lowpass.gain(0.9) < 0.1);
+
+        let highpass = Filter::butterworth(order, &FilterBand::HighPass(0.3));
+        assert!(highpass.gain(0.0) < 1e-9);
+        assert!((highpass.gain(0.3) - half_power).abs() < 1e-9);
+        assert!((highpass.gain(1.0) - 1.0).abs() < 1e-9);
+
+        let bandpass = Filter::butterworth(order, &FilterBand::BandPass(0.2, 0.5));
+        assert!(bandpass.gain(0.0) < 1e-9 && bandpass.gain(1.0) < 1e-9);
+        assert!((bandpass.gain(0.2) - half_power).abs() < 1e-9);
+        assert!((bandpass.gain(0.5) - half_power).abs() < 1e-9);
+
+        let bandstop = Filter::butterworth(order, &FilterBand::BandStop(0.2, 0.5));
+        assert!((bandstop.gain(0.0) - 1.0).abs() < 1e-9);
+        assert!((bandstop.gain(1.0) - 1.0).abs() < 1e-9);
+        assert!((bandstop.gain(0.2) - half_power).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn windowed_sinc_responses() {
+    let lowpass = Filter::windowed_sinc(61, &FilterBand::LowPass(0.3), WindowFunction::Hamming);
+    assert!((lowpass.gain(0.0) - 1.0).abs() < 1e-9);
+    assert!(lowpass.gain(0.6) < 0.01);
+
+    let highpass = Filter::windowed_sinc(61, &FilterBand::HighPass(0.3), WindowFunction::Hann);
+    assert!((highpass.gain(1.0) - 1.0).abs() < 1e-9);
+    assert!(highpass.gain(0.05) < 0.01);
+
+    let bandpass =
+        Filter::windowed_sinc(81, &FilterBand::BandPass(0.3, 0.6), WindowFunction::Blackman);
+    assert!((bandpass.gain(0.45) - 1.0).abs() < 0.01);
+    assert!(bandpass.gain(0.0) < 0.01 && bandpass.gain(0.9) < 0.01);
+
+    let bandstop =
+        Filter::windowed_sinc(81, &FilterBand::BandStop(0.3, 0.6), WindowFunction::Hamming);
+    assert!((bandstop.gain(0.0) - 1.0).abs() < 1e-9);
+    assert!(bandstop.gain(0.45) < 0.01);
+}
+
+#[test]
+fn filtfilt_zero_phase() {
+    // A slow sine passes a low-pass filter without being shifted
+    let series: Vec<f64> = (0..400)
+        .map(|i| (2.0 * std::f64::consts::PI * i as f64 / 100.0).sin())
+        .collect();
+    let noisy: Vec<f64> = series
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + if i % 2 == 0 { 0.3 } else { -0.3 })
+        .collect();
+
+    for filter in [
+        Filter::butterworth(4, &FilterBand::LowPass(0.2)),
+        Filter::windowed_sinc(31, &FilterBand::LowPass(0.2), WindowFunction::Hamming),
+    ] {
+        let filtered = filter.filtfilt(&noisy);
+        assert_eq!(filtered.len(), series.len());
+        for (a, b) in series.iter().zip(filtered.iter()).skip(20).take(360) {
+            assert!((a - b).abs() < 0.02, "{} != {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn filtfilt_constant() {
+    let series: Vec<f64> = vec![3.0; 50];
+    let filter = Filter::butterworth(3, &FilterBand::LowPass(0.1));
+
+    for v in filter.filtfilt(&series) {
+        assert!((v - 3.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn dataset_filtfilt_shape() {
+    let dataset = Dataset {
+        features: vec![make_signal(30, 0.0), make_signal(5, 0.0)],
+        labels: vec!["A".into(), "B".into()],
+    };
+    let filter = Filter::butterworth(2, &FilterBand::HighPass(0.5));
+
+    let filtered = dataset_filtfilt(&dataset, &filter, true);
+
+    assert_eq!(filtered.features[0].len(), 30);
+    assert_eq!(filtered.features[1].len(), 5);
+    assert_eq!(filtered.labels, dataset.labels);
+}
+
+#[test]
+fn single_precision_transforms() {
+    let dataset = Dataset {
+        features: vec![make_signal(100, 0.0), make_signal(37, 0.0)],
+        labels: vec!["A".into(), "B".into()],
+    };
+    let single: Dataset<f32> = Dataset {
+        features: dataset
+            .features
+            .iter()
+            .map(|x| x.iter().map(|&v| v as f32).collect())
+            .collect(),
+        labels: dataset.labels.clone(),
+    };
+    let close = |a: &Dataset<f32>, b: &Dataset, tolerance: f64| {
+        a.features
+            .iter()
+            .flatten()
+            .zip(b.features.iter().flatten())
+            .all(|(&x, &y)| (x as f64 - y).abs() < tolerance)
+    };
+
+    assert!(close(&dataset_fft(&single, true), &dataset_fft(&dataset, true), 1e-3));
+    assert!(close(&dataset_ifft(&dataset_fft(&single, false), false), &dataset, 1e-5));
+    assert!(close(&dataset_dct(&single, true), &dataset_dct(&dataset, true), 1e-3));
+    assert!(close(&dataset_idct(&dataset_dct(&single, false), false), &dataset, 1e-5));
+
+    let filter = Filter::butterworth(4, &FilterBand::LowPass(0.2));
+    assert!(close(
+        &dataset_filtfilt(&single, &filter, true),
+        &dataset_filtfilt(&dataset, &filter, true),
+        1e-4
+    ));
+
+    let params = StftParams::default();
+    let spectrograms = dataset_stft(&single, &params, true);
+    assert!(close(&dataset_istft(&spectrograms, &single.labels, false), &dataset, 1e-4));
+
+    let coefficients = dataset_wavedec(&single, &Wavelet::Daubechies(3), 2, true);
+    assert!(close(&dataset_waverec(&coefficients, &single.labels, false), &dataset, 1e-4));
+}
@@ -37,4 +37,36 @@ class AugmentationPipeline:
         for augmenter in self.augmenters:
             res = augmenter.augment_one(res)
 
-        return res
\ No newline at end of file
+        return res
+
+    def batches(self, dataset: Dataset, batch_size: int = 32, **kwargs) -> BatchLoader:
+        r"""Iterate over freshly augmented mini-batches of `dataset`
+
+        Returns a `BatchLoader` that applies the augmenters of this pipeline to every batch. All
+        keyword arguments are passed on to `BatchLoader`.
+        """
+        return BatchLoader(dataset, self.augmenters, batch_size, **kwargs)
+
+    def torch_dataset(self, dataset: Dataset, batch_size: int = 32, **kwargs):
+        r"""Wrap `batches` in a `torch.utils.data.IterableDataset`
+
+        Every iteration over the returned dataset starts a new `BatchLoader`, which already yields
+        whole batches, so use it with `DataLoader(..., batch_size=None)`:
+
+        ```
+        loader = torch.utils.data.DataLoader(
+            pipeline.torch_dataset(dataset, batch_size=64), batch_size=None
+        )
+        for features, labels in loader:
+            ...
+        ```
+        """
+        from torch.utils.data import IterableDataset
+
+        pipeline = self
+
+        class AugmentedBatches(IterableDataset):
+            def __iter__(self):
+                return iter(pipeline.batches(dataset, batch_size, **kwargs))
+
+        return AugmentedBatches()
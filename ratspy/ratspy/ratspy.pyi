@@ -29,11 +29,28 @@ class AddNoise:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -61,11 +78,28 @@ class AmplitudePhasePerturbation:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -73,14 +107,47 @@ class AmplitudePhasePerturbation:
         a series in a batch with the specified probability
         """
 
+class BatchLoader:
+    r"""
+    Iterable over freshly augmented mini-batches of a dataset, for training loops
+    
+    Yields `(features, labels)` tuples of a 2D numpy array with one series per row, in the precision
+    of the dataset, and a list of labels. The augmenters are applied to every batch in their order,
+    so every epoch sees new augmentations at constant memory. They must not change the number of
+    series.
+    
+    A background thread prepares up to `prefetch` batches ahead while the training loop runs, and the
+    GIL is released while augmenting. With `shuffle`, the series are drawn in a new random order every
+    epoch. A `seed` makes both the order and the augmentations reproducible, every batch is augmented
+    with a seed derived from it and the index of the batch. `epochs=None` streams indefinitely. Every
+    iteration over the loader starts over from the first epoch, also after a `break`.
+    
+    The loader keeps the features the dataset has when it is created, so changing the dataset
+    afterwards has no effect.
+    Use `AugmentationPipeline.torch_dataset` to feed a `torch.utils.data.DataLoader`.
+    """
+    def __new__(cls, dataset:Dataset, augmenters:typing.Sequence[typing.Any], batch_size:builtins.int=32, *, shuffle:builtins.bool=True, seed:typing.Optional[builtins.int]=None, drop_last:builtins.bool=False, epochs:typing.Optional[builtins.int]=1, prefetch:builtins.int=2, parallel:builtins.bool=True) -> BatchLoader: ...
+    def __len__(self) -> builtins.int:
+        r"""
+        Number of batches over all epochs
+        """
+    def __iter__(self) -> BatchLoader:
+        r"""
+        Starts a new pass over all epochs, stopping the worker of the previous one
+        """
+    def __next__(self) -> typing.Optional[tuple[numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], builtins.list[builtins.str]]]: ...
+
 class Convolve:
     r"""
     Usage of this augmenter is to convolve time series data with a kernel
     
-    The kernel can be flat or Gaussian, and the size of the kernel are the parameters
+    The kernel window and the size of the kernel are the parameters. When `max_size` is set, the kernel size
+    is drawn uniformly from `size` to `max_size` (inclusive) for each series. A user-supplied kernel can be
+    passed through `kernel`, which overrides `window` and `size`.
     
     The convolve operation is applied to each time series in the dataset, and smoothening is achieved
-    by averaging the values in the kernel window over the time series data.
+    by averaging the values in the kernel window over the time series data. How the series is extended beyond
+    its borders is controlled by `boundary`.
     """
     name: builtins.str
     probability: builtins.float
@@ -90,11 +157,28 @@ class Convolve:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -116,11 +200,28 @@ class Crop:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -132,12 +233,34 @@ class Dataset:
     r"""
     Holds multiple univariate time series with their labels
     
-    Passed to the `augment_batch` function from augmenters
+    Passed to the `augment_batch` function from augmenters. The series are stored in the precision
+    of the array they were created from, float32 or float64, and augmented and transformed in it.
+    
+    All series have the same length and are stored one after the other in one contiguous buffer,
+    which `features` returns as a read-only numpy view without copying. Augmenters that keep the
+    shape of the batch augment the buffer in place, unless a view of it is still alive, in which
+    case the dataset is copied first and the view keeps the features it was taken of.
+    """
+    features: numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]
+    r"""
+    Features as a 2D array with one series per row, in the precision of the dataset
+    
+    A read-only view of the dataset without copying it. Use `features.copy()` for an array that
+    can be changed.
     """
-    features: numpy.typing.NDArray[numpy.float64]
     labels: builtins.list[builtins.str]
-    def __new__(cls, features:numpy.typing.NDArray[numpy.float64], labels:typing.Sequence[builtins.str]) -> Dataset: ...
-    def set_features(self, features:numpy.typing.NDArray[numpy.float64]) -> None: ...
+    dtype: builtins.str
+    r"""
+    Name of the numpy dtype the series are stored in, `"float32"` or `"float64"`
+    """
+    def __new__(cls, features:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], labels:typing.Sequence[builtins.str]) -> Dataset:
+        r"""
+        Creates a dataset from a float32 or float64 array with one series per row
+        """
+    def set_features(self, features:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> None:
+        r"""
+        Replaces the series, the dataset takes over the precision of `features`
+        """
     def set_labels(self, labels:typing.Sequence[builtins.str]) -> None: ...
 
 class Drift:
@@ -156,11 +279,28 @@ class Drift:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -184,11 +324,28 @@ class Drop:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -212,11 +369,28 @@ class FrequencyMask:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -238,11 +412,28 @@ class Jittering:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -264,11 +455,28 @@ class Permutate:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -288,11 +496,28 @@ class Pool:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -309,7 +534,7 @@ class QualityBenchmarking:
     Currently, it includes using the Dynamic Time Warping (DTW) algorithm to measure the similarity between original and augmented time series data.
     """
     @staticmethod
-    def compute_dtw(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float]) -> tuple[builtins.float, builtins.list[tuple[builtins.int, builtins.int]]]:
+    def compute_dtw(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float], *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None) -> tuple[builtins.float, builtins.list[tuple[builtins.int, builtins.int]]]:
         r"""
         Implementation of Dynamic Time Warping (DTW) algorithm.
         
@@ -324,9 +549,149 @@ class QualityBenchmarking:
         
         # Returns
         
+        * `window` - Optional radius of a Sakoe-Chiba band around the diagonal the warping path must stay in.
+        
+        * `max_slope` - Optional maximum slope (> 1) of an Itakura parallelogram the warping path must stay in.
+        
+        # Returns
+        
         A tuple containing the DTW distance (float) and a list of tuples representing the
         optimal path as pairs of indices (int, int).
         """
+    @staticmethod
+    def compute_dtw_distance(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float], *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, upper_bound:typing.Optional[builtins.float]=None) -> builtins.float:
+        r"""
+        Computes only the DTW distance, without the warping path, using linear memory.
+        
+        Accepts the same constraints as `compute_dtw`. If `upper_bound` is given, the computation
+        is abandoned as soon as the distance is known to exceed it and `inf` is returned.
+        """
+    @staticmethod
+    def lb_kim(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float]) -> builtins.float:
+        r"""
+        LB_Kim lower bound of the DTW distance, computed from the first and last points.
+        """
+    @staticmethod
+    def lb_keogh(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float], window:builtins.int) -> builtins.float:
+        r"""
+        LB_Keogh lower bound of the DTW distance constrained by a Sakoe-Chiba band of `window`.
+        
+        Both sequences must have the same length.
+        """
+    @staticmethod
+    def compute_dtw_dependent(a:typing.Sequence[typing.Sequence[builtins.float]], b:typing.Sequence[typing.Sequence[builtins.float]], cost:LocalCost=..., *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None) -> tuple[builtins.float, builtins.list[tuple[builtins.int, builtins.int]]]:
+        r"""
+        Dependent multivariate DTW (DTW_D), all channels share one warping path.
+        
+        The series are given channel first as list[list[float]], i.e. `a[c][t]` is the value of channel `c` at time `t`.
+        
+        # Returns
+        
+        A tuple containing the DTW distance (float) and the optimal path as pairs of indices (int, int).
+        """
+    @staticmethod
+    def compute_dtw_independent(a:typing.Sequence[typing.Sequence[builtins.float]], b:typing.Sequence[typing.Sequence[builtins.float]], cost:LocalCost=..., *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None) -> builtins.float:
+        r"""
+        Independent multivariate DTW (DTW_I), the sum of the DTW distances of the channels.
+        
+        The series are given channel first like for `compute_dtw_dependent`.
+        """
+    @staticmethod
+    def compute_soft_dtw(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float], gamma:builtins.float=1.0) -> builtins.float:
+        r"""
+        Soft-DTW of two sequences with smoothing parameter `gamma` (> 0).
+        
+        Uses the squared difference as local cost and is not normalized. Converges to DTW as `gamma` goes to 0.
+        """
+    @staticmethod
+    def compute_soft_dtw_gradient(a:typing.Sequence[builtins.float], b:typing.Sequence[builtins.float], gamma:builtins.float=1.0) -> tuple[builtins.float, builtins.list[builtins.float]]:
+        r"""
+        Soft-DTW of two sequences and its gradient with respect to `a`.
+        
+        # Returns
+        
+        A tuple containing the soft-DTW value (float) and the gradient as a list[float] of the length of `a`.
+        """
+    @staticmethod
+    def distance_matrix(a:Dataset, b:Dataset, metric:DistanceMetric, *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Computes the distance between every series of `a` and every series of `b` in parallel.
+        
+        Typically used to compare an original dataset with its augmented version.
+        
+        # Returns
+        
+        A numpy array of shape (len(a), len(b)) whose entry [i, j] is the distance between
+        series i of `a` and series j of `b`.
+        """
+    @staticmethod
+    def pairwise_distance_matrix(dataset:Dataset, metric:DistanceMetric, *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Computes the distance between all pairs of series within one dataset in parallel.
+        
+        # Returns
+        
+        A symmetric numpy array of shape (len(dataset), len(dataset)) with zeros on the diagonal.
+        """
+    @staticmethod
+    def mmd(a:Dataset, b:Dataset, sigma:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> builtins.float:
+        r"""
+        Maximum mean discrepancy between two datasets with a Gaussian RBF kernel.
+        
+        Every series is treated as one point of its dataset. If no `sigma` is given, the median
+        pairwise distance of all series is used. Returns 0 for identical datasets.
+        """
+    @staticmethod
+    def per_timestep_ks(a:Dataset, b:Dataset, parallel:builtins.bool=True) -> builtins.list[tuple[builtins.float, builtins.float]]:
+        r"""
+        Kolmogorov-Smirnov test between the values of both datasets at every time step.
+        
+        # Returns
+        
+        A list of (statistic, p-value) tuples, one for each time step.
+        """
+    @staticmethod
+    def spectral_ks(a:Dataset, b:Dataset, parallel:builtins.bool=True) -> builtins.list[tuple[builtins.float, builtins.float]]:
+        r"""
+        Kolmogorov-Smirnov test between the FFT magnitudes of both datasets at every frequency bin.
+        
+        # Returns
+        
+        A list of (statistic, p-value) tuples, one for each non-negative frequency.
+        """
+    @staticmethod
+    def acf_distance(a:Dataset, b:Dataset, max_lag:builtins.int, parallel:builtins.bool=True) -> builtins.float:
+        r"""
+        Euclidean distance between the mean autocorrelation functions (lags 1 to `max_lag`) of two datasets.
+        """
+    @staticmethod
+    def psd_distance(a:Dataset, b:Dataset, parallel:builtins.bool=True) -> builtins.float:
+        r"""
+        Total variation distance between the normalized mean power spectral densities of two datasets.
+        
+        Ranges from 0 (same distribution of power over the frequencies) to 1.
+        """
+    @staticmethod
+    def diversity(dataset:Dataset, metric:DistanceMetric, *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> builtins.float:
+        r"""
+        Diversity of a dataset, the mean distance between all pairs of its series.
+        """
+    @staticmethod
+    def knn_predict(train:Dataset, test:Dataset, k:builtins.int=1, metric:DistanceMetric=..., *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> builtins.list[typing.Optional[builtins.str]]:
+        r"""
+        Predicts the labels of the test set with a k-nearest-neighbor classifier trained on `train`.
+        
+        Ties in the vote are broken in favor of the label whose closest neighbor is nearest. The label
+        is None for test series without a training series at a finite distance, e.g. empty ones.
+        """
+    @staticmethod
+    def knn_accuracy(train:Dataset, test:Dataset, k:builtins.int=1, metric:DistanceMetric=..., *, window:typing.Optional[builtins.int]=None, max_slope:typing.Optional[builtins.float]=None, parallel:builtins.bool=True) -> builtins.float:
+        r"""
+        Accuracy of a k-nearest-neighbor classifier trained on `train` on the test set.
+        
+        Train on an augmented training set and compare with the accuracy on the original training set
+        to judge an augmentation.
+        """
 
 class Quantize:
     r"""
@@ -342,11 +707,121 @@ class Quantize:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class RandAugment:
+    r"""
+    Augmenter that applies `n` augmenters drawn at random, all at the same `magnitude` in `[0, 1]`
+    
+    The augmenters are drawn uniformly with replacement from all built-in augmenters whose strength
+    can be set by a magnitude, independently for every series.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class RandomFilter:
+    r"""
+    Augmenter that applies a filter with a random cutoff frequency to simulate different sensor bandwidths
+    
+    For every series, a filter of kind `filter_type` with a cutoff drawn uniformly from `cutoff_range` is applied
+    forward and backward, so the series is not shifted in time. Band-pass and band-stop filters draw both band edges
+    from `cutoff_range`. Frequencies are normalized to the Nyquist frequency, so `cutoff_range` must lie within `(0, 1)`.
+    
+    By default, a Butterworth filter of order `order` is used. When `num_taps` is set, a windowed-sinc FIR filter with
+    `num_taps` coefficients and the window function `window` is used instead. High-pass and
+    band-stop filters need an odd `num_taps`.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -370,11 +845,28 @@ class RandomTimeWarpAugmenter:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -398,11 +890,28 @@ class Repeat:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -424,11 +933,28 @@ class Resize:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -450,11 +976,28 @@ class Reverse:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -474,11 +1017,28 @@ class Rotation:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -499,11 +1059,76 @@ class Scaling:
         
         Parallelized when `parallell` is set
         """
-    def augment_one(self, x:numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float64]:
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
         r"""
         Augment one time series
         
-        When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class SpecAugment:
+    r"""
+    SpecAugment-style augmenter for time series
+    
+    Each series is transformed into a spectrogram with a short-time Fourier transform of size `n_fft`,
+    hop length `hop_length` and window function `window`. Then `n_freq_masks` bands of up to `freq_mask_width`
+    frequency bins and `n_time_masks` blocks of up to `time_mask_width` frames are zeroed out and the
+    spectrogram is transformed back into a time series of the original length.
+    
+    If `time_warp` is set, the spectrogram is additionally warped along the time axis by up to `time_warp` frames before masking.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
         """
     def set_probability(self, p:builtins.float) -> None:
         r"""
@@ -550,12 +1175,205 @@ class Transforms:
         Computes maximum absolute difference between two Datasets and check if all differences are within a tolerance.
         """
 
+class TrivialAugment:
+    r"""
+    Augmenter that applies one augmenter drawn at random at a magnitude drawn uniformly from `[0, 1]`
+    
+    The augmenter is drawn from all built-in augmenters whose strength can be set by a magnitude,
+    independently for every series.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class WaveletDrop:
+    r"""
+    Augmenter that drops detail coefficients of a discrete wavelet decomposition
+    
+    Each series is decomposed with the wavelet `wavelet` (e.g. `"haar"`, `"db4"` or `"sym5"`) down to the deepest level
+    listed in `levels`. Of the detail coefficients of every level in `levels` (level 1 being the finest scale),
+    a fraction `percentage` of the coefficients is set to zero, then the series is reconstructed.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class WaveletPerturbation:
+    r"""
+    Augmenter that perturbs the detail coefficients of a discrete wavelet decomposition
+    
+    Each series is decomposed with the wavelet `wavelet` (e.g. `"haar"`, `"db4"` or `"sym5"`) down to the deepest level
+    listed in `levels`. Gaussian noise is added to the detail coefficients of every level in `levels` (level 1 being the finest scale),
+    then the series is reconstructed.
+    
+    The standard deviation of the noise is `std_dev` times the standard deviation of the coefficients of that level.
+    """
+    name: builtins.str
+    probability: builtins.float
+    def augment_batch(self, dataset:ratspy.Dataset, parallel:builtins.bool) -> None:
+        r"""
+        Augment a whole batch
+        
+        Parallelized when `parallell` is set
+        """
+    def augment_copies(self, dataset:Dataset, k:builtins.int=1, keep_original:builtins.bool=False, parallel:builtins.bool=True) -> tuple[Dataset, builtins.list[builtins.int]]:
+        r"""
+        Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+        
+        Returns the new dataset and the index of the source series of every series in it.
+        If `keep_original` is set, the unaugmented series follow the augmented copies.
+        """
+    def augment_one(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]) -> numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Augment one time series
+        
+        When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+        The series is augmented in the precision of `x`, float32 or float64.
+        """
+    def augment_inplace(self, x:numpy.typing.NDArray[numpy.float32] | numpy.typing.NDArray[numpy.float64], parallel:builtins.bool=True) -> None:
+        r"""
+        Augment time series stored in a writable numpy array in place
+        
+        `x` is either one series or a C-contiguous 2D array with one series per row, of
+        float32 or float64 values. Every series is augmented with the probability of the
+        augmenter, without copying the array. Only works with augmenters that keep the
+        length of the series and don't need the whole batch.
+        """
+    def set_probability(self, p:builtins.float) -> None:
+        r"""
+        By setting a probability with this function the augmenter will only augment
+        a series in a batch with the specified probability
+        """
+
+class ConvolveBoundary(Enum):
+    r"""
+    Enum to specify how the `Convolve` augmenter extends the series beyond its borders
+    
+    - Zero: pads with zeros
+    
+    - Reflect: mirrors the series without repeating the edge value
+    
+    - Replicate: repeats the edge value
+    
+    - Wrap: continues periodically with the other end of the series
+    """
+    Zero = ...
+    Reflect = ...
+    Replicate = ...
+    Wrap = ...
+
 class ConvolveWindow(Enum):
     r"""
     Enum to specify the kernel window for the `Convolve` augmenter
+    
+    All windows are normalized to sum to 1. The `Exponential` window decays as `exp(-|n - center| / tau)`.
     """
     Flat = ...
     Gaussian = ...
+    Triangular = ...
+    Hann = ...
+    Hamming = ...
+    Blackman = ...
+    Exponential = ...
+
+class DistanceMetric(Enum):
+    r"""
+    Enum to specify the metric used to compare two time series
+    
+    - `Euclidean`: Euclidean distance, needs series of the same length
+    - `Dtw`: DTW distance, optionally constrained with `window` or `max_slope`
+    - `Correlation`: one minus the Pearson correlation, needs series of the same length
+    """
+    Euclidean = ...
+    Dtw = ...
+    Correlation = ...
+
+class FilterType(Enum):
+    r"""
+    Enum to specify the kind of filter applied by the `RandomFilter` augmenter
+    """
+    LowPass = ...
+    HighPass = ...
+    BandPass = ...
+    BandStop = ...
+
+class LocalCost(Enum):
+    r"""
+    Enum to specify the local cost of matching two points in multivariate DTW
+    
+    - `Absolute`: sum of absolute differences over the channels
+    - `Squared`: sum of squared differences over the channels
+    - `Euclidean`: Euclidean distance between the points
+    """
+    Absolute = ...
+    Squared = ...
+    Euclidean = ...
 
 class NoiseType(Enum):
     r"""
@@ -574,3 +1392,12 @@ class PoolingMethod(Enum):
     Min = ...
     Average = ...
 
+class WindowFunction(Enum):
+    r"""
+    Enum to specify the window function applied to every frame of the short-time Fourier transform
+    """
+    Rectangular = ...
+    Hann = ...
+    Hamming = ...
+    Blackman = ...
+
@@ -0,0 +1,98 @@
+use ndarray::Dimension;
+use numpy::{PyArray, PyArrayMethods, PyReadonlyArray, PyReadwriteArray};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3_stub_gen::{PyStubType, TypeInfo};
+use std::convert::Infallible;
+
+/// Error for arrays that hold neither float32 nor float64 values or have the wrong dimension
+fn dtype_error<D: Dimension>(ob: &Bound<'_, PyAny>) -> PyErr {
+    let dimension = D::NDIM.map_or(String::new(), |n| format!("{}D ", n));
+    let found = ob
+        .get_type()
+        .name()
+        .map_or_else(|_| String::from("object"), |name| name.to_string());
+    PyTypeError::new_err(format!(
+        "Expected a {}numpy array of float32 or float64 values, got {}",
+        dimension, found
+    ))
+}
+
+/// A read-only float32 or float64 array
+pub(crate) enum ReadonlyFloatArray<'py, D: Dimension> {
+    Single(PyReadonlyArray<'py, f32, D>),
+    Double(PyReadonlyArray<'py, f64, D>),
+}
+
+impl<'py, D: Dimension> FromPyObject<'py> for ReadonlyFloatArray<'py, D> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(array) = ob.extract() {
+            Ok(ReadonlyFloatArray::Double(array))
+        } else if let Ok(array) = ob.extract() {
+            Ok(ReadonlyFloatArray::Single(array))
+        } else {
+            Err(dtype_error::<D>(ob))
+        }
+    }
+}
+
+/// A writable float32 or float64 array
+pub(crate) enum ReadwriteFloatArray<'py, D: Dimension> {
+    Single(PyReadwriteArray<'py, f32, D>),
+    Double(PyReadwriteArray<'py, f64, D>),
+}
+
+impl<'py, D: Dimension> FromPyObject<'py> for ReadwriteFloatArray<'py, D> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Checked before borrowing, so that a failed borrow is reported instead of the dtype
+        if let Ok(array) = ob.downcast::<PyArray<f64, D>>() {
+            Ok(ReadwriteFloatArray::Double(array.try_readwrite()?))
+        } else if let Ok(array) = ob.downcast::<PyArray<f32, D>>() {
+            Ok(ReadwriteFloatArray::Single(array.try_readwrite()?))
+        } else {
+            Err(dtype_error::<D>(ob))
+        }
+    }
+}
+
+/// A new float32 or float64 array
+pub(crate) enum FloatArray<'py, D: Dimension> {
+    Single(Bound<'py, PyArray<f32, D>>),
+    Double(Bound<'py, PyArray<f64, D>>),
+}
+
+impl<'py, D: Dimension> IntoPyObject<'py> for FloatArray<'py, D> {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, _py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(match self {
+            FloatArray::Single(array) => array.into_any(),
+            FloatArray::Double(array) => array.into_any(),
+        })
+    }
+}
+
+/// The stub type of all three, a union of both array types
+fn float_array_type<D: Dimension>() -> TypeInfo {
+    PyArray::<f32, D>::type_output() | PyArray::<f64, D>::type_output()
+}
+
+impl<D: Dimension> PyStubType for ReadonlyFloatArray<'_, D> {
+    fn type_output() -> TypeInfo {
+        float_array_type::<D>()
+    }
+}
+
+impl<D: Dimension> PyStubType for ReadwriteFloatArray<'_, D> {
+    fn type_output() -> TypeInfo {
+        float_array_type::<D>()
+    }
+}
+
+impl<D: Dimension> PyStubType for FloatArray<'_, D> {
+    fn type_output() -> TypeInfo {
+        float_array_type::<D>()
+    }
+}
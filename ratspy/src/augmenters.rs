@@ -1,36 +1,194 @@
-use crate::Dataset;
-use rats_rs::augmenters::Augmenter;
-use numpy::{PyArray1, PyArrayMethods, ToPyArray};
+use crate::arrays::{FloatArray, ReadonlyFloatArray, ReadwriteFloatArray};
+use crate::{to_dense, Dataset};
+use crate::transforms::WindowFunction;
+use ndarray::{Ix1, IxDyn};
+use rats_rs::augmenters::{Augmenter, AugmenterBase};
+use rats_rs::{DenseDataset, Float};
+use numpy::{Element, IntoPyArray, PyArray1, PyReadonlyArray1, PyReadwriteArrayDyn, PyUntypedArrayMethods};
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyclass_enum, gen_stub_pymethods};
+use rand::{rng, Rng};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Augments one series in the precision of its array, see `augment_one`
+fn augment_series<'py, T: Float + Element>(
+    py: Python<'py>,
+    augmenter: &(dyn Augmenter<T> + Sync),
+    x: PyReadonlyArray1<'py, T>,
+) -> Bound<'py, PyArray1<T>> {
+    let augmented = match x.as_slice() {
+        Ok(x) => py.allow_threads(|| augmenter.augment_one(x)),
+        // Not contiguous, so the values are copied first
+        Err(_) => {
+            let x = x.as_array().to_vec();
+            py.allow_threads(|| augmenter.augment_one(&x))
+        }
+    };
+    augmented.into_pyarray(py)
+}
+
+/// Augments the series of a 1D or 2D array in place, see `augment_inplace`
+fn augment_array<T: Float + Element>(
+    py: Python<'_>,
+    augmenter: &(dyn Augmenter<T> + Sync),
+    mut x: PyReadwriteArrayDyn<'_, T>,
+    parallel: bool,
+) -> PyResult<()> {
+    let length = match x.shape() {
+        [length] => *length,
+        [_, length] => *length,
+        _ => return Err(PyValueError::new_err("Array must be 1D or 2D")),
+    };
+    let data = x
+        .as_slice_mut()
+        .map_err(|_| PyValueError::new_err("Array must be C-contiguous"))?;
+    py.allow_threads(|| augment_inplace_rows(augmenter, data, length, parallel))
+}
+
+/// Augments the series of `length` values stored one after the other in `data` in place
+///
+/// Every series is augmented with the probability of the augmenter, like in `augment_batch`.
+fn augment_inplace_rows<T: Float>(
+    augmenter: &(dyn Augmenter<T> + Sync),
+    data: &mut [T],
+    length: usize,
+    parallel: bool,
+) -> PyResult<()> {
+    if !augmenter.supports_per_sample() {
+        return Err(PyValueError::new_err(format!(
+            "Augmenter '{}' needs the whole batch and cannot augment in place",
+            augmenter.get_name()
+        )));
+    }
+    if length == 0 {
+        return Ok(());
+    }
+    let augment = |row: &mut [T]| -> PyResult<()> {
+        if augmenter.get_probability() > rng().random() && !augmenter.augment_one_in_place(row) {
+            let augmented = augmenter.augment_one(row);
+            if augmented.len() != row.len() {
+                return Err(PyValueError::new_err(format!(
+                    "Augmenter '{}' changes the length of the series and cannot augment in place",
+                    augmenter.get_name()
+                )));
+            }
+            row.copy_from_slice(&augmented);
+        }
+        Ok(())
+    };
+    if parallel {
+        data.par_chunks_mut(length).try_for_each(augment)
+    } else {
+        data.chunks_mut(length).try_for_each(augment)
+    }
+}
+
+/// Augments the batch of a `Dataset`, see `augment_batch`
+///
+/// The series are augmented in place, or in a copy if a numpy view of them is alive. Augmenters
+/// that change the shape of the batch (`reshapes`) augment a nested copy, which replaces the batch.
+fn augment_dense_batch<T: Float>(
+    augmenter: &(dyn Augmenter<T> + Sync),
+    dataset: &mut Arc<DenseDataset<T>>,
+    parallel: bool,
+    reshapes: bool,
+) -> PyResult<()> {
+    if reshapes {
+        let mut nested = dataset.to_dataset();
+        augmenter.augment_batch(&mut nested, parallel, false);
+        *dataset = Arc::new(to_dense(&nested)?);
+    } else {
+        augmenter.augment_dense(Arc::make_mut(dataset), parallel);
+    }
+    Ok(())
+}
 
 macro_rules! wrap_augmentation_functions {
     ($struct_name:ident) => {
+        wrap_augmentation_functions!($struct_name, false);
+    };
+    ($struct_name:ident, reshapes) => {
+        wrap_augmentation_functions!($struct_name, true);
+    };
+    ($struct_name:ident, $reshapes:literal) => {
         #[gen_stub_pymethods]
         #[pymethods]
         impl $struct_name {
             /// Augment a whole batch
             ///
             /// Parallelized when `parallell` is set
-            fn augment_batch(&self, dataset: &mut Dataset, parallel: bool) {
-                self.inner.augment_batch(&mut dataset.inner, parallel, false);
+            fn augment_batch(
+                &self,
+                py: Python<'_>,
+                dataset: &mut Dataset,
+                parallel: bool,
+            ) -> PyResult<()> {
+                let inner = &self.inner;
+                match_precision!(&mut dataset.inner, dataset => {
+                    py.allow_threads(|| augment_dense_batch(inner, dataset, parallel, $reshapes))
+                })
+            }
+
+            /// Create `k` augmented copies of every series of a batch, leaving `dataset` untouched
+            ///
+            /// Returns the new dataset and the index of the source series of every series in it.
+            /// If `keep_original` is set, the unaugmented series follow the augmented copies.
+            #[pyo3(signature = (dataset, k=1, keep_original=false, parallel=true))]
+            fn augment_copies(
+                &self,
+                py: Python<'_>,
+                dataset: &Dataset,
+                k: usize,
+                keep_original: bool,
+                parallel: bool,
+            ) -> PyResult<(Dataset, Vec<usize>)> {
+                let inner = &self.inner;
+                match_precision!(&dataset.inner, dataset => {
+                    let augmented = py.allow_threads(|| {
+                        inner.augment_copies(&dataset.to_dataset(), k, keep_original, parallel, false)
+                    });
+                    Ok((Dataset::from(to_dense(&augmented.dataset)?), augmented.source))
+                })
             }
 
             /// Augment one time series
             ///
-            /// When called, the augmenter will always augment the series no matter what the probability for this augmenter is
+            /// When called, the augmenter will always augment the series no matter what the probability for this augmenter is.
+            /// The series is augmented in the precision of `x`, float32 or float64.
             fn augment_one<'py>(
                 &self,
                 py: Python<'py>,
-                x: &Bound<'py, PyArray1<f64>>,
-            ) -> Bound<'py, PyArray1<f64>> {
-                let x = x.to_owned_array();
-                let x_vec = x.as_slice().unwrap().to_vec();
-
-                let x_vec = self.inner.augment_one(&x_vec);
+                x: ReadonlyFloatArray<'py, Ix1>,
+            ) -> FloatArray<'py, Ix1> {
+                match x {
+                    ReadonlyFloatArray::Single(x) => {
+                        FloatArray::Single(augment_series(py, &self.inner, x))
+                    }
+                    ReadonlyFloatArray::Double(x) => {
+                        FloatArray::Double(augment_series(py, &self.inner, x))
+                    }
+                }
+            }
 
-                let x = ndarray::Array::from_vec(x_vec);
-                x.to_pyarray(py)
+            /// Augment time series stored in a writable numpy array in place
+            ///
+            /// `x` is either one series or a C-contiguous 2D array with one series per row, of
+            /// float32 or float64 values. Every series is augmented with the probability of the
+            /// augmenter, without copying the array. Only works with augmenters that keep the
+            /// length of the series and don't need the whole batch.
+            #[pyo3(signature = (x, parallel=true))]
+            fn augment_inplace(
+                &self,
+                py: Python<'_>,
+                x: ReadwriteFloatArray<'_, IxDyn>,
+                parallel: bool,
+            ) -> PyResult<()> {
+                match x {
+                    ReadwriteFloatArray::Single(x) => augment_array(py, &self.inner, x, parallel),
+                    ReadwriteFloatArray::Double(x) => augment_array(py, &self.inner, x, parallel),
+                }
             }
 
             #[getter]
@@ -54,6 +212,53 @@ macro_rules! wrap_augmentation_functions {
     };
 }
 
+/// Calls `f` with the Rust augmenter wrapped by the Python augmenter `augmenter`
+///
+/// The Python object stays borrowed while `f` runs, so `f` may release the GIL.
+pub(crate) fn with_inner<T: Float, R>(
+    augmenter: &Bound<'_, PyAny>,
+    f: impl FnOnce(&(dyn Augmenter<T> + Sync)) -> R,
+) -> PyResult<R> {
+    macro_rules! dispatch {
+        ($($struct_name:ident),*) => {
+            $(
+                if let Ok(augmenter) = augmenter.downcast::<$struct_name>() {
+                    return Ok(f(&augmenter.borrow().inner));
+                }
+            )*
+        };
+    }
+    dispatch!(
+        Repeat,
+        Scaling,
+        Rotation,
+        Jittering,
+        Drop,
+        Crop,
+        AddNoise,
+        AmplitudePhasePerturbation,
+        FrequencyMask,
+        RandomTimeWarpAugmenter,
+        Pool,
+        Quantize,
+        Resize,
+        Reverse,
+        Permutate,
+        Drift,
+        Convolve,
+        SpecAugment,
+        WaveletPerturbation,
+        WaveletDrop,
+        RandomFilter,
+        RandAugment,
+        TrivialAugment
+    );
+    Err(PyTypeError::new_err(format!(
+        "{} is not an augmenter",
+        augmenter.get_type().name()?
+    )))
+}
+
 /// Augmenter that repeats all data rows `n` times
 ///
 /// Resource intensive because the data needs to be copied `n` times
@@ -75,7 +280,7 @@ impl Repeat {
     }
 }
 
-wrap_augmentation_functions!(Repeat);
+wrap_augmentation_functions!(Repeat, reshapes);
 
 /// Augmenter that scales a time series with a random scalar within the range specified
 /// by `min_factor` (inclusive) and `max_factor` (inclusive)
@@ -180,7 +385,7 @@ impl Crop {
     }
 }
 
-wrap_augmentation_functions!(Crop);
+wrap_augmentation_functions!(Crop, reshapes);
 
 /// Enum to specify the noise type for the AddNoise augmenter
 #[gen_stub_pyclass_enum]
@@ -385,7 +590,7 @@ impl Resize {
     }
 }
 
-wrap_augmentation_functions!(Resize);
+wrap_augmentation_functions!(Resize, reshapes);
 
 /// Reverses time series
 ///
@@ -453,19 +658,47 @@ impl Drift {
 wrap_augmentation_functions!(Drift);
 
 /// Enum to specify the kernel window for the `Convolve` augmenter
+///
+/// All windows are normalized to sum to 1. The `Exponential` window decays as `exp(-|n - center| / tau)`.
 #[gen_stub_pyclass_enum]
 #[pyclass]
 pub enum ConvolveWindow {
     Flat,
     Gaussian,
+    Triangular,
+    Hann,
+    Hamming,
+    Blackman,
+    Exponential,
+}
+
+/// Enum to specify how the `Convolve` augmenter extends the series beyond its borders
+///
+/// - Zero: pads with zeros
+///
+/// - Reflect: mirrors the series without repeating the edge value
+///
+/// - Replicate: repeats the edge value
+///
+/// - Wrap: continues periodically with the other end of the series
+#[gen_stub_pyclass_enum]
+#[pyclass]
+pub enum ConvolveBoundary {
+    Zero,
+    Reflect,
+    Replicate,
+    Wrap,
 }
 
 /// Usage of this augmenter is to convolve time series data with a kernel
 ///
-/// The kernel can be flat or Gaussian, and the size of the kernel are the parameters
+/// The kernel window and the size of the kernel are the parameters. When `max_size` is set, the kernel size
+/// is drawn uniformly from `size` to `max_size` (inclusive) for each series. A user-supplied kernel can be
+/// passed through `kernel`, which overrides `window` and `size`.
 ///
 /// The convolve operation is applied to each time series in the dataset, and smoothening is achieved
-/// by averaging the values in the kernel window over the time series data.
+/// by averaging the values in the kernel window over the time series data. How the series is extended beyond
+/// its borders is controlled by `boundary`.
 #[gen_stub_pyclass]
 #[pyclass]
 pub struct Convolve {
@@ -475,15 +708,238 @@ pub struct Convolve {
 #[pymethods]
 impl Convolve {
     #[new]
-    fn new(window: &ConvolveWindow, size: usize) -> Self {
-        let int_window = match window {
-            ConvolveWindow::Flat => rats_rs::augmenters::ConvolveWindow::Flat,
-            ConvolveWindow::Gaussian => rats_rs::augmenters::ConvolveWindow::Gaussian,
+    #[pyo3(signature = (window, size, *, max_size=None, boundary=&ConvolveBoundary::Zero, tau=1.0, kernel=None))]
+    fn new(
+        window: &ConvolveWindow,
+        size: usize,
+        max_size: Option<usize>,
+        boundary: &ConvolveBoundary,
+        tau: f64,
+        kernel: Option<Vec<f64>>,
+    ) -> Self {
+        let int_window = match (kernel, window) {
+            (Some(kernel), _) => rats_rs::augmenters::ConvolveWindow::Custom(kernel),
+            (None, ConvolveWindow::Flat) => rats_rs::augmenters::ConvolveWindow::Flat,
+            (None, ConvolveWindow::Gaussian) => rats_rs::augmenters::ConvolveWindow::Gaussian,
+            (None, ConvolveWindow::Triangular) => rats_rs::augmenters::ConvolveWindow::Triangular,
+            (None, ConvolveWindow::Hann) => rats_rs::augmenters::ConvolveWindow::Hann,
+            (None, ConvolveWindow::Hamming) => rats_rs::augmenters::ConvolveWindow::Hamming,
+            (None, ConvolveWindow::Blackman) => rats_rs::augmenters::ConvolveWindow::Blackman,
+            (None, ConvolveWindow::Exponential) => {
+                rats_rs::augmenters::ConvolveWindow::Exponential(tau)
+            }
         };
-        Convolve {
-            inner: rats_rs::augmenters::Convolve::new(int_window, size),
-        }
+        let int_boundary = match boundary {
+            ConvolveBoundary::Zero => rats_rs::augmenters::ConvolveBoundary::Zero,
+            ConvolveBoundary::Reflect => rats_rs::augmenters::ConvolveBoundary::Reflect,
+            ConvolveBoundary::Replicate => rats_rs::augmenters::ConvolveBoundary::Replicate,
+            ConvolveBoundary::Wrap => rats_rs::augmenters::ConvolveBoundary::Wrap,
+        };
+
+        let mut inner = rats_rs::augmenters::Convolve::with_size_range(
+            int_window,
+            (size, max_size.unwrap_or(size)),
+        );
+        inner.set_boundary(int_boundary);
+        Convolve { inner }
     }
 }
 
 wrap_augmentation_functions!(Convolve);
+
+/// SpecAugment-style augmenter for time series
+///
+/// Each series is transformed into a spectrogram with a short-time Fourier transform of size `n_fft`,
+/// hop length `hop_length` and window function `window`. Then `n_freq_masks` bands of up to `freq_mask_width`
+/// frequency bins and `n_time_masks` blocks of up to `time_mask_width` frames are zeroed out and the
+/// spectrogram is transformed back into a time series of the original length.
+///
+/// If `time_warp` is set, the spectrogram is additionally warped along the time axis by up to `time_warp` frames before masking.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct SpecAugment {
+    inner: rats_rs::augmenters::SpecAugment,
+}
+
+#[pymethods]
+impl SpecAugment {
+    #[new]
+    #[pyo3(signature = (n_freq_masks, freq_mask_width, n_time_masks, time_mask_width, *, time_warp=None, n_fft=64, hop_length=16, window=&WindowFunction::Hann))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        n_freq_masks: usize,
+        freq_mask_width: usize,
+        n_time_masks: usize,
+        time_mask_width: usize,
+        time_warp: Option<usize>,
+        n_fft: usize,
+        hop_length: usize,
+        window: &WindowFunction,
+    ) -> Self {
+        let params = rats_rs::transforms::stft::StftParams::new(n_fft, hop_length, window.into(), true);
+        SpecAugment {
+            inner: rats_rs::augmenters::SpecAugment::new(
+                n_freq_masks,
+                freq_mask_width,
+                n_time_masks,
+                time_mask_width,
+                time_warp,
+                params,
+            ),
+        }
+    }
+}
+
+wrap_augmentation_functions!(SpecAugment);
+
+/// Augmenter that perturbs the detail coefficients of a discrete wavelet decomposition
+///
+/// Each series is decomposed with the wavelet `wavelet` (e.g. `"haar"`, `"db4"` or `"sym5"`) down to the deepest level
+/// listed in `levels`. Gaussian noise is added to the detail coefficients of every level in `levels` (level 1 being the finest scale),
+/// then the series is reconstructed.
+///
+/// The standard deviation of the noise is `std_dev` times the standard deviation of the coefficients of that level.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct WaveletPerturbation {
+    inner: rats_rs::augmenters::WaveletPerturbation,
+}
+
+#[pymethods]
+impl WaveletPerturbation {
+    #[new]
+    fn new(wavelet: &str, levels: Vec<usize>, std_dev: f64) -> PyResult<Self> {
+        let wavelet = wavelet.parse().map_err(PyValueError::new_err)?;
+        Ok(WaveletPerturbation {
+            inner: rats_rs::augmenters::WaveletPerturbation::new(wavelet, levels, std_dev),
+        })
+    }
+}
+
+wrap_augmentation_functions!(WaveletPerturbation);
+
+/// Augmenter that drops detail coefficients of a discrete wavelet decomposition
+///
+/// Each series is decomposed with the wavelet `wavelet` (e.g. `"haar"`, `"db4"` or `"sym5"`) down to the deepest level
+/// listed in `levels`. Of the detail coefficients of every level in `levels` (level 1 being the finest scale),
+/// a fraction `percentage` of the coefficients is set to zero, then the series is reconstructed.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct WaveletDrop {
+    inner: rats_rs::augmenters::WaveletDrop,
+}
+
+#[pymethods]
+impl WaveletDrop {
+    #[new]
+    fn new(wavelet: &str, levels: Vec<usize>, percentage: f64) -> PyResult<Self> {
+        let wavelet = wavelet.parse().map_err(PyValueError::new_err)?;
+        Ok(WaveletDrop {
+            inner: rats_rs::augmenters::WaveletDrop::new(wavelet, levels, percentage),
+        })
+    }
+}
+
+wrap_augmentation_functions!(WaveletDrop);
+
+/// Enum to specify the kind of filter applied by the `RandomFilter` augmenter
+#[gen_stub_pyclass_enum]
+#[pyclass]
+pub enum FilterType {
+    LowPass,
+    HighPass,
+    BandPass,
+    BandStop,
+}
+
+/// Augmenter that applies a filter with a random cutoff frequency to simulate different sensor bandwidths
+///
+/// For every series, a filter of kind `filter_type` with a cutoff drawn uniformly from `cutoff_range` is applied
+/// forward and backward, so the series is not shifted in time. Band-pass and band-stop filters draw both band edges
+/// from `cutoff_range`. Frequencies are normalized to the Nyquist frequency, so `cutoff_range` must lie within `(0, 1)`.
+///
+/// By default, a Butterworth filter of order `order` is used. When `num_taps` is set, a windowed-sinc FIR filter with
+/// `num_taps` coefficients and the window function `window` is used instead. High-pass and
+/// band-stop filters need an odd `num_taps`.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct RandomFilter {
+    inner: rats_rs::augmenters::RandomFilter,
+}
+
+#[pymethods]
+impl RandomFilter {
+    #[new]
+    #[pyo3(signature = (filter_type, cutoff_range, *, order=4, num_taps=None, window=&WindowFunction::Hamming))]
+    fn new(
+        filter_type: &FilterType,
+        cutoff_range: (f64, f64),
+        order: usize,
+        num_taps: Option<usize>,
+        window: &WindowFunction,
+    ) -> Self {
+        let int_filter_type = match filter_type {
+            FilterType::LowPass => rats_rs::augmenters::FilterType::LowPass,
+            FilterType::HighPass => rats_rs::augmenters::FilterType::HighPass,
+            FilterType::BandPass => rats_rs::augmenters::FilterType::BandPass,
+            FilterType::BandStop => rats_rs::augmenters::FilterType::BandStop,
+        };
+        let design = match num_taps {
+            Some(num_taps) => rats_rs::transforms::filter::FilterDesign::WindowedSinc {
+                num_taps,
+                window: window.into(),
+            },
+            None => rats_rs::transforms::filter::FilterDesign::Butterworth { order },
+        };
+
+        RandomFilter {
+            inner: rats_rs::augmenters::RandomFilter::new(int_filter_type, cutoff_range, design),
+        }
+    }
+}
+
+wrap_augmentation_functions!(RandomFilter);
+
+/// Augmenter that applies `n` augmenters drawn at random, all at the same `magnitude` in `[0, 1]`
+///
+/// The augmenters are drawn uniformly with replacement from all built-in augmenters whose strength
+/// can be set by a magnitude, independently for every series.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct RandAugment {
+    inner: rats_rs::augmenters::RandAugment,
+}
+
+#[pymethods]
+impl RandAugment {
+    #[new]
+    fn new(n: usize, magnitude: f64) -> Self {
+        RandAugment {
+            inner: rats_rs::augmenters::RandAugment::new(n, magnitude),
+        }
+    }
+}
+
+wrap_augmentation_functions!(RandAugment);
+
+/// Augmenter that applies one augmenter drawn at random at a magnitude drawn uniformly from `[0, 1]`
+///
+/// The augmenter is drawn from all built-in augmenters whose strength can be set by a magnitude,
+/// independently for every series.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct TrivialAugment {
+    inner: rats_rs::augmenters::TrivialAugment,
+}
+
+#[pymethods]
+impl TrivialAugment {
+    #[new]
+    fn new() -> Self {
+        TrivialAugment {
+            inner: rats_rs::augmenters::TrivialAugment::new(),
+        }
+    }
+}
+
+wrap_augmentation_functions!(TrivialAugment);
@@ -1,69 +1,206 @@
+/// Evaluates `$body` with `$dataset` bound to the shared `rats_rs::DenseDataset` of a
+/// `DatasetInner`, in whichever precision it is stored
+macro_rules! match_precision {
+    ($inner:expr, $dataset:ident => $body:expr) => {
+        match $inner {
+            $crate::DatasetInner::Single($dataset) => $body,
+            $crate::DatasetInner::Double($dataset) => $body,
+        }
+    };
+}
+
+mod arrays;
 mod augmenters;
 mod transforms;
 mod quality_benchmarking;
-use ndarray::Array2;
-use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+mod loader;
+use arrays::{FloatArray, ReadonlyFloatArray};
+use ndarray::Ix2;
+use numpy::{Element, PyArray2, PyArrayMethods, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3_stub_gen::{define_stub_info_gatherer, PyStubType, TypeInfo};
 use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use rats_rs::{DenseDataset, Float};
+use std::sync::Arc;
 
 /// Holds multiple univariate time series with their labels
 ///
-/// Passed to the `augment_batch` function from augmenters
+/// Passed to the `augment_batch` function from augmenters. The series are stored in the precision
+/// of the array they were created from, float32 or float64, and augmented and transformed in it.
+///
+/// All series have the same length and are stored one after the other in one contiguous buffer,
+/// which `features` returns as a read-only numpy view without copying. Augmenters that keep the
+/// shape of the batch augment the buffer in place, unless a view of it is still alive, in which
+/// case the dataset is copied first and the view keeps the features it was taken of.
 #[gen_stub_pyclass]
 #[pyclass]
 pub struct Dataset {
-    pub(crate) inner: rats_rs::Dataset,
+    pub(crate) inner: DatasetInner,
+}
+
+/// The series of a `Dataset` in single or double precision
+///
+/// The buffer is shared with the numpy views of it, `Arc::make_mut` copies it before it is changed
+/// while a view exists.
+#[derive(Clone)]
+pub(crate) enum DatasetInner {
+    Single(Arc<DenseDataset<f32>>),
+    Double(Arc<DenseDataset<f64>>),
 }
 
+/// Keeps the buffer of a dataset alive for the numpy views of it
+#[pyclass(frozen)]
+struct DatasetBuffer(#[allow(dead_code)] DatasetInner);
+
 impl PyStubType for &mut Dataset {
     fn type_output() -> TypeInfo {
         TypeInfo::with_module("ratspy.Dataset", "ratspy".into())
     }
 }
 
+impl From<DenseDataset<f32>> for DatasetInner {
+    fn from(dataset: DenseDataset<f32>) -> Self {
+        DatasetInner::Single(Arc::new(dataset))
+    }
+}
+
+impl From<DenseDataset> for DatasetInner {
+    fn from(dataset: DenseDataset) -> Self {
+        DatasetInner::Double(Arc::new(dataset))
+    }
+}
+
+impl<T: Float> From<DenseDataset<T>> for Dataset
+where
+    DenseDataset<T>: Into<DatasetInner>,
+{
+    fn from(dataset: DenseDataset<T>) -> Self {
+        Dataset {
+            inner: dataset.into(),
+        }
+    }
+}
+
+/// Copies the series of a `rats_rs::Dataset` into contiguous storage, they must have the same length
+///
+/// Used after augmenters that change the shape of the batch, which augment a nested copy.
+pub(crate) fn to_dense<T: Float>(dataset: &rats_rs::Dataset<T>) -> PyResult<DenseDataset<T>> {
+    let length = dataset.features.first().map_or(0, Vec::len);
+    if dataset.features.iter().any(|x| x.len() != length) {
+        return Err(PyValueError::new_err(
+            "All series of a dataset must have the same length",
+        ));
+    }
+    Ok(DenseDataset::from_dataset(dataset))
+}
+
+impl Dataset {
+    /// The series in double precision, converted if they are stored in single precision
+    ///
+    /// Used by the quality metrics, which compute in double precision anyway.
+    pub(crate) fn to_double(&self) -> rats_rs::Dataset {
+        match &self.inner {
+            DatasetInner::Single(dataset) => rats_rs::Dataset {
+                features: dataset
+                    .all_series()
+                    .map(|x| x.iter().map(|&v| v as f64).collect())
+                    .collect(),
+                labels: dataset.labels.clone(),
+            },
+            DatasetInner::Double(dataset) => dataset.to_dataset(),
+        }
+    }
+
+    /// Number of values of every series
+    pub(crate) fn lengths(&self) -> Vec<usize> {
+        match_precision!(&self.inner, dataset => vec![dataset.series_length(); dataset.n_samples()])
+    }
+}
+
+impl DatasetInner {
+    /// Copies a 2D array with one series per row into contiguous storage
+    fn from_array(features: ReadonlyFloatArray<'_, Ix2>, labels: Vec<String>) -> Self {
+        match features {
+            ReadonlyFloatArray::Single(features) => to_owned_dense(&features, labels).into(),
+            ReadonlyFloatArray::Double(features) => to_owned_dense(&features, labels).into(),
+        }
+    }
+
+    /// Features as a read-only 2D array with one series per row, viewing the buffer of the dataset
+    pub(crate) fn features_array<'py>(&self, py: Python<'py>) -> PyResult<FloatArray<'py, Ix2>> {
+        let buffer = Bound::new(py, DatasetBuffer(self.clone()))?.into_any();
+        // SAFETY: the view keeps `buffer` alive, which holds a reference to the data, and the data
+        // is never changed while it is shared, `Arc::make_mut` copies it instead
+        Ok(match self {
+            DatasetInner::Single(dataset) => FloatArray::Single(read_only(unsafe {
+                PyArray2::borrow_from_array(&dataset.series_view(), buffer)
+            })),
+            DatasetInner::Double(dataset) => FloatArray::Double(read_only(unsafe {
+                PyArray2::borrow_from_array(&dataset.series_view(), buffer)
+            })),
+        })
+    }
+}
+
+/// Copies a 2D array with one series per row into a new `DenseDataset`
+fn to_owned_dense<T: Float + Element>(
+    features: &PyReadonlyArray2<T>,
+    labels: Vec<String>,
+) -> DenseDataset<T> {
+    DenseDataset::from_array2(features.as_array().to_owned(), labels)
+}
+
+/// Clears the writeable flag of a view, so numpy does not change the shared buffer
+fn read_only<T: Element>(array: Bound<'_, PyArray2<T>>) -> Bound<'_, PyArray2<T>> {
+    array.readwrite().make_nonwriteable();
+    array
+}
+
 #[gen_stub_pymethods]
 #[pymethods]
 impl Dataset {
+    /// Creates a dataset from a float32 or float64 array with one series per row
     #[new]
-    fn new<'py>(features: &Bound<'py, PyArray2<f64>>, labels: Vec<String>) -> Self {
-        let features: PyReadonlyArray2<f64> = features.extract().unwrap();
-        let features: Array2<f64> = features.as_array().to_owned().into();
-
-        let features: Vec<Vec<f64>> = features.rows().into_iter().map(|x| x.to_vec()).collect();
-
+    fn new(features: ReadonlyFloatArray<'_, Ix2>, labels: Vec<String>) -> Self {
         Dataset {
-            inner: rats_rs::Dataset { features, labels },
+            inner: DatasetInner::from_array(features, labels),
         }
     }
 
+    /// Features as a 2D array with one series per row, in the precision of the dataset
+    ///
+    /// A read-only view of the dataset without copying it. Use `features.copy()` for an array that
+    /// can be changed.
     #[getter]
-    fn get_features<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
-        let features = self.inner.features.clone();
-        let features = Array2::from_shape_vec(
-            (features.len(), features[0].len()),
-            features.iter().flatten().map(|&x| x).collect(),
-        )
-        .unwrap();
-        features.into_pyarray(py)
+    fn get_features<'py>(&self, py: Python<'py>) -> PyResult<FloatArray<'py, Ix2>> {
+        self.inner.features_array(py)
     }
 
+    /// Replaces the series, the dataset takes over the precision of `features`
     #[setter]
-    fn set_features<'py>(&mut self, features: PyReadonlyArray2<f64>) {
-        let features: Array2<f64> = features.as_array().to_owned().into();
-        let features: Vec<Vec<f64>> = features.rows().into_iter().map(|x| x.to_vec()).collect();
-
-        self.inner.features = features;
+    fn set_features(&mut self, features: ReadonlyFloatArray<'_, Ix2>) {
+        let labels = self.get_labels();
+        self.inner = DatasetInner::from_array(features, labels);
     }
 
     #[getter]
     fn get_labels(&self) -> Vec<String> {
-        self.inner.labels.clone()
+        match_precision!(&self.inner, dataset => dataset.labels.clone())
     }
 
     #[setter]
     fn set_labels(&mut self, labels: Vec<String>) {
-        self.inner.labels = labels;
+        match_precision!(&mut self.inner, dataset => Arc::make_mut(dataset).labels = labels)
+    }
+
+    /// Name of the numpy dtype the series are stored in, `"float32"` or `"float64"`
+    #[getter]
+    fn dtype(&self) -> &'static str {
+        match self.inner {
+            DatasetInner::Single(_) => "float32",
+            DatasetInner::Double(_) => "float64",
+        }
     }
 }
 
@@ -99,10 +236,22 @@ fn ratspy(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<augmenters::Reverse>()?;
     m.add_class::<augmenters::Permutate>()?;
     m.add_class::<transforms::Transforms>()?;
+    m.add_class::<transforms::WindowFunction>()?;
     m.add_class::<augmenters::Drift>()?;
     m.add_class::<augmenters::Convolve>()?;
     m.add_class::<augmenters::ConvolveWindow>()?;
+    m.add_class::<augmenters::ConvolveBoundary>()?;
+    m.add_class::<augmenters::SpecAugment>()?;
+    m.add_class::<augmenters::WaveletPerturbation>()?;
+    m.add_class::<augmenters::WaveletDrop>()?;
+    m.add_class::<augmenters::FilterType>()?;
+    m.add_class::<augmenters::RandomFilter>()?;
+    m.add_class::<augmenters::RandAugment>()?;
+    m.add_class::<augmenters::TrivialAugment>()?;
+    m.add_class::<loader::BatchLoader>()?;
     m.add_class::<quality_benchmarking::QualityBenchmarking>()?;
+    m.add_class::<quality_benchmarking::DistanceMetric>()?;
+    m.add_class::<quality_benchmarking::LocalCost>()?;
     Ok(())
 }
 
@@ -0,0 +1,188 @@
+use crate::augmenters::with_inner;
+use crate::arrays::FloatArray;
+use crate::{Dataset, DatasetInner};
+use ndarray::Ix2;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use rats_rs::augmenters::{BatchSampler, StreamConfig};
+use rats_rs::{random, DenseDataset, Float};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type BatchResult = Result<DatasetInner, String>;
+
+/// Features and labels of one batch
+type PyBatch<'py> = (FloatArray<'py, Ix2>, Vec<String>);
+
+/// Iterable over freshly augmented mini-batches of a dataset, for training loops
+///
+/// Yields `(features, labels)` tuples of a 2D numpy array with one series per row, in the precision
+/// of the dataset, and a list of labels. The augmenters are applied to every batch in their order,
+/// so every epoch sees new augmentations at constant memory. They must not change the number of
+/// series.
+///
+/// A background thread prepares up to `prefetch` batches ahead while the training loop runs, and the
+/// GIL is released while augmenting. With `shuffle`, the series are drawn in a new random order every
+/// epoch. A `seed` makes both the order and the augmentations reproducible, every batch is augmented
+/// with a seed derived from it and the index of the batch. `epochs=None` streams indefinitely. Every
+/// iteration over the loader starts over from the first epoch, also after a `break`.
+///
+/// The loader keeps the features the dataset has when it is created, so changing the dataset
+/// afterwards has no effect.
+/// Use `AugmentationPipeline.torch_dataset` to feed a `torch.utils.data.DataLoader`.
+#[gen_stub_pyclass]
+#[pyclass]
+pub struct BatchLoader {
+    dataset: Arc<DatasetInner>,
+    augmenters: Arc<Vec<Py<PyAny>>>,
+    config: StreamConfig,
+    parallel: bool,
+    receiver: Option<Mutex<Receiver<BatchResult>>>,
+}
+
+/// Augments the batches drawn by `sampler` and sends them to `sender` until the loader is dropped
+///
+/// With a `seed`, every batch is augmented with a seed of its own derived from it and the index of
+/// the batch, like the batches of `AugmentationPipeline::stream`.
+fn augment_batches<T: Float>(
+    dataset: &DenseDataset<T>,
+    augmenters: &[Py<PyAny>],
+    sampler: BatchSampler,
+    seed: Option<u64>,
+    parallel: bool,
+    sender: SyncSender<BatchResult>,
+) where
+    DenseDataset<T>: Into<DatasetInner>,
+{
+    for (index, (_, rows)) in sampler.enumerate() {
+        let mut batch = dataset.select(&rows).to_dataset();
+        let mut augment = || {
+            Python::with_gil(|py| -> PyResult<()> {
+                for augmenter in augmenters.iter() {
+                    with_inner(augmenter.bind(py), |augmenter| {
+                        py.allow_threads(|| augmenter.augment_batch(&mut batch, parallel, false))
+                    })?;
+                }
+                Ok(())
+            })
+        };
+        let result = match seed {
+            Some(seed) => random::with_seed(random::mix(seed, index as u64), augment),
+            None => augment(),
+        }
+        .map_err(|e| e.to_string())
+        .and_then(|_| {
+            let length = batch.features.first().map_or(0, Vec::len);
+            if batch.features.len() != rows.len() {
+                Err("The augmenters must not change the number of series".to_string())
+            } else if batch.features.iter().any(|x| x.len() != length) {
+                Err("All series of a batch must have the same length".to_string())
+            } else {
+                Ok(DenseDataset::from_dataset(&batch).into())
+            }
+        });
+        let failed = result.is_err();
+        // Stops when the loader was dropped or after an error
+        if sender.send(result).is_err() || failed {
+            return;
+        }
+    }
+}
+
+impl BatchLoader {
+    /// Starts the background thread that augments the batches and queues them
+    fn spawn_worker(&self) -> Receiver<BatchResult> {
+        let (sender, receiver) = sync_channel(self.config.prefetch.max(1));
+        let dataset = Arc::clone(&self.dataset);
+        let augmenters = Arc::clone(&self.augmenters);
+        let n = match_precision!(dataset.as_ref(), dataset => dataset.n_samples());
+        let sampler = BatchSampler::new(n, self.config.clone());
+        let seed = self.config.seed;
+        let parallel = self.parallel;
+
+        thread::spawn(move || {
+            match_precision!(dataset.as_ref(), dataset => {
+                augment_batches(dataset, &augmenters, sampler, seed, parallel, sender)
+            })
+        });
+        receiver
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl BatchLoader {
+    #[new]
+    #[pyo3(signature = (dataset, augmenters, batch_size=32, *, shuffle=true, seed=None, drop_last=false, epochs=Some(1), prefetch=2, parallel=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python<'_>,
+        dataset: &Dataset,
+        augmenters: Vec<Py<PyAny>>,
+        batch_size: usize,
+        shuffle: bool,
+        seed: Option<u64>,
+        drop_last: bool,
+        epochs: Option<usize>,
+        prefetch: usize,
+        parallel: bool,
+    ) -> PyResult<Self> {
+        if batch_size == 0 {
+            return Err(PyValueError::new_err("batch_size must be greater than 0"));
+        }
+        for augmenter in &augmenters {
+            with_inner::<f64, _>(augmenter.bind(py), |_| ())?;
+        }
+        Ok(BatchLoader {
+            dataset: Arc::new(dataset.inner.clone()),
+            augmenters: Arc::new(augmenters),
+            config: StreamConfig {
+                batch_size,
+                shuffle,
+                seed,
+                drop_last,
+                per_sample: false,
+                prefetch,
+                epochs,
+            },
+            parallel,
+            receiver: None,
+        })
+    }
+
+    /// Number of batches over all epochs
+    fn __len__(&self) -> PyResult<usize> {
+        let epochs = self
+            .config
+            .epochs
+            .ok_or_else(|| PyTypeError::new_err("BatchLoader without epochs has no length"))?;
+        let n = match_precision!(self.dataset.as_ref(), dataset => dataset.n_samples());
+        let sampler = BatchSampler::new(n, self.config.clone());
+        Ok(sampler.batches_per_epoch() * epochs)
+    }
+
+    /// Starts a new pass over all epochs, stopping the worker of the previous one
+    fn __iter__(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        let receiver = slf.spawn_worker();
+        slf.receiver = Some(Mutex::new(receiver));
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<PyBatch<'py>>> {
+        if self.receiver.is_none() {
+            self.receiver = Some(Mutex::new(self.spawn_worker()));
+        }
+        let receiver = self.receiver.as_ref().unwrap();
+        let batch = match py.allow_threads(|| receiver.lock().unwrap().recv()) {
+            Ok(batch) => batch.map_err(PyValueError::new_err)?,
+            // The worker is done
+            Err(_) => return Ok(None),
+        };
+
+        let features = batch.features_array(py)?;
+        let labels = match_precision!(&batch, batch => batch.labels.clone());
+        Ok(Some((features, labels)))
+    }
+}
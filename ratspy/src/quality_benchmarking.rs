@@ -1,6 +1,29 @@
-use rats_rs::quality_benchmarking::dtw;
+use crate::Dataset;
+use ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
+use rats_rs::quality_benchmarking::knn::KnnClassifier;
+use rats_rs::quality_benchmarking::{
+    acf_distance, distance_matrix, diversity, dtw_constrained, dtw_dependent, dtw_distance,
+    dtw_independent, lb_keogh, lb_kim, mmd_rbf, pairwise_distance_matrix, per_timestep_ks,
+    psd_distance, soft_dtw, soft_dtw_gradient, spectral_ks, DtwConstraint, KsResult,
+};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyclass_enum, gen_stub_pymethods};
+
+/// Enum to specify the metric used to compare two time series
+///
+/// - `Euclidean`: Euclidean distance, needs series of the same length
+/// - `Dtw`: DTW distance, optionally constrained with `window` or `max_slope`
+/// - `Correlation`: one minus the Pearson correlation, needs series of the same length
+#[gen_stub_pyclass_enum]
+#[pyclass]
+#[derive(Clone)]
+pub enum DistanceMetric {
+    Euclidean,
+    Dtw,
+    Correlation,
+}
 
 /// Class to perform quality benchmarking of augmenters
 /// 
@@ -11,6 +34,122 @@ use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
 #[pyclass]
 pub struct QualityBenchmarking;
 
+/// Enum to specify the local cost of matching two points in multivariate DTW
+///
+/// - `Absolute`: sum of absolute differences over the channels
+/// - `Squared`: sum of squared differences over the channels
+/// - `Euclidean`: Euclidean distance between the points
+#[gen_stub_pyclass_enum]
+#[pyclass]
+#[derive(Clone)]
+pub enum LocalCost {
+    Absolute,
+    Squared,
+    Euclidean,
+}
+
+impl From<&LocalCost> for rats_rs::quality_benchmarking::LocalCost {
+    fn from(cost: &LocalCost) -> Self {
+        match cost {
+            LocalCost::Absolute => rats_rs::quality_benchmarking::LocalCost::Absolute,
+            LocalCost::Squared => rats_rs::quality_benchmarking::LocalCost::Squared,
+            LocalCost::Euclidean => rats_rs::quality_benchmarking::LocalCost::Euclidean,
+        }
+    }
+}
+
+/// Checks that two multivariate series have the same number of channels, each of one length
+fn check_channels(a: &[Vec<f64>], b: &[Vec<f64>]) -> PyResult<()> {
+    if a.len() != b.len() {
+        return Err(PyValueError::new_err(
+            "Series must have the same number of channels",
+        ));
+    }
+    for series in [a, b] {
+        if series.iter().any(|channel| channel.len() != series[0].len()) {
+            return Err(PyValueError::new_err(
+                "All channels of a series must have the same length",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn make_constraint(window: Option<usize>, max_slope: Option<f64>) -> PyResult<DtwConstraint> {
+    match (window, max_slope) {
+        (None, None) => Ok(DtwConstraint::None),
+        (Some(window), None) => Ok(DtwConstraint::SakoeChiba(window)),
+        (None, Some(max_slope)) if max_slope > 1.0 => Ok(DtwConstraint::Itakura(max_slope)),
+        (None, Some(_)) => Err(PyValueError::new_err("max_slope must be greater than 1")),
+        (Some(_), Some(_)) => Err(PyValueError::new_err(
+            "Only one of window and max_slope can be given",
+        )),
+    }
+}
+
+fn make_metric(
+    metric: &DistanceMetric,
+    window: Option<usize>,
+    max_slope: Option<f64>,
+) -> PyResult<rats_rs::quality_benchmarking::DistanceMetric> {
+    Ok(match metric {
+        DistanceMetric::Euclidean => rats_rs::quality_benchmarking::DistanceMetric::Euclidean,
+        DistanceMetric::Dtw => {
+            rats_rs::quality_benchmarking::DistanceMetric::Dtw(make_constraint(window, max_slope)?)
+        }
+        DistanceMetric::Correlation => rats_rs::quality_benchmarking::DistanceMetric::Correlation,
+    })
+}
+
+/// Checks that all series of both datasets have the same length
+fn check_lengths(a: &Dataset, b: &Dataset) -> PyResult<()> {
+    let (a, b) = (a.lengths(), b.lengths());
+    let mut lengths = a.iter().chain(b.iter());
+    let length = lengths.clone().next().copied().unwrap_or(0);
+    if lengths.all(|&x| x == length) {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(
+            "All series of both datasets must have the same length",
+        ))
+    }
+}
+
+fn make_classifier(
+    train: &Dataset,
+    test: &Dataset,
+    k: usize,
+    metric: &DistanceMetric,
+    window: Option<usize>,
+    max_slope: Option<f64>,
+) -> PyResult<KnnClassifier> {
+    if k == 0 {
+        return Err(PyValueError::new_err("k must be greater than 0"));
+    }
+    if train.lengths().is_empty() {
+        return Err(PyValueError::new_err("Training set must not be empty"));
+    }
+    if !matches!(metric, DistanceMetric::Dtw) {
+        check_lengths(train, test)?;
+    }
+    Ok(KnnClassifier::new(k, make_metric(metric, window, max_slope)?))
+}
+
+fn ks_tuples(results: Vec<KsResult>) -> Vec<(f64, f64)> {
+    results
+        .into_iter()
+        .map(|r| (r.statistic, r.p_value))
+        .collect()
+}
+
+fn to_pyarray<'py>(py: Python<'py>, matrix: Vec<Vec<f64>>, cols: usize) -> Bound<'py, PyArray2<f64>> {
+    let rows = matrix.len();
+    let flat: Vec<f64> = matrix.into_iter().flatten().collect();
+    Array2::from_shape_vec((rows, cols), flat)
+        .unwrap()
+        .into_pyarray(py)
+}
+
 #[gen_stub_pymethods]
 #[pymethods]
 impl QualityBenchmarking {
@@ -27,11 +166,317 @@ impl QualityBenchmarking {
     /// 
     /// # Returns
     /// 
+    /// * `window` - Optional radius of a Sakoe-Chiba band around the diagonal the warping path must stay in.
+    /// 
+    /// * `max_slope` - Optional maximum slope (> 1) of an Itakura parallelogram the warping path must stay in.
+    /// 
+    /// # Returns
+    /// 
     /// A tuple containing the DTW distance (float) and a list of tuples representing the
     /// optimal path as pairs of indices (int, int).
     #[staticmethod]
-    pub fn compute_dtw(a: Vec<f64>, b: Vec<f64>) -> (f64, Vec<(usize, usize)>) {
-        dtw(&a, &b)
+    #[pyo3(signature = (a, b, *, window=None, max_slope=None))]
+    pub fn compute_dtw(
+        a: Vec<f64>,
+        b: Vec<f64>,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+    ) -> PyResult<(f64, Vec<(usize, usize)>)> {
+        Ok(dtw_constrained(&a, &b, make_constraint(window, max_slope)?))
+    }
+
+    /// Computes only the DTW distance, without the warping path, using linear memory.
+    /// 
+    /// Accepts the same constraints as `compute_dtw`. If `upper_bound` is given, the computation
+    /// is abandoned as soon as the distance is known to exceed it and `inf` is returned.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, *, window=None, max_slope=None, upper_bound=None))]
+    pub fn compute_dtw_distance(
+        a: Vec<f64>,
+        b: Vec<f64>,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        upper_bound: Option<f64>,
+    ) -> PyResult<f64> {
+        Ok(dtw_distance(
+            &a,
+            &b,
+            make_constraint(window, max_slope)?,
+            upper_bound,
+        ))
+    }
+
+    /// LB_Kim lower bound of the DTW distance, computed from the first and last points.
+    #[staticmethod]
+    pub fn lb_kim(a: Vec<f64>, b: Vec<f64>) -> f64 {
+        lb_kim(&a, &b)
+    }
+
+    /// LB_Keogh lower bound of the DTW distance constrained by a Sakoe-Chiba band of `window`.
+    /// 
+    /// Both sequences must have the same length.
+    #[staticmethod]
+    pub fn lb_keogh(a: Vec<f64>, b: Vec<f64>, window: usize) -> PyResult<f64> {
+        if a.len() != b.len() {
+            return Err(PyValueError::new_err(
+                "LB_Keogh needs sequences of the same length",
+            ));
+        }
+        Ok(lb_keogh(&a, &b, window))
+    }
+
+    /// Dependent multivariate DTW (DTW_D), all channels share one warping path.
+    /// 
+    /// The series are given channel first as list[list[float]], i.e. `a[c][t]` is the value of channel `c` at time `t`.
+    /// 
+    /// # Returns
+    /// 
+    /// A tuple containing the DTW distance (float) and the optimal path as pairs of indices (int, int).
+    #[staticmethod]
+    #[pyo3(signature = (a, b, cost=LocalCost::Euclidean, *, window=None, max_slope=None))]
+    pub fn compute_dtw_dependent(
+        a: Vec<Vec<f64>>,
+        b: Vec<Vec<f64>>,
+        cost: LocalCost,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+    ) -> PyResult<(f64, Vec<(usize, usize)>)> {
+        check_channels(&a, &b)?;
+        let constraint = make_constraint(window, max_slope)?;
+        Ok(dtw_dependent(&a, &b, &(&cost).into(), constraint))
+    }
+
+    /// Independent multivariate DTW (DTW_I), the sum of the DTW distances of the channels.
+    /// 
+    /// The series are given channel first like for `compute_dtw_dependent`.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, cost=LocalCost::Absolute, *, window=None, max_slope=None))]
+    pub fn compute_dtw_independent(
+        a: Vec<Vec<f64>>,
+        b: Vec<Vec<f64>>,
+        cost: LocalCost,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+    ) -> PyResult<f64> {
+        check_channels(&a, &b)?;
+        let constraint = make_constraint(window, max_slope)?;
+        Ok(dtw_independent(&a, &b, &(&cost).into(), constraint))
     }
-}
 
+    /// Soft-DTW of two sequences with smoothing parameter `gamma` (> 0).
+    /// 
+    /// Uses the squared difference as local cost and is not normalized. Converges to DTW as `gamma` goes to 0.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, gamma=1.0))]
+    pub fn compute_soft_dtw(a: Vec<f64>, b: Vec<f64>, gamma: f64) -> PyResult<f64> {
+        if gamma <= 0.0 {
+            return Err(PyValueError::new_err("gamma must be greater than 0"));
+        }
+        Ok(soft_dtw(&a, &b, gamma))
+    }
+
+    /// Soft-DTW of two sequences and its gradient with respect to `a`.
+    /// 
+    /// # Returns
+    /// 
+    /// A tuple containing the soft-DTW value (float) and the gradient as a list[float] of the length of `a`.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, gamma=1.0))]
+    pub fn compute_soft_dtw_gradient(a: Vec<f64>, b: Vec<f64>, gamma: f64) -> PyResult<(f64, Vec<f64>)> {
+        if gamma <= 0.0 {
+            return Err(PyValueError::new_err("gamma must be greater than 0"));
+        }
+        Ok(soft_dtw_gradient(&a, &b, gamma))
+    }
+
+    /// Computes the distance between every series of `a` and every series of `b` in parallel.
+    /// 
+    /// Typically used to compare an original dataset with its augmented version.
+    /// 
+    /// # Returns
+    /// 
+    /// A numpy array of shape (len(a), len(b)) whose entry [i, j] is the distance between
+    /// series i of `a` and series j of `b`.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, metric, *, window=None, max_slope=None, parallel=true))]
+    pub fn distance_matrix<'py>(
+        py: Python<'py>,
+        a: &Dataset,
+        b: &Dataset,
+        metric: &DistanceMetric,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let metric = make_metric(metric, window, max_slope)?;
+        let (a, b) = (&a.to_double(), &b.to_double());
+        let matrix = py.allow_threads(|| distance_matrix(a, b, metric, parallel));
+        Ok(to_pyarray(py, matrix, b.features.len()))
+    }
+
+    /// Computes the distance between all pairs of series within one dataset in parallel.
+    /// 
+    /// # Returns
+    /// 
+    /// A symmetric numpy array of shape (len(dataset), len(dataset)) with zeros on the diagonal.
+    #[staticmethod]
+    #[pyo3(signature = (dataset, metric, *, window=None, max_slope=None, parallel=true))]
+    pub fn pairwise_distance_matrix<'py>(
+        py: Python<'py>,
+        dataset: &Dataset,
+        metric: &DistanceMetric,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        let metric = make_metric(metric, window, max_slope)?;
+        let dataset = &dataset.to_double();
+        let matrix = py.allow_threads(|| pairwise_distance_matrix(dataset, metric, parallel));
+        Ok(to_pyarray(py, matrix, dataset.features.len()))
+    }
+
+    /// Maximum mean discrepancy between two datasets with a Gaussian RBF kernel.
+    /// 
+    /// Every series is treated as one point of its dataset. If no `sigma` is given, the median
+    /// pairwise distance of all series is used. Returns 0 for identical datasets.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, sigma=None, parallel=true))]
+    pub fn mmd(
+        py: Python<'_>,
+        a: &Dataset,
+        b: &Dataset,
+        sigma: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<f64> {
+        check_lengths(a, b)?;
+        if sigma.is_some_and(|sigma| sigma <= 0.0) {
+            return Err(PyValueError::new_err("sigma must be greater than 0"));
+        }
+        let (a, b) = (&a.to_double(), &b.to_double());
+        Ok(py.allow_threads(|| mmd_rbf(a, b, sigma, parallel)))
+    }
+
+    /// Kolmogorov-Smirnov test between the values of both datasets at every time step.
+    /// 
+    /// # Returns
+    /// 
+    /// A list of (statistic, p-value) tuples, one for each time step.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, parallel=true))]
+    pub fn per_timestep_ks(
+        py: Python<'_>,
+        a: &Dataset,
+        b: &Dataset,
+        parallel: bool,
+    ) -> PyResult<Vec<(f64, f64)>> {
+        check_lengths(a, b)?;
+        let (a, b) = (&a.to_double(), &b.to_double());
+        Ok(ks_tuples(py.allow_threads(|| per_timestep_ks(a, b, parallel))))
+    }
+
+    /// Kolmogorov-Smirnov test between the FFT magnitudes of both datasets at every frequency bin.
+    /// 
+    /// # Returns
+    /// 
+    /// A list of (statistic, p-value) tuples, one for each non-negative frequency.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, parallel=true))]
+    pub fn spectral_ks(
+        py: Python<'_>,
+        a: &Dataset,
+        b: &Dataset,
+        parallel: bool,
+    ) -> PyResult<Vec<(f64, f64)>> {
+        check_lengths(a, b)?;
+        let (a, b) = (&a.to_double(), &b.to_double());
+        Ok(ks_tuples(py.allow_threads(|| spectral_ks(a, b, parallel))))
+    }
+
+    /// Euclidean distance between the mean autocorrelation functions (lags 1 to `max_lag`) of two datasets.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, max_lag, parallel=true))]
+    pub fn acf_distance(
+        py: Python<'_>,
+        a: &Dataset,
+        b: &Dataset,
+        max_lag: usize,
+        parallel: bool,
+    ) -> f64 {
+        let (a, b) = (&a.to_double(), &b.to_double());
+        py.allow_threads(|| acf_distance(a, b, max_lag, parallel))
+    }
+
+    /// Total variation distance between the normalized mean power spectral densities of two datasets.
+    /// 
+    /// Ranges from 0 (same distribution of power over the frequencies) to 1.
+    #[staticmethod]
+    #[pyo3(signature = (a, b, parallel=true))]
+    pub fn psd_distance(py: Python<'_>, a: &Dataset, b: &Dataset, parallel: bool) -> PyResult<f64> {
+        check_lengths(a, b)?;
+        let (a, b) = (&a.to_double(), &b.to_double());
+        Ok(py.allow_threads(|| psd_distance(a, b, parallel)))
+    }
+
+    /// Diversity of a dataset, the mean distance between all pairs of its series.
+    #[staticmethod]
+    #[pyo3(signature = (dataset, metric, *, window=None, max_slope=None, parallel=true))]
+    pub fn diversity(
+        py: Python<'_>,
+        dataset: &Dataset,
+        metric: &DistanceMetric,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<f64> {
+        if !matches!(metric, DistanceMetric::Dtw) {
+            check_lengths(dataset, dataset)?;
+        }
+        let metric = make_metric(metric, window, max_slope)?;
+        let dataset = &dataset.to_double();
+        Ok(py.allow_threads(|| diversity(dataset, metric, parallel)))
+    }
+
+    /// Predicts the labels of the test set with a k-nearest-neighbor classifier trained on `train`.
+    /// 
+    /// Ties in the vote are broken in favor of the label whose closest neighbor is nearest. The label
+    /// is None for test series without a training series at a finite distance, e.g. empty ones.
+    #[staticmethod]
+    #[pyo3(signature = (train, test, k=1, metric=DistanceMetric::Euclidean, *, window=None, max_slope=None, parallel=true))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn knn_predict(
+        py: Python<'_>,
+        train: &Dataset,
+        test: &Dataset,
+        k: usize,
+        metric: DistanceMetric,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<Vec<Option<String>>> {
+        let classifier = make_classifier(train, test, k, &metric, window, max_slope)?;
+        let (train, test) = (&train.to_double(), &test.to_double());
+        Ok(py.allow_threads(|| classifier.predict_batch(train, test, parallel)))
+    }
+
+    /// Accuracy of a k-nearest-neighbor classifier trained on `train` on the test set.
+    /// 
+    /// Train on an augmented training set and compare with the accuracy on the original training set
+    /// to judge an augmentation.
+    #[staticmethod]
+    #[pyo3(signature = (train, test, k=1, metric=DistanceMetric::Euclidean, *, window=None, max_slope=None, parallel=true))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn knn_accuracy(
+        py: Python<'_>,
+        train: &Dataset,
+        test: &Dataset,
+        k: usize,
+        metric: DistanceMetric,
+        window: Option<usize>,
+        max_slope: Option<f64>,
+        parallel: bool,
+    ) -> PyResult<f64> {
+        let classifier = make_classifier(train, test, k, &metric, window, max_slope)?;
+        let (train, test) = (&train.to_double(), &test.to_double());
+        Ok(py.allow_threads(|| classifier.accuracy(train, test, parallel)))
+    }
+}
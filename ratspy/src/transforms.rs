@@ -1,6 +1,27 @@
 use crate::Dataset;
 use pyo3::prelude::*;
-use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyclass_enum, gen_stub_pymethods};
+
+/// Enum to specify the window function applied to every frame of the short-time Fourier transform
+#[gen_stub_pyclass_enum]
+#[pyclass]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl From<&WindowFunction> for rats_rs::transforms::stft::WindowFunction {
+    fn from(window: &WindowFunction) -> Self {
+        match window {
+            WindowFunction::Rectangular => rats_rs::transforms::stft::WindowFunction::Rectangular,
+            WindowFunction::Hann => rats_rs::transforms::stft::WindowFunction::Hann,
+            WindowFunction::Hamming => rats_rs::transforms::stft::WindowFunction::Hamming,
+            WindowFunction::Blackman => rats_rs::transforms::stft::WindowFunction::Blackman,
+        }
+    }
+}
 
 /// Class containing various frequency domain transforms for time series data.
 /// 
@@ -19,15 +40,17 @@ impl Transforms {
     /// storing the result as interleaved real and imaginary parts: [re0, im0, re1, im1, ...]
     #[staticmethod]
     pub fn fft(dataset: &Dataset, parallel: bool) -> Dataset {
-        let result = rats_rs::transforms::fastfourier::dataset_fft(&dataset.inner, parallel);
-        Dataset { inner: result }
+        match_precision!(&dataset.inner, dataset => {
+            Dataset::from(rats_rs::transforms::fastfourier::dense_fft(dataset, parallel))
+        })
     }
 
     /// Reconstructs each time series from its frequency domain representation (interleaved real/imag parts).
     #[staticmethod]
     pub fn ifft(dataset: &Dataset, parallel: bool) -> Dataset {
-        let result = rats_rs::transforms::fastfourier::dataset_ifft(&dataset.inner, parallel);
-        Dataset { inner: result }
+        match_precision!(&dataset.inner, dataset => {
+            Dataset::from(rats_rs::transforms::fastfourier::dense_ifft(dataset, parallel))
+        })
     }
 
     /// Discrete Cosine Transform (DCT-II) for time series data.
@@ -35,16 +58,18 @@ impl Transforms {
     /// Converts each real-valued time series in the dataset into DCT coefficients (real, frequency representation)
     #[staticmethod]
     pub fn dct(dataset: &Dataset, parallel: bool) -> Dataset {
-        let result = rats_rs::transforms::dct::dataset_dct(&dataset.inner, parallel);
-        Dataset { inner: result }
+        match_precision!(&dataset.inner, dataset => {
+            Dataset::from(rats_rs::transforms::dct::dense_dct(dataset, parallel))
+        })
     }
 
     /// Inverse Discrete Cosine Transform (DCT-III) for time series data.
     /// Reconstructs each time series from its DCT coefficients, recovering the original signal.
     #[staticmethod]
     pub fn idct(dataset: &Dataset, parallel: bool) -> Dataset {
-        let result = rats_rs::transforms::dct::dataset_idct(&dataset.inner, parallel);
-        Dataset { inner: result }
+        match_precision!(&dataset.inner, dataset => {
+            Dataset::from(rats_rs::transforms::dct::dense_idct(dataset, parallel))
+        })
     }
 
     /// Computes maximum absolute difference between two Datasets and check if all differences are within a tolerance.
@@ -55,8 +80,8 @@ impl Transforms {
         tolerance: f64,
     ) -> (f64, bool) {
         rats_rs::transforms::accuracy::compare_datasets_within_tolerance(
-            &original.inner,
-            &reconstructed.inner,
+            &original.to_double(),
+            &reconstructed.to_double(),
             tolerance,
         )
     }
@@ -92,6 +92,99 @@ class TestRATSPy(unittest.TestCase):
         self.assertGreaterEqual(dtw_distance, 0.0)
         self.assertEqual(len(path), len(dataset.features[0] - 1))
 
+    def test_features_view(self):
+        dataset = rp.Dataset(self.features, self.labels)
+        view = dataset.features
+        self.assertFalse(view.flags.writeable)
+        self.assertTrue(np.shares_memory(view, dataset.features))
+
+        # The view keeps the features it was taken of
+        rp.Scaling(min=2.0, max=2.0).augment_batch(dataset, parallel=True)
+        np.testing.assert_array_equal(view, self.features)
+        np.testing.assert_array_equal(dataset.features, 2.0 * self.features)
+
+    def test_augment_inplace(self):
+        features = self.features.copy()
+        rp.Scaling(min=2.0, max=2.0).augment_inplace(features, parallel=True)
+        np.testing.assert_array_equal(features, 2.0 * self.features)
+
+        series = self.features[0].copy()
+        rp.Reverse().augment_inplace(series)
+        np.testing.assert_array_equal(series, self.features[0][::-1])
+
+        with self.assertRaises(ValueError):
+            rp.Crop(size=2).augment_inplace(self.features.copy())
+
+    def test_batch_loader(self):
+        dataset = rp.Dataset(self.features, self.labels)
+        pipeline = rp.AugmentationPipeline() + rp.Scaling(min=2.0, max=2.0)
+
+        loader = pipeline.batches(dataset, batch_size=3, seed=0, epochs=2)
+        batches = list(loader)
+
+        per_epoch = (len(self.features) + 2) // 3
+        self.assertEqual(len(batches), 2 * per_epoch)
+        features, labels = batches[0]
+        self.assertEqual(features.shape[1], self.features.shape[1])
+        self.assertEqual(len(labels), features.shape[0])
+        # The dataset itself is left untouched
+        np.testing.assert_array_equal(dataset.features, self.features)
+
+    def test_batch_loader_restarts(self):
+        dataset = rp.Dataset(self.features, self.labels)
+        pipeline = rp.AugmentationPipeline() + rp.Scaling(min=2.0, max=2.0)
+        loader = pipeline.batches(dataset, batch_size=3, seed=0, epochs=2)
+
+        first = [labels for _, labels in loader]
+        second = [labels for _, labels in loader]
+        self.assertEqual(len(first), len(loader))
+        self.assertEqual(first, second)
+
+        # A loop that was left early starts over from the first batch
+        for _ in loader:
+            break
+        self.assertEqual([labels for _, labels in loader], first)
+
+    def test_batch_loader_seeded_augmentations(self):
+        dataset = rp.Dataset(self.features, self.labels)
+        pipeline = rp.AugmentationPipeline() + rp.Jittering(standard_deviation=0.1)
+
+        def features(seed):
+            loader = pipeline.batches(dataset, batch_size=3, seed=seed, epochs=2)
+            return [features for features, _ in loader]
+
+        first = features(0)
+        for a, b in zip(first, features(0)):
+            np.testing.assert_array_equal(a, b)
+        self.assertFalse(all(np.array_equal(a, b) for a, b in zip(first, features(1))))
+
+    def test_float32(self):
+        features = self.features.astype(np.float32)
+        dataset = rp.Dataset(features, self.labels)
+        self.assertEqual(dataset.dtype, "float32")
+
+        rp.Scaling(min=2.0, max=2.0).augment_batch(dataset, parallel=True)
+        self.assertEqual(dataset.features.dtype, np.float32)
+        np.testing.assert_array_equal(dataset.features, 2.0 * features)
+
+        inverse = rp.Transforms.ifft(rp.Transforms.fft(dataset, parallel=True), parallel=True)
+        self.assertEqual(inverse.dtype, "float32")
+        _, is_valid = rp.Transforms.compare_within_tolerance(dataset, inverse, tolerance=1e-4)
+        self.assertTrue(is_valid)
+
+        series = rp.Reverse().augment_one(features[0])
+        self.assertEqual(series.dtype, np.float32)
+        rp.Reverse().augment_inplace(features)
+        np.testing.assert_array_equal(features[:, 0], self.features[:, -1])
+
+        loader = (rp.AugmentationPipeline() + rp.Jittering(standard_deviation=0.1)).batches(
+            dataset, batch_size=2
+        )
+        self.assertTrue(all(batch.dtype == np.float32 for batch, _ in loader))
+
+        with self.assertRaises(TypeError):
+            rp.Dataset(self.features.astype(np.int64), self.labels)
+
 
 if __name__ == "__main__":
     unittest.main(exit=False)